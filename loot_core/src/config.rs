@@ -114,7 +114,7 @@ impl Config {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().map_or(false, |ext| ext == "toml") {
+            if path.extension().is_some_and(|ext| ext == "toml") {
                 let content = Self::read_file_with_context(&path)?;
                 let wrapper: BaseTypesWrapper = Self::parse_toml_with_context(&content, &path)?;
                 for bt in wrapper.base_types {
@@ -139,7 +139,7 @@ impl Config {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().map_or(false, |ext| ext == "toml") {
+            if path.extension().is_some_and(|ext| ext == "toml") {
                 let content = Self::read_file_with_context(&path)?;
                 let wrapper: AffixesWrapper = Self::parse_toml_with_context(&content, &path)?;
                 for affix in wrapper.affixes {
@@ -164,7 +164,7 @@ impl Config {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().map_or(false, |ext| ext == "toml") {
+            if path.extension().is_some_and(|ext| ext == "toml") {
                 let content = Self::read_file_with_context(&path)?;
                 let wrapper: AffixPoolsWrapper = Self::parse_toml_with_context(&content, &path)?;
                 for pool in wrapper.pools {
@@ -189,7 +189,7 @@ impl Config {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().map_or(false, |ext| ext == "toml") {
+            if path.extension().is_some_and(|ext| ext == "toml") {
                 let content = Self::read_file_with_context(&path)?;
                 let wrapper: CurrenciesWrapper = Self::parse_toml_with_context(&content, &path)?;
                 for currency in wrapper.currencies {
@@ -217,7 +217,7 @@ impl Config {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().map_or(false, |ext| ext == "toml") {
+            if path.extension().is_some_and(|ext| ext == "toml") {
                 let content = Self::read_file_with_context(&path)?;
                 let file_config: UniqueFileConfig = Self::parse_toml_with_context(&content, &path)?;
 
@@ -491,6 +491,16 @@ pub struct AffixConfig {
     /// Optional attribute scaling — when present, effective value = rolled_value * (attribute / per)
     #[serde(default)]
     pub scaling: Option<ScalingConfig>,
+    /// Influence an item needs for this affix to roll (None = any item)
+    #[serde(default)]
+    pub influence: Option<String>,
+}
+
+impl AffixConfig {
+    /// Whether this affix can roll on an item with the given influence
+    pub fn allows_influence(&self, influence: Option<&str>) -> bool {
+        self.influence.is_none() || self.influence.as_deref() == influence
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -205,10 +205,13 @@ fn can_add_any_specific_affix(
             return false;
         }
 
-        // Check if allowed for item class
+        // Check if allowed for item class and influence
         if !affix.allowed_classes.is_empty() && !affix.allowed_classes.contains(&item.class) {
             return false;
         }
+        if !affix.allows_influence(item.influence.as_deref()) {
+            return false;
+        }
 
         // Check if there's a slot for this affix type
         match affix.affix_type {
@@ -271,13 +274,6 @@ fn add_random_affix(
     pools: &[String],
     rng: &mut ChaCha8Rng,
 ) -> bool {
-    let existing: Vec<String> = item
-        .prefixes
-        .iter()
-        .chain(item.suffixes.iter())
-        .map(|m| m.affix_id.clone())
-        .collect();
-
     let (can_prefix, can_suffix) = if let Some(rarity) = generator.config().get_rarity(&item.rarity)
     {
         (
@@ -306,10 +302,7 @@ fn add_random_affix(
         (false, false) => return false,
     };
 
-    let item_level = item.requirements.level as u32;
-    if let Some(modifier) = generator.roll_affix_from_pools(
-        item.class, &item.tags, affix_type, &existing, pools, item_level, rng,
-    ) {
+    if let Some(modifier) = generator.roll_affix_for_item(item, affix_type, pools, rng) {
         match affix_type {
             AffixType::Prefix => item.prefixes.push(modifier),
             AffixType::Suffix => item.suffixes.push(modifier),
@@ -328,9 +321,7 @@ fn add_random_affix(
         };
 
         if can_other {
-            if let Some(modifier) = generator.roll_affix_from_pools(
-                item.class, &item.tags, other_type, &existing, pools, item_level, rng,
-            ) {
+            if let Some(modifier) = generator.roll_affix_for_item(item, other_type, pools, rng) {
                 match other_type {
                     AffixType::Prefix => item.prefixes.push(modifier),
                     AffixType::Suffix => item.suffixes.push(modifier),
@@ -371,10 +362,13 @@ fn add_specific_affix_from_set(
                 return false;
             }
 
-            // Check if allowed for item class
+            // Check if allowed for item class and influence
             if !affix.allowed_classes.is_empty() && !affix.allowed_classes.contains(&item.class) {
                 return false;
             }
+            if !affix.allows_influence(item.influence.as_deref()) {
+                return false;
+            }
 
             // Check if there's a slot
             if let Some(rarity) = generator.config().get_rarity(&item.rarity) {
@@ -428,7 +422,7 @@ fn add_affix_by_id(
         .get(affix_id)
         .ok_or_else(|| CurrencyError::AffixNotFound(affix_id.to_string()))?;
 
-    let item_level = item.requirements.level as u32;
+    let item_level = item.item_level;
 
     // Select tier
     let selected_tier = if let Some(specific_tier) = tier {
@@ -547,49 +541,18 @@ fn reroll_random_affix(
 
     let idx = rng.gen_range(0..total);
     let is_prefix = idx < prefix_count;
-    let item_level = item.requirements.level as u32;
 
     if is_prefix {
         item.prefixes.remove(idx);
 
-        let existing_ids: Vec<String> = item
-            .prefixes
-            .iter()
-            .chain(item.suffixes.iter())
-            .map(|m| m.affix_id.clone())
-            .collect();
-
-        if let Some(modifier) = generator.roll_affix_from_pools(
-            item.class,
-            &item.tags,
-            AffixType::Prefix,
-            &existing_ids,
-            pools,
-            item_level,
-            rng,
-        ) {
+        if let Some(modifier) = generator.roll_affix_for_item(item, AffixType::Prefix, pools, rng) {
             item.prefixes.push(modifier);
         }
     } else {
         let removed_idx = idx - prefix_count;
         item.suffixes.remove(removed_idx);
 
-        let existing_ids: Vec<String> = item
-            .prefixes
-            .iter()
-            .chain(item.suffixes.iter())
-            .map(|m| m.affix_id.clone())
-            .collect();
-
-        if let Some(modifier) = generator.roll_affix_from_pools(
-            item.class,
-            &item.tags,
-            AffixType::Suffix,
-            &existing_ids,
-            pools,
-            item_level,
-            rng,
-        ) {
+        if let Some(modifier) = generator.roll_affix_for_item(item, AffixType::Suffix, pools, rng) {
             item.suffixes.push(modifier);
         }
     }
@@ -597,6 +560,24 @@ fn reroll_random_affix(
     Ok(())
 }
 
+/// Reroll the numeric values of every affix within its rolled tier range
+///
+/// The set of affixes and their tiers is unchanged.
+pub(crate) fn reroll_modifier_values(item: &mut Item, rng: &mut ChaCha8Rng) {
+    for modifier in item.prefixes.iter_mut().chain(item.suffixes.iter_mut()) {
+        let (min, max) = (
+            modifier.tier_min.min(modifier.tier_max),
+            modifier.tier_min.max(modifier.tier_max),
+        );
+        modifier.value = rng.gen_range(min..=max);
+
+        if let Some((max_lo, max_hi)) = modifier.tier_max_value {
+            let (lo, hi) = (max_lo.min(max_hi), max_lo.max(max_hi));
+            modifier.value_max = Some(rng.gen_range(lo..=hi));
+        }
+    }
+}
+
 /// Try to transform item into a unique based on recipes
 fn try_unique_transformation(
     generator: &Generator,
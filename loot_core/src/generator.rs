@@ -1,5 +1,5 @@
 use crate::config::{AffixConfig, BaseTypeConfig, Config, CurrencyConfig, UniqueConfig};
-use crate::currency::{apply_currency, CurrencyError};
+use crate::currency::{apply_currency, reroll_modifier_values, CurrencyError};
 use crate::item::{Item, Modifier};
 use crate::storage::Operation;
 use crate::types::*;
@@ -19,6 +19,8 @@ pub enum GeneratorError {
         unique_id: String,
         base_type: String,
     },
+    /// No currency turns a normal item into the requested rarity
+    UnreachableRarity(String),
     /// A currency operation failed during reconstruction
     Currency(CurrencyError),
 }
@@ -42,6 +44,9 @@ impl fmt::Display for GeneratorError {
                     unique_id, base_type
                 )
             }
+            GeneratorError::UnreachableRarity(id) => {
+                write!(f, "no currency upgrades a normal item to rarity '{}'", id)
+            }
             GeneratorError::Currency(e) => write!(f, "currency error: {}", e),
         }
     }
@@ -62,6 +67,17 @@ impl From<CurrencyError> for GeneratorError {
     }
 }
 
+/// Options for generating or reconstructing an item
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenerateOptions {
+    /// Item level gating affix tiers; `None` uses the base type's level
+    /// requirement
+    pub item_level: Option<u32>,
+    /// Influence adding its configured affixes to the pool for every currency
+    /// applied later
+    pub influence: Option<String>,
+}
+
 /// Item generator using seeded RNG for deterministic results
 pub struct Generator {
     config: Config,
@@ -82,7 +98,24 @@ impl Generator {
     }
 
     /// Generate a normal item from a base type with the given seed
+    ///
+    /// The item level defaults to the base type's level requirement.
     pub fn generate(&self, base_type_id: &str, seed: u64) -> Result<Item, GeneratorError> {
+        self.generate_with_options(base_type_id, seed, &GenerateOptions::default())
+    }
+
+    /// Generate a normal item from a base type with an item level and/or
+    /// influence
+    ///
+    /// Only affix tiers whose `min_ilvl` is at or below the item level can roll,
+    /// both now and for any currency applied later. Influence doesn't change
+    /// the base rolls.
+    pub fn generate_with_options(
+        &self,
+        base_type_id: &str,
+        seed: u64,
+        options: &GenerateOptions,
+    ) -> Result<Item, GeneratorError> {
         let base = self
             .config
             .base_types
@@ -91,6 +124,8 @@ impl Generator {
 
         let mut rng = Self::make_rng(seed);
         let mut item = Item::new_normal(base, seed);
+        item.item_level = options.item_level.unwrap_or(base.requirements.level);
+        item.influence = options.influence.clone();
 
         // Roll implicit if present
         if let Some(ref implicit_cfg) = base.implicit {
@@ -127,6 +162,42 @@ impl Generator {
         Ok(item)
     }
 
+    /// Generate an item directly at a rarity (e.g. "normal", "magic", "rare")
+    ///
+    /// Applies the rarity currency that upgrades a normal item to `rarity` with
+    /// random affixes, so the result is deterministic from the seed and
+    /// reconstructs from its recorded operations like any crafted item.
+    pub fn generate_at_rarity(
+        &self,
+        base_type_id: &str,
+        seed: u64,
+        rarity: &str,
+    ) -> Result<Item, GeneratorError> {
+        let item = self.generate(base_type_id, seed)?;
+        if item.rarity == rarity {
+            return Ok(item);
+        }
+
+        let mut upgrades: Vec<&CurrencyConfig> = self
+            .config
+            .currencies
+            .values()
+            .filter(|c| {
+                c.requires.rarities.iter().any(|r| r == &item.rarity)
+                    && c.effects.set_rarity.as_deref() == Some(rarity)
+                    && c.effects.add_affixes.is_some()
+                    && c.effects.add_specific_affix.is_empty()
+            })
+            .collect();
+        // HashMap order is unstable; pick by ID so the seed alone decides the item
+        upgrades.sort_by(|a, b| a.id.cmp(&b.id));
+        let currency = upgrades
+            .first()
+            .ok_or_else(|| GeneratorError::UnreachableRarity(rarity.to_string()))?;
+
+        Ok(self.apply_currency(&item, &currency.id)?)
+    }
+
     /// Apply a currency to an item by currency ID.
     ///
     /// Returns a new item with the currency applied. The original item is not modified.
@@ -151,6 +222,24 @@ impl Generator {
         Ok(new_item)
     }
 
+    /// Reroll the numeric value of every affix within its tier range
+    ///
+    /// Which affixes exist (and their tiers) is unchanged. The new values are
+    /// derived from the item's seed and this operation's index, so
+    /// reconstruction reproduces them exactly.
+    pub fn reroll_values(&self, item: &Item) -> Item {
+        let mut new_item = item.clone();
+        let mut rng = Self::reroll_values_rng(item.seed, item.operations.len());
+        reroll_modifier_values(&mut new_item, &mut rng);
+        new_item.operations.push(Operation::RerollValues);
+        new_item
+    }
+
+    /// RNG for a RerollValues operation, independent of the currency RNG stream
+    fn reroll_values_rng(seed: u64, op_index: usize) -> ChaCha8Rng {
+        Self::make_rng(seed ^ (op_index as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
+
     /// Check if a currency can be applied to an item
     pub fn can_apply_currency(&self, item: &Item, currency_id: &str) -> bool {
         let Some(currency) = self.config.currencies.get(currency_id) else {
@@ -186,18 +275,32 @@ impl Generator {
     }
 
     /// Reconstruct an item from its base type, seed, and operations
+    ///
+    /// The item level defaults to the base type's level requirement.
     pub fn reconstruct(
         &self,
         base_type_id: &str,
         seed: u64,
         operations: &[Operation],
     ) -> Result<Item, GeneratorError> {
-        let mut item = self.generate(base_type_id, seed)?;
+        self.reconstruct_with_options(base_type_id, seed, &GenerateOptions::default(), operations)
+    }
+
+    /// Reconstruct an item from its base type, seed, generate options, and
+    /// operations
+    pub fn reconstruct_with_options(
+        &self,
+        base_type_id: &str,
+        seed: u64,
+        options: &GenerateOptions,
+        operations: &[Operation],
+    ) -> Result<Item, GeneratorError> {
+        let mut item = self.generate_with_options(base_type_id, seed, options)?;
 
         // Replay operations (but don't record them again)
         let mut rng = self.replay_rng(&item);
 
-        for op in operations {
+        for (op_index, op) in operations.iter().enumerate() {
             match op {
                 Operation::Currency(currency_id) => {
                     if let Some(currency) = self.config.currencies.get(currency_id) {
@@ -206,6 +309,10 @@ impl Generator {
                         let _ = apply_currency(self, &mut item, currency, &mut rng);
                     }
                 }
+                Operation::RerollValues => {
+                    let mut reroll_rng = Self::reroll_values_rng(seed, op_index);
+                    reroll_modifier_values(&mut item, &mut reroll_rng);
+                }
             }
         }
 
@@ -242,6 +349,8 @@ impl Generator {
         // We need to actually apply each currency to advance the RNG correctly
         if let Some(base) = base {
             let mut replay_item = Item::new_normal(base, item.seed);
+            replay_item.item_level = item.item_level;
+            replay_item.influence = item.influence.clone();
 
             // Re-roll initial values
             if let Some(ref implicit_cfg) = base.implicit {
@@ -273,13 +382,17 @@ impl Generator {
                 }
             }
 
-            for op in &item.operations {
+            for (op_index, op) in item.operations.iter().enumerate() {
                 match op {
                     Operation::Currency(currency_id) => {
                         if let Some(currency) = self.config.currencies.get(currency_id) {
                             let _ = apply_currency(self, &mut replay_item, currency, &mut rng);
                         }
                     }
+                    Operation::RerollValues => {
+                        let mut reroll_rng = Self::reroll_values_rng(item.seed, op_index);
+                        reroll_modifier_values(&mut replay_item, &mut reroll_rng);
+                    }
                 }
             }
         }
@@ -342,6 +455,8 @@ impl Generator {
     }
 
     /// Roll a random affix for an item
+    ///
+    /// Influence-only affixes are never rolled; see `roll_affix_for_item`.
     pub fn roll_affix(
         &self,
         class: ItemClass,
@@ -370,6 +485,9 @@ impl Generator {
     }
 
     /// Roll a random affix for an item, filtered by affix pools
+    ///
+    /// Influence-only affixes are never rolled; see `roll_affix_for_item`.
+    #[allow(clippy::too_many_arguments)]
     pub fn roll_affix_from_pools(
         &self,
         class: ItemClass,
@@ -385,8 +503,49 @@ impl Generator {
             .into_iter()
             .filter(|a| !existing_affix_ids.contains(&a.id))
             .filter(|a| Self::has_matching_tag(a, item_tags))
+            .filter(|a| a.allows_influence(None))
             .collect();
 
+        self.pick_affix(valid_affixes, item_tags, item_level, rng)
+    }
+
+    /// Roll a random affix for `item`, filtered by affix pools
+    ///
+    /// Class, tags, item level and influence come from the item, and affixes
+    /// it already has are skipped.
+    pub fn roll_affix_for_item(
+        &self,
+        item: &Item,
+        affix_type: AffixType,
+        pools: &[String],
+        rng: &mut ChaCha8Rng,
+    ) -> Option<Modifier> {
+        let influence = item.influence.as_deref();
+        let valid_affixes: Vec<_> = self
+            .get_valid_affixes_from_pools(item.class, affix_type, pools)
+            .into_iter()
+            .filter(|a| {
+                !item
+                    .prefixes
+                    .iter()
+                    .chain(item.suffixes.iter())
+                    .any(|m| m.affix_id == a.id)
+            })
+            .filter(|a| Self::has_matching_tag(a, &item.tags))
+            .filter(|a| a.allows_influence(influence))
+            .collect();
+
+        self.pick_affix(valid_affixes, &item.tags, item.item_level, rng)
+    }
+
+    /// Pick one affix by spawn weight, then a tier the item level allows
+    fn pick_affix(
+        &self,
+        valid_affixes: Vec<&AffixConfig>,
+        item_tags: &[Tag],
+        item_level: u32,
+        rng: &mut ChaCha8Rng,
+    ) -> Option<Modifier> {
         if valid_affixes.is_empty() {
             return None;
         }
@@ -472,13 +631,6 @@ impl Generator {
         };
 
         for _ in 0..affix_count {
-            let existing: Vec<String> = item
-                .prefixes
-                .iter()
-                .chain(item.suffixes.iter())
-                .map(|m| m.affix_id.clone())
-                .collect();
-
             let can_prefix = item.prefixes.len() < rarity.max_prefixes;
             let can_suffix = item.suffixes.len() < rarity.max_suffixes;
 
@@ -495,10 +647,7 @@ impl Generator {
                 (false, false) => break,
             };
 
-            let item_level = item.requirements.level;
-            if let Some(modifier) = self.roll_affix(
-                item.class, &item.tags, affix_type, &existing, item_level, rng,
-            ) {
+            if let Some(modifier) = self.roll_affix_for_item(item, affix_type, &[], rng) {
                 match affix_type {
                     AffixType::Prefix => item.prefixes.push(modifier),
                     AffixType::Suffix => item.suffixes.push(modifier),
@@ -634,3 +783,270 @@ impl Generator {
         self.config.currencies.get(id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn make_generator() -> Generator {
+        let config = Config::load_from_dir(Path::new("../config")).unwrap();
+        Generator::new(config)
+    }
+
+    fn at_level(item_level: u32) -> GenerateOptions {
+        GenerateOptions {
+            item_level: Some(item_level),
+            ..Default::default()
+        }
+    }
+
+    /// Highest `min_ilvl` among the tiers rolled on an item
+    fn max_rolled_tier_ilvl(generator: &Generator, item: &Item) -> u32 {
+        item.prefixes
+            .iter()
+            .chain(item.suffixes.iter())
+            .filter_map(|m| {
+                let affix = generator.config().affixes.get(&m.affix_id)?;
+                affix.tiers.iter().find(|t| t.tier == m.tier)
+            })
+            .map(|t| t.min_ilvl)
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_modifiers_by_scope() {
+        let generator = make_generator();
+        let mut item = generator.generate("iron_sword", 7).unwrap();
+        let modifier = |id: &str| {
+            let affix = generator.config().affixes.get(id).unwrap();
+            Modifier::from_affix(affix, &affix.tiers[0], affix.tiers[0].min, None)
+        };
+
+        item.implicit = None;
+        item.prefixes = vec![modifier("added_physical_damage")];
+        item.suffixes = vec![modifier("fire_resistance")];
+        let (local, global) = item.modifiers_by_scope();
+        assert_eq!(local.len(), 1);
+        assert_eq!(local[0].stat, StatType::AddedPhysicalDamage);
+        assert_eq!(global.len(), 1);
+        assert_eq!(global[0].stat, StatType::FireResistance);
+
+        // The implicit is sorted like any other modifier
+        item.implicit = Some(modifier("acc_fire_resist"));
+        let (local, global) = item.modifiers_by_scope();
+        assert_eq!(local.len(), 1);
+        assert_eq!(global.len(), 2);
+    }
+
+    #[test]
+    fn test_best_and_lowest_tier_affix() {
+        let generator = make_generator();
+        let mut item = generator.generate("iron_sword", 7).unwrap();
+        let affix = generator.config().affixes.get("fire_resistance").unwrap();
+        let tier1 = |value: i32| Modifier::from_affix(affix, &affix.tiers[0], value, None);
+        let mut tier5 = tier1(40);
+        tier5.tier = 5;
+
+        item.prefixes.clear();
+        item.suffixes = vec![tier5, tier1(40)];
+        assert_eq!(item.best_affix().unwrap().tier, 1);
+        assert_eq!(item.lowest_tier_affix().unwrap().tier, 5);
+
+        // Within a tier the better roll wins
+        item.suffixes = vec![tier1(37), tier1(44), tier1(40)];
+        assert_eq!(item.best_affix().unwrap().value, 44);
+        assert_eq!(item.lowest_tier_affix().unwrap().value, 37);
+
+        item.suffixes.clear();
+        assert!(item.best_affix().is_none());
+    }
+
+    #[test]
+    fn test_item_level_gates_affix_tiers() {
+        let generator = make_generator();
+
+        let mut high_tier_at_80 = false;
+        for seed in 0..50 {
+            let low = generator
+                .generate_with_options("iron_sword", seed, &at_level(1))
+                .unwrap();
+            let low = generator.apply_currency(&low, "alchemy").unwrap();
+            assert_eq!(low.item_level, 1);
+            assert!(max_rolled_tier_ilvl(&generator, &low) <= 1);
+
+            let high = generator
+                .generate_with_options("iron_sword", seed, &at_level(80))
+                .unwrap();
+            let high = generator.apply_currency(&high, "alchemy").unwrap();
+            if max_rolled_tier_ilvl(&generator, &high) > 1 {
+                high_tier_at_80 = true;
+            }
+        }
+
+        assert!(high_tier_at_80);
+    }
+
+    #[test]
+    fn test_item_level_is_deterministic() {
+        let generator = make_generator();
+
+        let a = generator
+            .generate_with_options("iron_sword", 777, &at_level(60))
+            .unwrap();
+        let a = generator.apply_currency(&a, "alchemy").unwrap();
+        let b = generator
+            .generate_with_options("iron_sword", 777, &at_level(60))
+            .unwrap();
+        let b = generator.apply_currency(&b, "alchemy").unwrap();
+
+        assert_eq!(a.prefixes.len(), b.prefixes.len());
+        assert_eq!(a.suffixes.len(), b.suffixes.len());
+        for (m1, m2) in a.prefixes.iter().zip(b.prefixes.iter()) {
+            assert_eq!(m1.affix_id, m2.affix_id);
+            assert_eq!(m1.value, m2.value);
+        }
+    }
+
+    #[test]
+    fn test_reroll_values_is_deterministic() {
+        let generator = make_generator();
+
+        let item = generator.generate("iron_sword", 4242).unwrap();
+        let item = generator.apply_currency(&item, "alchemy").unwrap();
+        assert!(item.affix_count() > 0);
+
+        let a = generator.reroll_values(&item);
+        let b = generator.reroll_values(&item);
+
+        let affix_ids = |i: &Item| -> Vec<(String, u32)> {
+            i.prefixes
+                .iter()
+                .chain(i.suffixes.iter())
+                .map(|m| (m.affix_id.clone(), m.tier))
+                .collect()
+        };
+        assert_eq!(affix_ids(&a), affix_ids(&item));
+        for (m1, m2) in a
+            .prefixes
+            .iter()
+            .chain(a.suffixes.iter())
+            .zip(b.prefixes.iter().chain(b.suffixes.iter()))
+        {
+            assert_eq!(m1.value, m2.value);
+            assert_eq!(m1.value_max, m2.value_max);
+            assert!(m1.value >= m1.tier_min.min(m1.tier_max));
+            assert!(m1.value <= m1.tier_min.max(m1.tier_max));
+        }
+
+        // Reconstruction replays the reroll exactly, even with later currency
+        let c = generator.apply_currency(&a, "exalt").unwrap_or(a.clone());
+        let options = GenerateOptions {
+            item_level: Some(c.item_level),
+            ..Default::default()
+        };
+        let rebuilt = generator
+            .reconstruct_with_options(&c.base_type_id, c.seed, &options, &c.operations)
+            .unwrap();
+        assert_eq!(affix_ids(&rebuilt), affix_ids(&c));
+        for (m1, m2) in rebuilt
+            .prefixes
+            .iter()
+            .chain(rebuilt.suffixes.iter())
+            .zip(c.prefixes.iter().chain(c.suffixes.iter()))
+        {
+            assert_eq!(m1.value, m2.value);
+            assert_eq!(m1.value_max, m2.value_max);
+        }
+    }
+
+    #[test]
+    fn test_generate_at_rarity() {
+        let generator = make_generator();
+
+        for seed in 0..20 {
+            let rare = generator
+                .generate_at_rarity("iron_sword", seed, "rare")
+                .unwrap();
+            assert_eq!(rare.rarity, "rare");
+            assert!(rare.prefixes.len() <= 3 && rare.suffixes.len() <= 3);
+            assert!((4..=6).contains(&rare.affix_count()));
+
+            let again = generator
+                .generate_at_rarity("iron_sword", seed, "rare")
+                .unwrap();
+            assert_eq!(again.name, rare.name);
+            assert_eq!(again.operations, rare.operations);
+        }
+
+        let normal = generator
+            .generate_at_rarity("iron_sword", 1, "normal")
+            .unwrap();
+        assert_eq!(normal.affix_count(), 0);
+        assert!(matches!(
+            generator.generate_at_rarity("iron_sword", 1, "legendary"),
+            Err(GeneratorError::UnreachableRarity(_))
+        ));
+    }
+
+    #[test]
+    fn test_influence_only_affixes() {
+        let mut config = Config::load_from_dir(Path::new("../config")).unwrap();
+        let mut affix = config
+            .affixes
+            .values()
+            .find(|a| {
+                a.affix_type == AffixType::Prefix
+                    && a.allowed_classes.contains(&ItemClass::OneHandSword)
+            })
+            .unwrap()
+            .clone();
+        affix.id = "shaper_prefix".to_string();
+        affix.tags.clear();
+        affix.influence = Some("shaper".to_string());
+        for tier in &mut affix.tiers {
+            tier.weight = 100_000;
+            tier.min_ilvl = 0;
+        }
+        config.affixes.insert(affix.id.clone(), affix);
+        for pool in config.affix_pools.values_mut() {
+            pool.affixes.push("shaper_prefix".to_string());
+        }
+        let generator = Generator::new(config);
+        let has_shaper = |item: &Item| item.prefixes.iter().any(|m| m.affix_id == "shaper_prefix");
+
+        let options = GenerateOptions {
+            item_level: Some(70),
+            influence: Some("shaper".to_string()),
+        };
+        let mut influenced_rolls = 0;
+        for seed in 0..20 {
+            let plain = generator.generate("iron_sword", seed).unwrap();
+            let plain = generator.apply_currency(&plain, "alchemy").unwrap();
+            assert!(plain.influence.is_none());
+            assert!(!has_shaper(&plain));
+
+            let shaped = generator
+                .generate_with_options("iron_sword", seed, &options)
+                .unwrap();
+            let shaped = generator.apply_currency(&shaped, "alchemy").unwrap();
+            assert_eq!(shaped.influence.as_deref(), Some("shaper"));
+            assert_eq!(shaped.item_level, 70);
+            if has_shaper(&shaped) {
+                influenced_rolls += 1;
+            }
+
+            // Seed plus influence reproduces the item
+            let rebuilt = generator
+                .reconstruct_with_options("iron_sword", seed, &options, &shaped.operations)
+                .unwrap();
+            assert_eq!(rebuilt.item_level, 70);
+            assert_eq!(rebuilt.prefixes.len(), shaped.prefixes.len());
+            for (a, b) in rebuilt.prefixes.iter().zip(&shaped.prefixes) {
+                assert_eq!((&a.affix_id, a.value), (&b.affix_id, b.value));
+            }
+        }
+        assert!(influenced_rolls > 0);
+    }
+}
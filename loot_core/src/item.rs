@@ -28,6 +28,9 @@ pub struct Item {
     pub tags: Vec<Tag>,
     /// Requirements to equip
     pub requirements: Requirements,
+    /// Item level - gates which affix tiers can roll (tier `min_ilvl` <= item level)
+    #[serde(default)]
+    pub item_level: u32,
     /// Implicit modifier (if any)
     pub implicit: Option<Modifier>,
     /// Rolled prefix modifiers
@@ -41,6 +44,9 @@ pub struct Item {
     /// Skill IDs granted by this item's base type
     #[serde(default)]
     pub granted_skills: Vec<String>,
+    /// Influence unlocking influence-only affixes (e.g. "shaper")
+    #[serde(default)]
+    pub influence: Option<String>,
 }
 
 impl Item {
@@ -81,12 +87,14 @@ impl Item {
             rarity: "normal".to_string(),
             tags: base.tags.clone(),
             requirements: base.requirements.clone(),
+            item_level: base.requirements.level,
             implicit: None, // Will be rolled with seed
             prefixes: Vec::new(),
             suffixes: Vec::new(),
             defenses,
             damage,
             granted_skills: base.granted_skills.clone(),
+            influence: None,
         }
     }
 
@@ -101,6 +109,33 @@ impl Item {
         self.prefixes.len() + self.suffixes.len()
     }
 
+    /// The affix with the best (lowest-numbered) tier, ties broken by the
+    /// better roll within the tier
+    pub fn best_affix(&self) -> Option<&Modifier> {
+        self.prefixes
+            .iter()
+            .chain(self.suffixes.iter())
+            .min_by(|a, b| Modifier::cmp_tier_quality(a, b))
+    }
+
+    /// The affix with the worst (highest-numbered) tier, ties broken by the
+    /// worse roll within the tier
+    pub fn lowest_tier_affix(&self) -> Option<&Modifier> {
+        self.prefixes
+            .iter()
+            .chain(self.suffixes.iter())
+            .max_by(|a, b| Modifier::cmp_tier_quality(a, b))
+    }
+
+    /// Split all modifiers, including the implicit, into `(local, global)` by scope
+    pub fn modifiers_by_scope(&self) -> (Vec<&Modifier>, Vec<&Modifier>) {
+        self.implicit
+            .iter()
+            .chain(self.prefixes.iter())
+            .chain(self.suffixes.iter())
+            .partition(|m| m.scope == AffixScope::Local)
+    }
+
     /// Get all skill IDs granted by this item (base type + affixes)
     pub fn all_skills(&self) -> Vec<&str> {
         let mut skills: Vec<&str> = self.granted_skills.iter().map(|s| s.as_str()).collect();
@@ -310,6 +345,23 @@ impl Modifier {
         }
     }
 
+    /// Where the rolled value sits in its tier's range, from 0.0 (min) to 1.0 (max)
+    ///
+    /// Fixed-value tiers count as a perfect roll.
+    pub fn roll_quality(&self) -> f64 {
+        if self.tier_max <= self.tier_min {
+            return 1.0;
+        }
+        (self.value - self.tier_min) as f64 / (self.tier_max - self.tier_min) as f64
+    }
+
+    /// Order modifiers best first: lower tier number, then higher roll quality
+    fn cmp_tier_quality(a: &Modifier, b: &Modifier) -> std::cmp::Ordering {
+        a.tier
+            .cmp(&b.tier)
+            .then(b.roll_quality().total_cmp(&a.roll_quality()))
+    }
+
     /// Display the modifier as a human-readable string
     pub fn display(&self) -> String {
         // Check if this is a flat damage stat with a range
@@ -347,11 +399,19 @@ impl Modifier {
                 | StatType::IncreasedAttackSpeed
                 | StatType::IncreasedCriticalChance
                 | StatType::IncreasedCriticalDamage
+                | StatType::IncreasedPhysicalCriticalDamage
+                | StatType::IncreasedFireCriticalDamage
+                | StatType::IncreasedColdCriticalDamage
+                | StatType::IncreasedLightningCriticalDamage
+                | StatType::IncreasedChaosCriticalDamage
                 | StatType::IncreasedArmour
                 | StatType::IncreasedEvasion
                 | StatType::IncreasedEnergyShield
                 | StatType::IncreasedLife
                 | StatType::IncreasedMana
+                | StatType::IncreasedLifeRecoveryRate
+                | StatType::IncreasedManaRecoveryRate
+                | StatType::IncreasedEnergyShieldRechargeRate
                 | StatType::IncreasedAccuracy
                 | StatType::IncreasedStrength
                 | StatType::IncreasedDexterity
@@ -7,11 +7,13 @@ pub mod types;
 
 // Core API - what most users need
 pub use config::Config;
-pub use generator::{Generator, GeneratorError};
+pub use generator::{GenerateOptions, Generator, GeneratorError};
 pub use item::Item;
 
 // Types users commonly need
-pub use types::{AffixScope, AffixType, DamageType, ItemClass, StatType, StatusEffect, Tag};
+pub use types::{
+    AffixScope, AffixType, DamageType, ItemClass, StatCategory, StatType, StatusEffect, Tag,
+};
 
 // Config types
 pub use config::RarityConfig;
@@ -22,7 +24,8 @@ pub use storage::{BinaryDecode, BinaryEncode, ItemCollection};
 #[cfg(test)]
 mod tests {
     use super::item::Modifier;
-    use super::types::{AffixScope, StatType};
+    use super::types::{AffixScope, StatCategory, StatType};
+    use std::collections::HashSet;
 
     #[test]
     fn test_damage_range_display() {
@@ -63,4 +66,205 @@ mod tests {
 
         assert_eq!(modifier.display(), "+50 Added Life");
     }
+    /// Position of each variant in `StatType::all()`
+    ///
+    /// Has no wildcard arm, so a new StatType doesn't compile until it is
+    /// listed here (and in `all()`).
+    fn stat_type_index(stat: StatType) -> usize {
+        match stat {
+            StatType::AddedPhysicalDamage => 0,
+            StatType::AddedFireDamage => 1,
+            StatType::AddedColdDamage => 2,
+            StatType::AddedLightningDamage => 3,
+            StatType::AddedChaosDamage => 4,
+            StatType::IncreasedPhysicalDamage => 5,
+            StatType::IncreasedFireDamage => 6,
+            StatType::IncreasedColdDamage => 7,
+            StatType::IncreasedLightningDamage => 8,
+            StatType::IncreasedElementalDamage => 9,
+            StatType::IncreasedChaosDamage => 10,
+            StatType::IncreasedAttackSpeed => 11,
+            StatType::IncreasedCriticalChance => 12,
+            StatType::IncreasedCriticalDamage => 13,
+            StatType::IncreasedPhysicalCriticalDamage => 14,
+            StatType::IncreasedFireCriticalDamage => 15,
+            StatType::IncreasedColdCriticalDamage => 16,
+            StatType::IncreasedLightningCriticalDamage => 17,
+            StatType::IncreasedChaosCriticalDamage => 18,
+            StatType::PoisonDamageOverTime => 19,
+            StatType::IncreasedPoisonDuration => 20,
+            StatType::PoisonMagnitude => 21,
+            StatType::PoisonMaxStacks => 22,
+            StatType::ConvertPhysicalToPoison => 23,
+            StatType::ConvertFireToPoison => 24,
+            StatType::ConvertColdToPoison => 25,
+            StatType::ConvertLightningToPoison => 26,
+            StatType::ConvertChaosToPoison => 27,
+            StatType::BleedDamageOverTime => 28,
+            StatType::IncreasedBleedDuration => 29,
+            StatType::BleedMagnitude => 30,
+            StatType::BleedMaxStacks => 31,
+            StatType::ConvertPhysicalToBleed => 32,
+            StatType::ConvertFireToBleed => 33,
+            StatType::ConvertColdToBleed => 34,
+            StatType::ConvertLightningToBleed => 35,
+            StatType::ConvertChaosToBleed => 36,
+            StatType::BurnDamageOverTime => 37,
+            StatType::IncreasedBurnDuration => 38,
+            StatType::BurnMagnitude => 39,
+            StatType::BurnMaxStacks => 40,
+            StatType::ConvertPhysicalToBurn => 41,
+            StatType::ConvertFireToBurn => 42,
+            StatType::ConvertColdToBurn => 43,
+            StatType::ConvertLightningToBurn => 44,
+            StatType::ConvertChaosToBurn => 45,
+            StatType::IncreasedFreezeDuration => 46,
+            StatType::FreezeMagnitude => 47,
+            StatType::FreezeMaxStacks => 48,
+            StatType::ConvertPhysicalToFreeze => 49,
+            StatType::ConvertFireToFreeze => 50,
+            StatType::ConvertColdToFreeze => 51,
+            StatType::ConvertLightningToFreeze => 52,
+            StatType::ConvertChaosToFreeze => 53,
+            StatType::IncreasedChillDuration => 54,
+            StatType::ChillMagnitude => 55,
+            StatType::ChillMaxStacks => 56,
+            StatType::ConvertPhysicalToChill => 57,
+            StatType::ConvertFireToChill => 58,
+            StatType::ConvertColdToChill => 59,
+            StatType::ConvertLightningToChill => 60,
+            StatType::ConvertChaosToChill => 61,
+            StatType::IncreasedStaticDuration => 62,
+            StatType::StaticMagnitude => 63,
+            StatType::StaticMaxStacks => 64,
+            StatType::ConvertPhysicalToStatic => 65,
+            StatType::ConvertFireToStatic => 66,
+            StatType::ConvertColdToStatic => 67,
+            StatType::ConvertLightningToStatic => 68,
+            StatType::ConvertChaosToStatic => 69,
+            StatType::IncreasedFearDuration => 70,
+            StatType::FearMagnitude => 71,
+            StatType::FearMaxStacks => 72,
+            StatType::ConvertPhysicalToFear => 73,
+            StatType::ConvertFireToFear => 74,
+            StatType::ConvertColdToFear => 75,
+            StatType::ConvertLightningToFear => 76,
+            StatType::ConvertChaosToFear => 77,
+            StatType::IncreasedSlowDuration => 78,
+            StatType::SlowMagnitude => 79,
+            StatType::SlowMaxStacks => 80,
+            StatType::ConvertPhysicalToSlow => 81,
+            StatType::ConvertFireToSlow => 82,
+            StatType::ConvertColdToSlow => 83,
+            StatType::ConvertLightningToSlow => 84,
+            StatType::ConvertChaosToSlow => 85,
+            StatType::AddedArmour => 86,
+            StatType::AddedEvasion => 87,
+            StatType::AddedEnergyShield => 88,
+            StatType::IncreasedArmour => 89,
+            StatType::IncreasedEvasion => 90,
+            StatType::IncreasedEnergyShield => 91,
+            StatType::AddedStrength => 92,
+            StatType::AddedDexterity => 93,
+            StatType::AddedConstitution => 94,
+            StatType::AddedIntelligence => 95,
+            StatType::AddedWisdom => 96,
+            StatType::AddedCharisma => 97,
+            StatType::AddedAllAttributes => 98,
+            StatType::IncreasedStrength => 99,
+            StatType::IncreasedDexterity => 100,
+            StatType::IncreasedConstitution => 101,
+            StatType::IncreasedIntelligence => 102,
+            StatType::IncreasedWisdom => 103,
+            StatType::IncreasedCharisma => 104,
+            StatType::IncreasedAllAttributes => 105,
+            StatType::AddedLife => 106,
+            StatType::AddedMana => 107,
+            StatType::IncreasedLife => 108,
+            StatType::IncreasedMana => 109,
+            StatType::LifeRegeneration => 110,
+            StatType::ManaRegeneration => 111,
+            StatType::IncreasedLifeRecoveryRate => 112,
+            StatType::IncreasedManaRecoveryRate => 113,
+            StatType::IncreasedEnergyShieldRechargeRate => 114,
+            StatType::LifeOnHit => 115,
+            StatType::LifeLeech => 116,
+            StatType::ManaLeech => 117,
+            StatType::FireResistance => 118,
+            StatType::ColdResistance => 119,
+            StatType::LightningResistance => 120,
+            StatType::ChaosResistance => 121,
+            StatType::AllResistances => 122,
+            StatType::AddedAccuracy => 123,
+            StatType::IncreasedAccuracy => 124,
+            StatType::IncreasedMovementSpeed => 125,
+            StatType::IncreasedItemRarity => 126,
+            StatType::IncreasedItemQuantity => 127,
+            StatType::IncreasedPoisonDamage => 128,
+            StatType::IncreasedBleedDamage => 129,
+            StatType::IncreasedBurnDamage => 130,
+            StatType::IncreasedFreezeDamage => 131,
+            StatType::IncreasedChillDamage => 132,
+            StatType::IncreasedStaticDamage => 133,
+            StatType::IncreasedFearDamage => 134,
+            StatType::IncreasedSlowDamage => 135,
+            StatType::IncreasedAllStatusDamage => 136,
+            StatType::IncreasedDamagingStatusDamage => 137,
+            StatType::IncreasedNonDamagingStatusDamage => 138,
+            StatType::StatusMagnitudeOnCrit => 139,
+            StatType::IncreasedStatusDamageOnCrit => 140,
+            StatType::MoreAilmentDotOnCrit => 141,
+            StatType::BlockChance => 142,
+            StatType::BlockAmount => 143,
+            StatType::FlatBlockAbsorb => 144,
+            StatType::SpellDodgeChance => 145,
+            StatType::IncreasedAreaOfEffect => 146,
+            StatType::AdditionalProjectiles => 147,
+            StatType::IncreasedProjectileSpeed => 148,
+            StatType::IncreasedSkillDuration => 149,
+            StatType::CooldownReduction => 150,
+            StatType::ReducedManaCost => 151,
+            StatType::IncreasedCastSpeed => 152,
+            StatType::IncreasedGlobalDamage => 153,
+            StatType::DamageOverTimeMultiplier => 154,
+            StatType::MoreDamageWhileStationary => 155,
+            StatType::MoreDamageWhileMoving => 156,
+            StatType::MoreTrapDamage => 157,
+            StatType::MoreMineDamage => 158,
+            StatType::MoreTotemDamage => 159,
+            StatType::ReducedDamageTaken => 160,
+            StatType::IncreasedDotDamageTaken => 161,
+            StatType::PhysicalDamageReduction => 162,
+            StatType::PhysicalPenetration => 163,
+            StatType::CullingStrike => 164,
+            StatType::DamageTakenFromManaBeforeLife => 165,
+            StatType::IncreasedStunThreshold => 166,
+            StatType::IncreasedBuffEffectReceived => 167,
+            StatType::IncreasedConsumableEffect => 168,
+            StatType::ReflectPhysicalDamage => 169,
+            StatType::ReflectElementalDamage => 170,
+            StatType::LifeOnKill => 171,
+            StatType::ManaOnKill => 172,
+        }
+    }
+
+    #[test]
+    fn test_stat_type_all_covers_every_variant() {
+        let all = StatType::all();
+        for (index, stat) in all.iter().enumerate() {
+            assert_eq!(stat_type_index(*stat), index, "{stat:?} out of place");
+        }
+        // The last declared variant is the last entry, so none are missing
+        assert_eq!(stat_type_index(StatType::ManaOnKill), all.len() - 1);
+
+        // Every category is used, and a few spot checks
+        let categories: HashSet<StatCategory> =
+            StatType::all().iter().map(|stat| stat.category()).collect();
+        assert_eq!(categories.len(), 5);
+        assert_eq!(StatType::AddedFireDamage.category(), StatCategory::Offense);
+        assert_eq!(StatType::FireResistance.category(), StatCategory::Defense);
+        assert_eq!(StatType::AddedStrength.category(), StatCategory::Attributes);
+        assert_eq!(StatType::BurnMagnitude.category(), StatCategory::Ailment);
+        assert_eq!(StatType::AddedMana.category(), StatCategory::Utility);
+    }
 }
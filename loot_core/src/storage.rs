@@ -1,11 +1,15 @@
-use crate::generator::{Generator, GeneratorError};
+use crate::generator::{GenerateOptions, Generator, GeneratorError};
 use crate::item::Item;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::io::{self, Read, Write};
 
 /// Current binary format version
-const BINARY_VERSION: u8 = 1;
+const BINARY_VERSION: u8 = 3;
+
+/// Oldest binary format version that can still be decoded
+/// (version 1 has no item level; the base type's level requirement is used)
+const MIN_BINARY_VERSION: u8 = 1;
 
 /// Magic bytes for item collection files
 const COLLECTION_MAGIC: &[u8; 4] = b"LOOT";
@@ -16,12 +20,15 @@ const COLLECTION_MAGIC: &[u8; 4] = b"LOOT";
 pub enum Operation {
     /// Apply a currency by ID
     Currency(String),
+    /// Reroll the numeric values of existing affixes within their tiers
+    RerollValues,
 }
 
 /// Operation type discriminants for binary encoding
 #[repr(u8)]
 enum OpType {
     Currency = 0,
+    RerollValues = 1,
 }
 
 impl TryFrom<u8> for OpType {
@@ -30,6 +37,7 @@ impl TryFrom<u8> for OpType {
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
             0 => Ok(OpType::Currency),
+            1 => Ok(OpType::RerollValues),
             _ => Err(DecodeError::InvalidOperationType(value)),
         }
     }
@@ -103,11 +111,14 @@ pub trait BinaryDecode: Sized {
 impl BinaryEncode for Item {
     /// Encode item to binary format.
     ///
-    /// Format (version 1):
+    /// Format (version 3):
     /// - version: u8
     /// - base_type_id_len: u8
     /// - base_type_id: [u8; base_type_id_len]
     /// - seed: u64 (little-endian)
+    /// - item_level: u32 (little-endian)
+    /// - influence_len: u8 (0 = no influence)
+    /// - influence: [u8; influence_len]
     /// - operations_count: u16 (little-endian)
     /// - for each operation:
     ///   - op_type: u8
@@ -122,6 +133,12 @@ impl BinaryEncode for Item {
         // Seed
         writer.write_all(&self.seed.to_le_bytes())?;
 
+        // Item level
+        writer.write_all(&self.item_level.to_le_bytes())?;
+
+        // Influence
+        write_string(writer, self.influence.as_deref().unwrap_or(""))?;
+
         // Operations
         let ops_count = self.operations.len().min(u16::MAX as usize) as u16;
         writer.write_all(&ops_count.to_le_bytes())?;
@@ -132,6 +149,9 @@ impl BinaryEncode for Item {
                     writer.write_all(&[OpType::Currency as u8])?;
                     write_string(writer, currency_id)?;
                 }
+                Operation::RerollValues => {
+                    writer.write_all(&[OpType::RerollValues as u8])?;
+                }
             }
         }
 
@@ -143,7 +163,7 @@ impl BinaryDecode for Item {
     fn decode<R: Read>(reader: &mut R, generator: &Generator) -> Result<Self, DecodeError> {
         // Version
         let version = read_u8(reader)?;
-        if version != BINARY_VERSION {
+        if !(MIN_BINARY_VERSION..=BINARY_VERSION).contains(&version) {
             return Err(DecodeError::InvalidVersion(version));
         }
 
@@ -153,6 +173,20 @@ impl BinaryDecode for Item {
         // Seed
         let seed = read_u64(reader)?;
 
+        // Item level (added in version 2)
+        let item_level = if version >= 2 {
+            Some(read_u32(reader)?)
+        } else {
+            None
+        };
+
+        // Influence (added in version 3)
+        let influence = if version >= 3 {
+            read_influence(reader)?
+        } else {
+            None
+        };
+
         // Operations
         let ops_count = read_u16(reader)?;
         let mut operations = Vec::with_capacity(ops_count as usize);
@@ -164,12 +198,19 @@ impl BinaryDecode for Item {
                     let currency_id = read_string(reader)?;
                     Operation::Currency(currency_id)
                 }
+                OpType::RerollValues => Operation::RerollValues,
             };
             operations.push(op);
         }
 
         // Reconstruct the item
-        Ok(generator.reconstruct(&base_type_id, seed, &operations)?)
+        let options = GenerateOptions {
+            item_level,
+            influence,
+        };
+        let item =
+            generator.reconstruct_with_options(&base_type_id, seed, &options, &operations)?;
+        Ok(item)
     }
 }
 
@@ -250,6 +291,8 @@ impl BinaryEncode for ItemCollection {
     /// - for each item:
     ///   - base_type_id_index: u16 (little-endian)
     ///   - seed: u64 (little-endian)
+    ///   - item_level: u32 (little-endian, version 2+)
+    ///   - influence_len: u8, influence: [u8; influence_len] (version 3+, 0 = none)
     ///   - operations_count: u16 (little-endian)
     ///   - for each operation:
     ///     - op_type: u8
@@ -278,6 +321,7 @@ impl BinaryEncode for ItemCollection {
                     Operation::Currency(id) => {
                         intern(id);
                     }
+                    Operation::RerollValues => {}
                 }
             }
         }
@@ -301,6 +345,8 @@ impl BinaryEncode for ItemCollection {
             let base_idx = *string_indices.get(&item.base_type_id).unwrap();
             writer.write_all(&base_idx.to_le_bytes())?;
             writer.write_all(&item.seed.to_le_bytes())?;
+            writer.write_all(&item.item_level.to_le_bytes())?;
+            write_string(writer, item.influence.as_deref().unwrap_or(""))?;
 
             let ops_count = item.operations.len().min(u16::MAX as usize) as u16;
             writer.write_all(&ops_count.to_le_bytes())?;
@@ -312,6 +358,9 @@ impl BinaryEncode for ItemCollection {
                         let idx = *string_indices.get(currency_id).unwrap();
                         writer.write_all(&idx.to_le_bytes())?;
                     }
+                    Operation::RerollValues => {
+                        writer.write_all(&[OpType::RerollValues as u8])?;
+                    }
                 }
             }
         }
@@ -331,7 +380,7 @@ impl BinaryDecode for ItemCollection {
 
         // Version
         let version = read_u8(reader)?;
-        if version != BINARY_VERSION {
+        if !(MIN_BINARY_VERSION..=BINARY_VERSION).contains(&version) {
             return Err(DecodeError::InvalidVersion(version));
         }
 
@@ -354,6 +403,16 @@ impl BinaryDecode for ItemCollection {
                 .clone();
 
             let seed = read_u64(reader)?;
+            let item_level = if version >= 2 {
+                Some(read_u32(reader)?)
+            } else {
+                None
+            };
+            let influence = if version >= 3 {
+                read_influence(reader)?
+            } else {
+                None
+            };
 
             let ops_count = read_u16(reader)?;
             let mut operations = Vec::with_capacity(ops_count as usize);
@@ -369,12 +428,18 @@ impl BinaryDecode for ItemCollection {
                             .clone();
                         Operation::Currency(currency_id)
                     }
+                    OpType::RerollValues => Operation::RerollValues,
                 };
                 operations.push(op);
             }
 
             // Reconstruct item
-            let item = generator.reconstruct(&base_type_id, seed, &operations)?;
+            let options = GenerateOptions {
+                item_level,
+                influence,
+            };
+            let item =
+                generator.reconstruct_with_options(&base_type_id, seed, &options, &operations)?;
 
             items.push(item);
         }
@@ -393,6 +458,12 @@ fn write_string<W: Write>(writer: &mut W, s: &str) -> io::Result<()> {
     Ok(())
 }
 
+/// Read an influence string, where an empty string means no influence
+fn read_influence<R: Read>(reader: &mut R) -> Result<Option<String>, DecodeError> {
+    let influence = read_string(reader)?;
+    Ok((!influence.is_empty()).then_some(influence))
+}
+
 fn read_u8<R: Read>(reader: &mut R) -> Result<u8, DecodeError> {
     let mut buf = [0u8; 1];
     reader.read_exact(&mut buf).map_err(|e| {
@@ -465,6 +536,20 @@ mod tests {
         Generator::new(config)
     }
 
+    fn at_level(item_level: u32) -> GenerateOptions {
+        GenerateOptions {
+            item_level: Some(item_level),
+            ..Default::default()
+        }
+    }
+
+    fn shaper() -> GenerateOptions {
+        GenerateOptions {
+            influence: Some("shaper".to_string()),
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_item_encode_decode_roundtrip() {
         let generator = make_generator();
@@ -546,8 +631,9 @@ mod tests {
 
         let binary = item.encode_to_vec();
 
-        // Binary: 1 (version) + 1 + 10 (base_type) + 8 (seed) + 2 (ops count) + 1 (op type) + 1 + 9 (currency) = 33 bytes
-        assert_eq!(binary.len(), 33);
+        // Binary: 1 (version) + 1 + 10 (base_type) + 8 (seed) + 4 (item level)
+        //         + 1 (no influence) + 2 (ops count) + 1 (op type) + 1 + 9 (currency) = 38 bytes
+        assert_eq!(binary.len(), 38);
     }
 
     #[test]
@@ -574,4 +660,159 @@ mod tests {
             assert_eq!(p1.value, p2.value);
         }
     }
+
+    #[test]
+    fn test_item_level_roundtrip() {
+        let generator = make_generator();
+
+        let item1 = generator
+            .generate_with_options("iron_sword", 4242, &at_level(80))
+            .unwrap();
+        let item1 = generator.apply_currency(&item1, "alchemy").unwrap();
+
+        let bytes = item1.encode_to_vec();
+        let item2 = Item::decode_from_slice(&bytes, &generator).unwrap();
+
+        assert_eq!(item2.item_level, 80);
+        let mods1: Vec<_> = item1.prefixes.iter().chain(&item1.suffixes).collect();
+        let mods2: Vec<_> = item2.prefixes.iter().chain(&item2.suffixes).collect();
+        assert_eq!(mods1.len(), mods2.len());
+        for (m1, m2) in mods1.iter().zip(mods2.iter()) {
+            assert_eq!(m1.affix_id, m2.affix_id);
+            assert_eq!(m1.tier, m2.tier);
+            assert_eq!(m1.value, m2.value);
+        }
+    }
+
+    #[test]
+    fn test_item_influence_roundtrip() {
+        let generator = make_generator();
+
+        let item = generator
+            .generate_with_options("iron_sword", 777, &shaper())
+            .unwrap();
+        let item = generator.apply_currency(&item, "transmute").unwrap();
+        let item = generator.apply_currency(&item, "augment").unwrap();
+
+        let bytes = item.encode_to_vec();
+        let decoded = Item::decode_from_slice(&bytes, &generator).unwrap();
+
+        assert_eq!(decoded.influence.as_deref(), Some("shaper"));
+        assert_eq!(decoded.operations, item.operations);
+        let ids = |item: &Item| -> Vec<String> {
+            item.prefixes
+                .iter()
+                .chain(&item.suffixes)
+                .map(|m| m.affix_id.clone())
+                .collect()
+        };
+        assert_eq!(ids(&decoded), ids(&item));
+    }
+
+    #[test]
+    fn test_item_collection_influence_roundtrip() {
+        let generator = make_generator();
+
+        let mut collection = ItemCollection::new();
+        let influenced = generator
+            .generate_with_options("iron_sword", 111, &shaper())
+            .unwrap();
+        collection.add(generator.apply_currency(&influenced, "transmute").unwrap());
+        collection.add(generator.generate("leather_boots", 222).unwrap());
+
+        let encoded = collection.encode_to_vec();
+        let decoded = ItemCollection::decode_from_slice(&encoded, &generator).unwrap();
+
+        assert_eq!(decoded.items.len(), 2);
+        assert_eq!(decoded.items[0].influence.as_deref(), Some("shaper"));
+        assert_eq!(decoded.items[0].operations, collection.items[0].operations);
+        assert_eq!(decoded.items[1].influence, None);
+    }
+
+    #[test]
+    fn test_decode_version_2() {
+        let generator = make_generator();
+        let expected = generator
+            .generate_with_options("iron_sword", 4242, &at_level(30))
+            .unwrap();
+        let expected = generator.apply_currency(&expected, "transmute").unwrap();
+
+        // Version 2 item: no influence field after the item level
+        let mut bytes = vec![2];
+        write_string(&mut bytes, "iron_sword").unwrap();
+        bytes.extend_from_slice(&4242u64.to_le_bytes());
+        bytes.extend_from_slice(&30u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.push(OpType::Currency as u8);
+        write_string(&mut bytes, "transmute").unwrap();
+
+        let item = Item::decode_from_slice(&bytes, &generator).unwrap();
+        assert_eq!(item.item_level, 30);
+        assert_eq!(item.influence, None);
+        assert_eq!(item.operations, expected.operations);
+        assert_eq!(item.prefixes.len(), expected.prefixes.len());
+        assert_eq!(item.suffixes.len(), expected.suffixes.len());
+
+        // Version 2 collection with the same item
+        let mut bytes = COLLECTION_MAGIC.to_vec();
+        bytes.push(2);
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        write_string(&mut bytes, "iron_sword").unwrap();
+        write_string(&mut bytes, "transmute").unwrap();
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+        bytes.extend_from_slice(&4242u64.to_le_bytes());
+        bytes.extend_from_slice(&30u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.push(OpType::Currency as u8);
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+
+        let collection = ItemCollection::decode_from_slice(&bytes, &generator).unwrap();
+        assert_eq!(collection.items.len(), 1);
+        assert_eq!(collection.items[0].item_level, 30);
+        assert_eq!(collection.items[0].influence, None);
+        assert_eq!(collection.items[0].operations, expected.operations);
+    }
+
+    #[test]
+    fn test_decode_version_1() {
+        let generator = make_generator();
+        let expected = generator.generate("iron_sword", 4242).unwrap();
+        let expected = generator.apply_currency(&expected, "transmute").unwrap();
+
+        // Version 1 item: no item level or influence after the seed
+        let mut bytes = vec![1];
+        write_string(&mut bytes, "iron_sword").unwrap();
+        bytes.extend_from_slice(&4242u64.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.push(OpType::Currency as u8);
+        write_string(&mut bytes, "transmute").unwrap();
+
+        // The base type's level requirement stands in for the item level
+        let item = Item::decode_from_slice(&bytes, &generator).unwrap();
+        assert_eq!(item.item_level, expected.item_level);
+        assert_eq!(item.influence, None);
+        assert_eq!(item.operations, expected.operations);
+        assert_eq!(item.prefixes.len(), expected.prefixes.len());
+        assert_eq!(item.suffixes.len(), expected.suffixes.len());
+
+        // Version 1 collection with the same item
+        let mut bytes = COLLECTION_MAGIC.to_vec();
+        bytes.push(1);
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        write_string(&mut bytes, "iron_sword").unwrap();
+        write_string(&mut bytes, "transmute").unwrap();
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+        bytes.extend_from_slice(&4242u64.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.push(OpType::Currency as u8);
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+
+        let collection = ItemCollection::decode_from_slice(&bytes, &generator).unwrap();
+        assert_eq!(collection.items.len(), 1);
+        assert_eq!(collection.items[0].item_level, expected.item_level);
+        assert_eq!(collection.items[0].influence, None);
+        assert_eq!(collection.items[0].operations, expected.operations);
+    }
 }
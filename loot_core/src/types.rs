@@ -182,6 +182,22 @@ pub enum AffixScope {
     Global,
 }
 
+/// Broad grouping of stat types, for tooling and UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatCategory {
+    /// Damage, speed, crit, accuracy and penetration
+    Offense,
+    /// Life, defenses, resistances, block and mitigation
+    Defense,
+    /// Core attributes
+    Attributes,
+    /// Status effects: duration, magnitude, conversion and status damage
+    Ailment,
+    /// Mana, movement, loot and skill mechanics
+    Utility,
+}
+
 /// Stat modifier types that affixes can grant
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -202,6 +218,12 @@ pub enum StatType {
     IncreasedAttackSpeed,
     IncreasedCriticalChance,
     IncreasedCriticalDamage,
+    // Critical multiplier for skills dealing a damage type
+    IncreasedPhysicalCriticalDamage,
+    IncreasedFireCriticalDamage,
+    IncreasedColdCriticalDamage,
+    IncreasedLightningCriticalDamage,
+    IncreasedChaosCriticalDamage,
     // Status effect - Poison
     PoisonDamageOverTime,
     IncreasedPoisonDuration,
@@ -307,6 +329,9 @@ pub enum StatType {
     IncreasedMana,
     LifeRegeneration,
     ManaRegeneration,
+    IncreasedLifeRecoveryRate,
+    IncreasedManaRecoveryRate,
+    IncreasedEnergyShieldRechargeRate,
     LifeOnHit,
     LifeLeech,
     ManaLeech,
@@ -338,9 +363,11 @@ pub enum StatType {
     // Crit-specific status
     StatusMagnitudeOnCrit,
     IncreasedStatusDamageOnCrit,
+    MoreAilmentDotOnCrit,
     // Block
     BlockChance,
     BlockAmount,
+    FlatBlockAbsorb,
     // Dodge
     SpellDodgeChance,
     // Area of Effect
@@ -356,16 +383,388 @@ pub enum StatType {
     // Damage modifiers (global)
     IncreasedGlobalDamage,
     DamageOverTimeMultiplier,
+    MoreDamageWhileStationary,
+    MoreDamageWhileMoving,
+    MoreTrapDamage,
+    MoreMineDamage,
+    MoreTotemDamage,
     // Defensive
     ReducedDamageTaken,
+    IncreasedDotDamageTaken,
     PhysicalDamageReduction,
     PhysicalPenetration,
     CullingStrike,
+    DamageTakenFromManaBeforeLife,
+    IncreasedStunThreshold,
+    IncreasedBuffEffectReceived,
+    IncreasedConsumableEffect,
+    ReflectPhysicalDamage,
+    ReflectElementalDamage,
     // On-kill recovery
     LifeOnKill,
     ManaOnKill,
 }
 
+impl StatType {
+    /// Get all stat type variants, in declaration order
+    pub fn all() -> &'static [StatType] {
+        &[
+            StatType::AddedPhysicalDamage,
+            StatType::AddedFireDamage,
+            StatType::AddedColdDamage,
+            StatType::AddedLightningDamage,
+            StatType::AddedChaosDamage,
+            StatType::IncreasedPhysicalDamage,
+            StatType::IncreasedFireDamage,
+            StatType::IncreasedColdDamage,
+            StatType::IncreasedLightningDamage,
+            StatType::IncreasedElementalDamage,
+            StatType::IncreasedChaosDamage,
+            StatType::IncreasedAttackSpeed,
+            StatType::IncreasedCriticalChance,
+            StatType::IncreasedCriticalDamage,
+            StatType::IncreasedPhysicalCriticalDamage,
+            StatType::IncreasedFireCriticalDamage,
+            StatType::IncreasedColdCriticalDamage,
+            StatType::IncreasedLightningCriticalDamage,
+            StatType::IncreasedChaosCriticalDamage,
+            StatType::PoisonDamageOverTime,
+            StatType::IncreasedPoisonDuration,
+            StatType::PoisonMagnitude,
+            StatType::PoisonMaxStacks,
+            StatType::ConvertPhysicalToPoison,
+            StatType::ConvertFireToPoison,
+            StatType::ConvertColdToPoison,
+            StatType::ConvertLightningToPoison,
+            StatType::ConvertChaosToPoison,
+            StatType::BleedDamageOverTime,
+            StatType::IncreasedBleedDuration,
+            StatType::BleedMagnitude,
+            StatType::BleedMaxStacks,
+            StatType::ConvertPhysicalToBleed,
+            StatType::ConvertFireToBleed,
+            StatType::ConvertColdToBleed,
+            StatType::ConvertLightningToBleed,
+            StatType::ConvertChaosToBleed,
+            StatType::BurnDamageOverTime,
+            StatType::IncreasedBurnDuration,
+            StatType::BurnMagnitude,
+            StatType::BurnMaxStacks,
+            StatType::ConvertPhysicalToBurn,
+            StatType::ConvertFireToBurn,
+            StatType::ConvertColdToBurn,
+            StatType::ConvertLightningToBurn,
+            StatType::ConvertChaosToBurn,
+            StatType::IncreasedFreezeDuration,
+            StatType::FreezeMagnitude,
+            StatType::FreezeMaxStacks,
+            StatType::ConvertPhysicalToFreeze,
+            StatType::ConvertFireToFreeze,
+            StatType::ConvertColdToFreeze,
+            StatType::ConvertLightningToFreeze,
+            StatType::ConvertChaosToFreeze,
+            StatType::IncreasedChillDuration,
+            StatType::ChillMagnitude,
+            StatType::ChillMaxStacks,
+            StatType::ConvertPhysicalToChill,
+            StatType::ConvertFireToChill,
+            StatType::ConvertColdToChill,
+            StatType::ConvertLightningToChill,
+            StatType::ConvertChaosToChill,
+            StatType::IncreasedStaticDuration,
+            StatType::StaticMagnitude,
+            StatType::StaticMaxStacks,
+            StatType::ConvertPhysicalToStatic,
+            StatType::ConvertFireToStatic,
+            StatType::ConvertColdToStatic,
+            StatType::ConvertLightningToStatic,
+            StatType::ConvertChaosToStatic,
+            StatType::IncreasedFearDuration,
+            StatType::FearMagnitude,
+            StatType::FearMaxStacks,
+            StatType::ConvertPhysicalToFear,
+            StatType::ConvertFireToFear,
+            StatType::ConvertColdToFear,
+            StatType::ConvertLightningToFear,
+            StatType::ConvertChaosToFear,
+            StatType::IncreasedSlowDuration,
+            StatType::SlowMagnitude,
+            StatType::SlowMaxStacks,
+            StatType::ConvertPhysicalToSlow,
+            StatType::ConvertFireToSlow,
+            StatType::ConvertColdToSlow,
+            StatType::ConvertLightningToSlow,
+            StatType::ConvertChaosToSlow,
+            StatType::AddedArmour,
+            StatType::AddedEvasion,
+            StatType::AddedEnergyShield,
+            StatType::IncreasedArmour,
+            StatType::IncreasedEvasion,
+            StatType::IncreasedEnergyShield,
+            StatType::AddedStrength,
+            StatType::AddedDexterity,
+            StatType::AddedConstitution,
+            StatType::AddedIntelligence,
+            StatType::AddedWisdom,
+            StatType::AddedCharisma,
+            StatType::AddedAllAttributes,
+            StatType::IncreasedStrength,
+            StatType::IncreasedDexterity,
+            StatType::IncreasedConstitution,
+            StatType::IncreasedIntelligence,
+            StatType::IncreasedWisdom,
+            StatType::IncreasedCharisma,
+            StatType::IncreasedAllAttributes,
+            StatType::AddedLife,
+            StatType::AddedMana,
+            StatType::IncreasedLife,
+            StatType::IncreasedMana,
+            StatType::LifeRegeneration,
+            StatType::ManaRegeneration,
+            StatType::IncreasedLifeRecoveryRate,
+            StatType::IncreasedManaRecoveryRate,
+            StatType::IncreasedEnergyShieldRechargeRate,
+            StatType::LifeOnHit,
+            StatType::LifeLeech,
+            StatType::ManaLeech,
+            StatType::FireResistance,
+            StatType::ColdResistance,
+            StatType::LightningResistance,
+            StatType::ChaosResistance,
+            StatType::AllResistances,
+            StatType::AddedAccuracy,
+            StatType::IncreasedAccuracy,
+            StatType::IncreasedMovementSpeed,
+            StatType::IncreasedItemRarity,
+            StatType::IncreasedItemQuantity,
+            StatType::IncreasedPoisonDamage,
+            StatType::IncreasedBleedDamage,
+            StatType::IncreasedBurnDamage,
+            StatType::IncreasedFreezeDamage,
+            StatType::IncreasedChillDamage,
+            StatType::IncreasedStaticDamage,
+            StatType::IncreasedFearDamage,
+            StatType::IncreasedSlowDamage,
+            StatType::IncreasedAllStatusDamage,
+            StatType::IncreasedDamagingStatusDamage,
+            StatType::IncreasedNonDamagingStatusDamage,
+            StatType::StatusMagnitudeOnCrit,
+            StatType::IncreasedStatusDamageOnCrit,
+            StatType::MoreAilmentDotOnCrit,
+            StatType::BlockChance,
+            StatType::BlockAmount,
+            StatType::FlatBlockAbsorb,
+            StatType::SpellDodgeChance,
+            StatType::IncreasedAreaOfEffect,
+            StatType::AdditionalProjectiles,
+            StatType::IncreasedProjectileSpeed,
+            StatType::IncreasedSkillDuration,
+            StatType::CooldownReduction,
+            StatType::ReducedManaCost,
+            StatType::IncreasedCastSpeed,
+            StatType::IncreasedGlobalDamage,
+            StatType::DamageOverTimeMultiplier,
+            StatType::MoreDamageWhileStationary,
+            StatType::MoreDamageWhileMoving,
+            StatType::MoreTrapDamage,
+            StatType::MoreMineDamage,
+            StatType::MoreTotemDamage,
+            StatType::ReducedDamageTaken,
+            StatType::IncreasedDotDamageTaken,
+            StatType::PhysicalDamageReduction,
+            StatType::PhysicalPenetration,
+            StatType::CullingStrike,
+            StatType::DamageTakenFromManaBeforeLife,
+            StatType::IncreasedStunThreshold,
+            StatType::IncreasedBuffEffectReceived,
+            StatType::IncreasedConsumableEffect,
+            StatType::ReflectPhysicalDamage,
+            StatType::ReflectElementalDamage,
+            StatType::LifeOnKill,
+            StatType::ManaOnKill,
+        ]
+    }
+
+    /// The category this stat type is grouped under
+    pub fn category(&self) -> StatCategory {
+        match self {
+            StatType::AddedPhysicalDamage
+            | StatType::AddedFireDamage
+            | StatType::AddedColdDamage
+            | StatType::AddedLightningDamage
+            | StatType::AddedChaosDamage
+            | StatType::IncreasedPhysicalDamage
+            | StatType::IncreasedFireDamage
+            | StatType::IncreasedColdDamage
+            | StatType::IncreasedLightningDamage
+            | StatType::IncreasedElementalDamage
+            | StatType::IncreasedChaosDamage
+            | StatType::IncreasedAttackSpeed
+            | StatType::IncreasedCriticalChance
+            | StatType::IncreasedCriticalDamage
+            | StatType::IncreasedPhysicalCriticalDamage
+            | StatType::IncreasedFireCriticalDamage
+            | StatType::IncreasedColdCriticalDamage
+            | StatType::IncreasedLightningCriticalDamage
+            | StatType::IncreasedChaosCriticalDamage
+            | StatType::AddedAccuracy
+            | StatType::IncreasedAccuracy
+            | StatType::IncreasedAreaOfEffect
+            | StatType::AdditionalProjectiles
+            | StatType::IncreasedProjectileSpeed
+            | StatType::IncreasedCastSpeed
+            | StatType::IncreasedGlobalDamage
+            | StatType::DamageOverTimeMultiplier
+            | StatType::MoreDamageWhileStationary
+            | StatType::MoreDamageWhileMoving
+            | StatType::MoreTrapDamage
+            | StatType::MoreMineDamage
+            | StatType::MoreTotemDamage
+            | StatType::PhysicalPenetration
+            | StatType::CullingStrike => StatCategory::Offense,
+            StatType::AddedArmour
+            | StatType::AddedEvasion
+            | StatType::AddedEnergyShield
+            | StatType::IncreasedArmour
+            | StatType::IncreasedEvasion
+            | StatType::IncreasedEnergyShield
+            | StatType::AddedLife
+            | StatType::IncreasedLife
+            | StatType::LifeRegeneration
+            | StatType::IncreasedLifeRecoveryRate
+            | StatType::IncreasedEnergyShieldRechargeRate
+            | StatType::LifeOnHit
+            | StatType::LifeLeech
+            | StatType::FireResistance
+            | StatType::ColdResistance
+            | StatType::LightningResistance
+            | StatType::ChaosResistance
+            | StatType::AllResistances
+            | StatType::BlockChance
+            | StatType::BlockAmount
+            | StatType::FlatBlockAbsorb
+            | StatType::SpellDodgeChance
+            | StatType::ReducedDamageTaken
+            | StatType::IncreasedDotDamageTaken
+            | StatType::PhysicalDamageReduction
+            | StatType::DamageTakenFromManaBeforeLife
+            | StatType::IncreasedStunThreshold
+            | StatType::ReflectPhysicalDamage
+            | StatType::ReflectElementalDamage
+            | StatType::LifeOnKill => StatCategory::Defense,
+            StatType::AddedStrength
+            | StatType::AddedDexterity
+            | StatType::AddedConstitution
+            | StatType::AddedIntelligence
+            | StatType::AddedWisdom
+            | StatType::AddedCharisma
+            | StatType::AddedAllAttributes
+            | StatType::IncreasedStrength
+            | StatType::IncreasedDexterity
+            | StatType::IncreasedConstitution
+            | StatType::IncreasedIntelligence
+            | StatType::IncreasedWisdom
+            | StatType::IncreasedCharisma
+            | StatType::IncreasedAllAttributes => StatCategory::Attributes,
+            StatType::PoisonDamageOverTime
+            | StatType::IncreasedPoisonDuration
+            | StatType::PoisonMagnitude
+            | StatType::PoisonMaxStacks
+            | StatType::ConvertPhysicalToPoison
+            | StatType::ConvertFireToPoison
+            | StatType::ConvertColdToPoison
+            | StatType::ConvertLightningToPoison
+            | StatType::ConvertChaosToPoison
+            | StatType::BleedDamageOverTime
+            | StatType::IncreasedBleedDuration
+            | StatType::BleedMagnitude
+            | StatType::BleedMaxStacks
+            | StatType::ConvertPhysicalToBleed
+            | StatType::ConvertFireToBleed
+            | StatType::ConvertColdToBleed
+            | StatType::ConvertLightningToBleed
+            | StatType::ConvertChaosToBleed
+            | StatType::BurnDamageOverTime
+            | StatType::IncreasedBurnDuration
+            | StatType::BurnMagnitude
+            | StatType::BurnMaxStacks
+            | StatType::ConvertPhysicalToBurn
+            | StatType::ConvertFireToBurn
+            | StatType::ConvertColdToBurn
+            | StatType::ConvertLightningToBurn
+            | StatType::ConvertChaosToBurn
+            | StatType::IncreasedFreezeDuration
+            | StatType::FreezeMagnitude
+            | StatType::FreezeMaxStacks
+            | StatType::ConvertPhysicalToFreeze
+            | StatType::ConvertFireToFreeze
+            | StatType::ConvertColdToFreeze
+            | StatType::ConvertLightningToFreeze
+            | StatType::ConvertChaosToFreeze
+            | StatType::IncreasedChillDuration
+            | StatType::ChillMagnitude
+            | StatType::ChillMaxStacks
+            | StatType::ConvertPhysicalToChill
+            | StatType::ConvertFireToChill
+            | StatType::ConvertColdToChill
+            | StatType::ConvertLightningToChill
+            | StatType::ConvertChaosToChill
+            | StatType::IncreasedStaticDuration
+            | StatType::StaticMagnitude
+            | StatType::StaticMaxStacks
+            | StatType::ConvertPhysicalToStatic
+            | StatType::ConvertFireToStatic
+            | StatType::ConvertColdToStatic
+            | StatType::ConvertLightningToStatic
+            | StatType::ConvertChaosToStatic
+            | StatType::IncreasedFearDuration
+            | StatType::FearMagnitude
+            | StatType::FearMaxStacks
+            | StatType::ConvertPhysicalToFear
+            | StatType::ConvertFireToFear
+            | StatType::ConvertColdToFear
+            | StatType::ConvertLightningToFear
+            | StatType::ConvertChaosToFear
+            | StatType::IncreasedSlowDuration
+            | StatType::SlowMagnitude
+            | StatType::SlowMaxStacks
+            | StatType::ConvertPhysicalToSlow
+            | StatType::ConvertFireToSlow
+            | StatType::ConvertColdToSlow
+            | StatType::ConvertLightningToSlow
+            | StatType::ConvertChaosToSlow
+            | StatType::IncreasedPoisonDamage
+            | StatType::IncreasedBleedDamage
+            | StatType::IncreasedBurnDamage
+            | StatType::IncreasedFreezeDamage
+            | StatType::IncreasedChillDamage
+            | StatType::IncreasedStaticDamage
+            | StatType::IncreasedFearDamage
+            | StatType::IncreasedSlowDamage
+            | StatType::IncreasedAllStatusDamage
+            | StatType::IncreasedDamagingStatusDamage
+            | StatType::IncreasedNonDamagingStatusDamage
+            | StatType::StatusMagnitudeOnCrit
+            | StatType::IncreasedStatusDamageOnCrit
+            | StatType::MoreAilmentDotOnCrit => StatCategory::Ailment,
+            StatType::AddedMana
+            | StatType::IncreasedMana
+            | StatType::ManaRegeneration
+            | StatType::IncreasedManaRecoveryRate
+            | StatType::ManaLeech
+            | StatType::IncreasedMovementSpeed
+            | StatType::IncreasedItemRarity
+            | StatType::IncreasedItemQuantity
+            | StatType::IncreasedSkillDuration
+            | StatType::CooldownReduction
+            | StatType::ReducedManaCost
+            | StatType::IncreasedBuffEffectReceived
+            | StatType::IncreasedConsumableEffect
+            | StatType::ManaOnKill => StatCategory::Utility,
+        }
+    }
+}
+
 /// Attribute requirements for equipping an item
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Requirements {
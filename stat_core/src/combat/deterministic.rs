@@ -0,0 +1,159 @@
+//! DeterministicRng - Fixed-outcome rolls for reproducible combat
+
+use rand::{Error, RngCore};
+
+/// Every random draw lands exactly halfway: 0.5 for `gen::<f64>()`
+const MIDPOINT: u64 = 1 << 63;
+
+/// Whether resolution rolls chance-based avoidance
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CombatMode {
+    /// Block and spell dodge roll against their chance
+    #[default]
+    Random,
+    /// Block and spell dodge never trigger, so a hit's outcome depends only
+    /// on the packet and the defender's mitigation. Pair with
+    /// `DeterministicRng` for fully reproducible combat.
+    Deterministic,
+}
+
+impl CombatMode {
+    /// Whether block and spell dodge are rolled
+    pub fn rolls_avoidance(self) -> bool {
+        self == CombatMode::Random
+    }
+}
+
+/// An RNG that resolves every roll to its midpoint, for golden-file tests
+///
+/// Pass it anywhere combat takes an RNG (`StatBlock::attack_with_rng`,
+/// `resolve_damage_with_rng`) to get the same outcome on every run without
+/// picking seeds:
+/// - Chance rolls (crit, spell dodge, block, status application, ailment
+///   avoidance) succeed only when the chance is above 50%. Resolve with
+///   `CombatMode::Deterministic` (`resolve_damage_with_mode`) to skip block
+///   and dodge entirely.
+/// - Damage ranges (skill and weapon rolls) land on their average, up to
+///   float rounding.
+/// - Random picks from a list (random element conversion, random on-hit
+///   ailments) take the middle entry.
+///
+/// Evasion is already deterministic (a damage cap rather than a roll).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeterministicRng;
+
+impl RngCore for DeterministicRng {
+    fn next_u32(&mut self) -> u32 {
+        (MIDPOINT >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        MIDPOINT
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            chunk.copy_from_slice(&MIDPOINT.to_le_bytes()[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::{resolve_damage_with_mode, resolve_damage_with_rng};
+    use crate::config::{ensure_constants_initialized, ensure_dot_registry_initialized};
+    use crate::damage::{BaseDamage, DamagePacketGenerator};
+    use crate::source::TemplateSource;
+    use crate::stat_block::StatBlock;
+    use crate::types::SkillTag;
+    use loot_core::types::{DamageType, StatType};
+    use rand::Rng;
+
+    #[test]
+    fn test_rolls_resolve_to_midpoint() {
+        let mut rng = DeterministicRng;
+        assert_eq!(rng.gen::<f64>(), 0.5);
+        assert!((rng.gen_range(50.0f64..=150.0) - 100.0).abs() < 1e-9);
+        assert!(rng.gen::<f64>() < 0.6);
+        assert!(rng.gen::<f64>() >= 0.4);
+    }
+
+    #[test]
+    fn test_deterministic_combat_is_exact() {
+        ensure_constants_initialized();
+        ensure_dot_registry_initialized();
+        let attacker = StatBlock::with_id("player");
+        let skill = |crit: f64| DamagePacketGenerator {
+            id: "fireball".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 50.0, 150.0)],
+            weapon_effectiveness: 0.0,
+            base_crit_chance: crit,
+            tags: vec![SkillTag::Spell],
+            ..Default::default()
+        };
+        let mut defender = StatBlock::with_id("dummy");
+        defender.set_template(
+            TemplateSource::new("dummy")
+                .with_modifier(StatType::FireResistance, 50.0)
+                .with_modifier(StatType::BlockChance, 40.0),
+        );
+        defender.rebuild();
+
+        // 60% crit succeeds: 100 average * 1.5, halved by resistance.
+        // 40% block never triggers.
+        let packet = attacker.attack_with_rng(&skill(60.0), &mut DeterministicRng);
+        assert!(packet.is_critical);
+        assert!((packet.total_damage() - 150.0).abs() < 1e-9);
+        let (_, result) = resolve_damage_with_rng(&defender, &packet, &mut DeterministicRng);
+        assert!(!result.was_blocked);
+        assert!((result.total_damage - 75.0).abs() < 1e-9);
+
+        let packet = attacker.attack_with_rng(&skill(40.0), &mut DeterministicRng);
+        assert!(!packet.is_critical);
+        let (_, result) = resolve_damage_with_rng(&defender, &packet, &mut DeterministicRng);
+        assert!((result.total_damage - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_deterministic_mode_skips_block_and_dodge() {
+        ensure_constants_initialized();
+        ensure_dot_registry_initialized();
+        let attacker = StatBlock::with_id("player");
+        let skill = DamagePacketGenerator {
+            id: "fireball".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            tags: vec![SkillTag::Spell],
+            ..Default::default()
+        };
+        let mut defender = StatBlock::with_id("dummy");
+        defender.set_template(
+            TemplateSource::new("dummy")
+                .with_modifier(StatType::BlockChance, 75.0)
+                .with_modifier(StatType::BlockAmount, 40.0)
+                .with_modifier(StatType::SpellDodgeChance, 75.0),
+        );
+        defender.rebuild();
+        let packet = attacker.attack_with_rng(&skill, &mut DeterministicRng);
+
+        // 75% dodge succeeds on a midpoint roll in random mode
+        let (_, result) = resolve_damage_with_rng(&defender, &packet, &mut DeterministicRng);
+        assert!(result.was_dodged);
+
+        let (_, result) = resolve_damage_with_mode(
+            &defender,
+            &packet,
+            CombatMode::Deterministic,
+            &mut DeterministicRng,
+        );
+        assert!(!result.was_dodged);
+        assert!(!result.was_blocked);
+        assert!((result.total_damage - 100.0).abs() < 1e-9);
+    }
+}
@@ -1,7 +1,17 @@
 //! Combat resolution - Apply damage packets to stat blocks
 
+mod deterministic;
 mod resolution;
 mod result;
+mod stats;
+mod trace;
 
-pub use resolution::{resolve_damage, resolve_damage_with_rng};
-pub use result::{CombatResult, DamageTaken};
+pub use deterministic::{CombatMode, DeterministicRng};
+pub use resolution::{
+    resolve_damage, resolve_damage_traced, resolve_damage_with_mode, resolve_damage_with_registry,
+    resolve_damage_with_rng,
+};
+pub use result::{CombatResult, DamageTaken, MitigationBreakdown, MitigationLayer};
+pub(crate) use stats::record;
+pub use stats::{combat_stats_enabled, set_combat_stats_enabled, take_combat_stats, CombatStats};
+pub use trace::{CombatTrace, TraceStage, TraceStep};
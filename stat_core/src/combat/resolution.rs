@@ -1,16 +1,23 @@
 //! Damage resolution - Apply DamagePacket to StatBlock
 
+use super::deterministic::CombatMode;
 use super::result::{CombatResult, DamageTaken};
-use crate::config::dot_registry;
+use super::stats::record;
+use super::trace::{CombatTrace, TraceStage};
+use crate::config::{constants, dot_registry};
 use crate::damage::DamagePacket;
 use crate::defense::{
     apply_evasion_cap, calculate_armour_reduction, calculate_resistance_mitigation,
 };
+use crate::dot::{status_config_id, DotRegistry};
 use crate::stat_block::StatBlock;
-use crate::types::Effect;
+use crate::types::{DotOrigin, Effect, ReflectBasis};
 use loot_core::types::{DamageType, StatusEffect};
 use rand::Rng;
 
+/// Life percent at or above which a defender counts as being at full life
+const FULL_LIFE_PERCENT: f64 = 99.99;
+
 /// Resolve a damage packet against a defending stat block (immutable API)
 ///
 /// Returns the new defender state and combat result. This is the main combat
@@ -18,8 +25,12 @@ use rand::Rng;
 /// 1. Applies resistances to each damage type
 /// 2. Applies armour to physical damage
 /// 3. Applies evasion one-shot protection
-/// 4. Applies damage to ES then life
+/// 4. Applies damage to mana (`damage_to_mana_percent`), then ES, then life
+///    (chaos skips ES unless the defender opts out via `chaos_bypasses_es`)
 /// 5. Processes status effect applications (chance = status_damage / max_health)
+///
+/// Reflected damage is reported in the result for the caller to apply to the
+/// attacker.
 pub fn resolve_damage(defender: &StatBlock, packet: &DamagePacket) -> (StatBlock, CombatResult) {
     let mut rng = rand::thread_rng();
     resolve_damage_with_rng(defender, packet, &mut rng)
@@ -30,6 +41,85 @@ pub fn resolve_damage_with_rng(
     defender: &StatBlock,
     packet: &DamagePacket,
     rng: &mut impl Rng,
+) -> (StatBlock, CombatResult) {
+    resolve(defender, packet, rng, None, None, CombatMode::Random)
+}
+
+/// Resolve damage in the given combat mode
+///
+/// `CombatMode::Deterministic` skips the block and spell dodge rolls, so
+/// golden tests aren't at the mercy of avoidance chances.
+pub fn resolve_damage_with_mode(
+    defender: &StatBlock,
+    packet: &DamagePacket,
+    mode: CombatMode,
+    rng: &mut impl Rng,
+) -> (StatBlock, CombatResult) {
+    resolve(defender, packet, rng, None, None, mode)
+}
+
+/// Resolve damage using `registry` for status effect configs instead of the
+/// global DoT registry (e.g. for tools or tests with custom DoT configs)
+pub fn resolve_damage_with_registry(
+    defender: &StatBlock,
+    packet: &DamagePacket,
+    registry: &DotRegistry,
+    rng: &mut impl Rng,
+) -> (StatBlock, CombatResult) {
+    resolve(
+        defender,
+        packet,
+        rng,
+        None,
+        Some(registry),
+        CombatMode::Random,
+    )
+}
+
+/// Resolve damage like `resolve_damage`, also recording the damage total
+/// entering and leaving each stage
+///
+/// Meant for debugging and tooling; `resolve_damage` skips building the trace.
+pub fn resolve_damage_traced(
+    defender: &StatBlock,
+    packet: &DamagePacket,
+) -> (StatBlock, CombatResult, CombatTrace) {
+    let mut trace = CombatTrace::default();
+    let mut rng = rand::thread_rng();
+    let (new_defender, result) = resolve(
+        defender,
+        packet,
+        &mut rng,
+        Some(&mut trace),
+        None,
+        CombatMode::Random,
+    );
+    (new_defender, result, trace)
+}
+
+/// Record a trace stage when tracing; `after` is only computed if so
+fn trace_step(
+    trace: &mut Option<&mut CombatTrace>,
+    stage: TraceStage,
+    after: impl FnOnce() -> f64,
+) {
+    if let Some(trace) = trace {
+        trace.record(stage, after());
+    }
+}
+
+/// Sum of final damage across all types
+fn damage_total(result: &CombatResult) -> f64 {
+    result.damage_taken.iter().map(|d| d.final_amount).sum()
+}
+
+fn resolve(
+    defender: &StatBlock,
+    packet: &DamagePacket,
+    rng: &mut impl Rng,
+    mut trace: Option<&mut CombatTrace>,
+    registry: Option<&DotRegistry>,
+    mode: CombatMode,
 ) -> (StatBlock, CombatResult) {
     let mut new_defender = defender.clone();
     let mut result = CombatResult::new();
@@ -37,23 +127,42 @@ pub fn resolve_damage_with_rng(
     // Store initial state
     result.es_before = new_defender.current_energy_shield;
     result.life_before = new_defender.current_life;
+    if let Some(trace) = trace.as_deref_mut() {
+        trace.raw_damage = packet.total_damage();
+    }
 
     // Step 0: Spell dodge check
-    if packet.is_spell {
+    if packet.is_spell && mode.rolls_avoidance() {
         let dodge_chance = new_defender.computed_spell_dodge_chance() / 100.0;
+        if dodge_chance > 0.0 {
+            record(|s| s.dodge_rolls += 1);
+        }
         if dodge_chance > 0.0 && rng.gen::<f64>() < dodge_chance {
             result.was_dodged = true;
+            trace_step(&mut trace, TraceStage::Dodge, || 0.0);
             result.es_after = new_defender.current_energy_shield;
             result.life_after = new_defender.current_life;
             return (new_defender, result);
         }
     }
 
+    // Step 0b: Opener bonus against full-life defenders
+    let full_life_mult = match packet.more_vs_full_life {
+        Some(more) if new_defender.life_percent() >= FULL_LIFE_PERCENT => 1.0 + more,
+        _ => 1.0,
+    };
+
+    // Step 0c: Increased damage taken from Static, before mitigation
+    let incoming_mult = full_life_mult * new_defender.incoming_damage_multiplier();
+    if let Some(trace) = trace.as_deref_mut() {
+        trace.raw_damage *= incoming_mult;
+    }
+
     // Step 1: Calculate mitigated damage for each type
     for final_damage in &packet.damages {
-        let raw = final_damage.amount;
+        let raw = final_damage.amount * incoming_mult;
         let pen = packet.penetration(final_damage.damage_type);
-        let resist = new_defender.resistance(final_damage.damage_type);
+        let resist = new_defender.modified_resistance(final_damage.damage_type);
 
         let after_resist = if final_damage.damage_type == DamageType::Physical {
             // Physical uses armour instead of resistance
@@ -74,8 +183,12 @@ pub fn resolve_damage_with_rng(
             after_resist,
         ));
     }
+    trace_step(&mut trace, TraceStage::Resistance, || damage_total(&result));
 
     // Step 2: Apply armour to physical damage
+    // Armour and physical DR together never reduce more than the configured cap
+    let max_physical_reduction = constants().armour.max_physical_reduction.clamp(0.0, 100.0);
+    let mut physical_floor = 0.0;
     let physical_damage = result
         .damage_taken
         .iter_mut()
@@ -83,8 +196,10 @@ pub fn resolve_damage_with_rng(
 
     if let Some(phys) = physical_damage {
         if phys.final_amount > 0.0 {
+            physical_floor = phys.final_amount * (1.0 - max_physical_reduction / 100.0);
             let armour = new_defender.armour.compute();
-            let after_armour = calculate_armour_reduction(armour, phys.final_amount);
+            let after_armour =
+                calculate_armour_reduction(armour, phys.final_amount).max(physical_floor);
             let armour_reduced = phys.final_amount - after_armour;
 
             result.damage_reduced_by_armour = armour_reduced;
@@ -92,6 +207,7 @@ pub fn resolve_damage_with_rng(
             phys.final_amount = after_armour;
         }
     }
+    trace_step(&mut trace, TraceStage::Armour, || damage_total(&result));
 
     // Step 2b: Apply physical damage reduction (% reduction, separate from armour)
     let phys_dr = new_defender.physical_damage_reduction.clamp(0.0, 90.0) / 100.0;
@@ -102,19 +218,22 @@ pub fn resolve_damage_with_rng(
             .find(|d| d.damage_type == DamageType::Physical)
         {
             if phys.final_amount > 0.0 {
-                let reduced = phys.final_amount * phys_dr;
+                let reduced = (phys.final_amount * phys_dr)
+                    .min((phys.final_amount - physical_floor).max(0.0));
                 result.damage_reduced_by_physical_dr = reduced;
                 phys.mitigated_amount += reduced;
                 phys.final_amount -= reduced;
             }
         }
     }
+    trace_step(&mut trace, TraceStage::PhysicalDr, || damage_total(&result));
 
     // Recalculate total after armour + physical DR
     let total_before_evasion: f64 = result.damage_taken.iter().map(|d| d.final_amount).sum();
 
     // Step 3: Apply evasion one-shot protection (accuracy vs evasion)
     let evasion = new_defender.evasion.compute();
+    record(|s| s.evasion_checks += 1);
     let accuracy = packet.accuracy;
     let (damage_after_evasion, evaded) = apply_evasion_cap(accuracy, evasion, total_before_evasion);
 
@@ -132,13 +251,20 @@ pub fn resolve_damage_with_rng(
             }
         }
     }
+    trace_step(&mut trace, TraceStage::Evasion, || damage_total(&result));
 
     // Step 3b: Block check
-    let block_chance = new_defender.computed_block_chance() / 100.0;
+    let block_chance = if mode.rolls_avoidance() {
+        new_defender.computed_block_chance() / 100.0
+    } else {
+        0.0
+    };
+    if block_chance > 0.0 {
+        record(|s| s.block_rolls += 1);
+    }
     if block_chance > 0.0 && rng.gen::<f64>() < block_chance {
         let block_amount = new_defender.computed_block_amount();
         result.was_blocked = true;
-        result.damage_blocked = block_amount;
 
         // Subtract block amount proportionally from each damage type
         let total_pre_block: f64 = result.damage_taken.iter().map(|d| d.final_amount).sum();
@@ -148,9 +274,26 @@ pub fn resolve_damage_with_rng(
                 let blocked = damage.final_amount * block_ratio;
                 damage.mitigated_amount += blocked;
                 damage.final_amount -= blocked;
+                result.damage_blocked += blocked;
+            }
+        }
+    }
+
+    // Flat block absorb: applies to every hit, independent of the block roll
+    let flat_absorb = new_defender.flat_block_absorb;
+    if flat_absorb > 0.0 {
+        let total_pre_absorb: f64 = result.damage_taken.iter().map(|d| d.final_amount).sum();
+        if total_pre_absorb > 0.0 {
+            let absorb_ratio = (flat_absorb / total_pre_absorb).min(1.0);
+            for damage in &mut result.damage_taken {
+                let absorbed = damage.final_amount * absorb_ratio;
+                damage.mitigated_amount += absorbed;
+                damage.final_amount -= absorbed;
+                result.damage_absorbed_by_flat_block += absorbed;
             }
         }
     }
+    trace_step(&mut trace, TraceStage::Block, || damage_total(&result));
 
     // Step 3c: Reduced damage taken (final global multiplier)
     let dr = new_defender.reduced_damage_taken.clamp(0.0, 90.0) / 100.0;
@@ -164,22 +307,74 @@ pub fn resolve_damage_with_rng(
         let total_post_dr: f64 = result.damage_taken.iter().map(|d| d.final_amount).sum();
         result.damage_reduced_by_dr = total_pre_dr - total_post_dr;
     }
+    trace_step(&mut trace, TraceStage::DamageReduction, || {
+        damage_total(&result)
+    });
 
     // Calculate final total damage
     result.total_damage = result.damage_taken.iter().map(|d| d.final_amount).sum();
 
-    // Step 4: Apply damage to ES then life
+    // Step 3d: Stun check against the hit's final damage
+    let max_life = new_defender.computed_max_life();
+    if max_life > 0.0 {
+        result.stun_magnitude = result.total_damage / max_life;
+        let threshold = constants().stun.threshold * (1.0 + new_defender.stun_threshold_increased);
+        result.stunned = !new_defender.stun_immune && result.stun_magnitude > threshold;
+    }
+
+    // Step 4: Apply damage to ward, then mana, then ES, then life
     let mut remaining_damage = result.total_damage;
 
+    // Ward soaks the hit before any other pool
+    if new_defender.current_ward > 0.0 && remaining_damage > 0.0 {
+        let ward_absorbed = remaining_damage.min(new_defender.current_ward);
+        new_defender.current_ward -= ward_absorbed;
+        remaining_damage -= ward_absorbed;
+        result.damage_absorbed_by_ward = ward_absorbed;
+    }
+    trace_step(&mut trace, TraceStage::Ward, || remaining_damage);
+
+    // A share of the hit is taken from mana first; whatever mana can't
+    // cover overflows to ES/life
+    let mana_share = new_defender.damage_to_mana_percent.clamp(0.0, 100.0) / 100.0;
+    if mana_share > 0.0 && new_defender.current_mana > 0.0 {
+        let mana_absorbed = (remaining_damage * mana_share).min(new_defender.current_mana);
+        new_defender.current_mana -= mana_absorbed;
+        remaining_damage -= mana_absorbed;
+        result.damage_taken_from_mana = mana_absorbed;
+    }
+    trace_step(&mut trace, TraceStage::Mana, || remaining_damage);
+    let unabsorbed_fraction = if result.total_damage > 0.0 {
+        remaining_damage / result.total_damage
+    } else {
+        0.0
+    };
+
+    // Chaos damage bypasses ES by default and goes straight to life
+    let es_bypassing_damage: f64 = if new_defender.chaos_bypasses_es {
+        result
+            .damage_taken
+            .iter()
+            .filter(|d| d.damage_type == DamageType::Chaos)
+            .map(|d| d.final_amount)
+            .sum::<f64>()
+            * unabsorbed_fraction
+    } else {
+        0.0
+    };
+    let es_eligible_damage = remaining_damage - es_bypassing_damage;
+
     // ES absorbs damage first
-    if new_defender.current_energy_shield > 0.0 && remaining_damage > 0.0 {
-        let es_absorbed = remaining_damage.min(new_defender.current_energy_shield);
+    if new_defender.current_energy_shield > 0.0 && es_eligible_damage > 0.0 {
+        let es_absorbed = es_eligible_damage.min(new_defender.current_energy_shield);
         new_defender.current_energy_shield -= es_absorbed;
         remaining_damage -= es_absorbed;
         result.damage_blocked_by_es = es_absorbed;
     }
+    trace_step(&mut trace, TraceStage::EnergyShield, || remaining_damage);
 
     // Remaining damage goes to life
+    let life_damage = remaining_damage;
     if remaining_damage > 0.0 {
         new_defender.current_life -= remaining_damage;
     }
@@ -189,9 +384,13 @@ pub fn resolve_damage_with_rng(
         result.is_killing_blow = true;
         new_defender.current_life = 0.0;
     }
+    trace_step(&mut trace, TraceStage::Life, || {
+        result.life_before - new_defender.current_life
+    });
 
     // Step 4b: Culling strike — if defender is below threshold, kill them
-    if !result.is_killing_blow && packet.culling_strike > 0.0 {
+    let can_cull = !packet.cull_requires_crit || packet.is_critical;
+    if !result.is_killing_blow && packet.culling_strike > 0.0 && can_cull {
         let life_percent = new_defender.life_percent();
         if life_percent > 0.0 && life_percent <= packet.culling_strike {
             result.is_killing_blow = true;
@@ -200,12 +399,44 @@ pub fn resolve_damage_with_rng(
         }
     }
 
-    // Step 4c: Life/Mana on kill
+    // Step 4c: Life/Mana on kill, overkill for corpse/chain mechanics
     if result.is_killing_blow {
+        result.overkill_damage = (life_damage - result.life_before).max(0.0);
         result.life_gained_on_kill = packet.life_on_kill;
         result.mana_gained_on_kill = packet.mana_on_kill;
     }
 
+    // Step 4d: Reflect a share of the hit back at the attacker, never more
+    // than the defender's own life before the hit. Landed reflect only
+    // counts damage left after mitigation, evasion and block.
+    let reflect_percent = |dt: DamageType| match dt {
+        DamageType::Physical => new_defender.reflect_physical_percent,
+        DamageType::Fire | DamageType::Cold | DamageType::Lightning => {
+            new_defender.reflect_elemental_percent
+        }
+        DamageType::Chaos => 0.0,
+    };
+    let reflected: f64 = match new_defender.reflect_basis {
+        ReflectBasis::Raw => packet
+            .damages
+            .iter()
+            .map(|d| d.amount * reflect_percent(d.damage_type).max(0.0) / 100.0)
+            .sum(),
+        ReflectBasis::Landed => result
+            .damage_taken
+            .iter()
+            .map(|d| d.final_amount * reflect_percent(d.damage_type).max(0.0) / 100.0)
+            .sum(),
+    };
+    result.reflected_damage = reflected.min(result.life_before.max(0.0));
+
+    // Step 4e: Break "expires on hit" effects and restart ES recharge once
+    // damage lands
+    if result.total_damage > 0.0 {
+        result.effects_broken = new_defender.remove_effects_expiring_on_hit();
+        result.resets_es_recharge = true;
+    }
+
     // Store final state
     result.es_after = new_defender.current_energy_shield;
     result.life_after = new_defender.current_life;
@@ -213,12 +444,18 @@ pub fn resolve_damage_with_rng(
     // Step 5: Process status effect applications using unified Effect system
     let target_max_health = new_defender.computed_max_life();
     for pending_status in &packet.status_effects_to_apply {
-        let config_id = status_to_config_id(pending_status.effect_type);
-        let registry = dot_registry();
+        let config_id = status_config_id(pending_status.effect_type);
+        let registry: &DotRegistry = match registry {
+            Some(registry) => registry,
+            None => dot_registry(),
+        };
         let config = registry.get(config_id);
+        let avoid_chance = new_defender.ailment_avoid(pending_status.effect_type) / 100.0;
 
         let should_apply = match config.map(|c| &c.application) {
-            Some(crate::dot::StatusApplication::Buildup { threshold }) => {
+            Some(crate::dot::StatusApplication::Buildup { threshold })
+                if pending_status.fixed_chance.is_none() =>
+            {
                 // Buildup-based: accumulate status damage until threshold
                 let buildup = new_defender
                     .status_buildup
@@ -227,27 +464,33 @@ pub fn resolve_damage_with_rng(
                 *buildup += pending_status.status_damage;
                 if *buildup >= *threshold {
                     *buildup -= threshold;
-                    true
+                    !avoided(rng, avoid_chance)
                 } else {
                     false
                 }
             }
             _ => {
                 // Default: chance-based (status_damage / target_max_health)
-                let apply_chance = pending_status.calculate_apply_chance(target_max_health);
+                let apply_chance =
+                    pending_status.calculate_apply_chance(target_max_health) * (1.0 - avoid_chance);
+                record(|s| s.status_rolls += 1);
                 rng.gen::<f64>() < apply_chance
             }
         };
 
         if should_apply {
+            record(|s| s.status_applications += 1);
             // Create unified Effect based on status type
             let effect = create_effect_from_status(
+                registry,
                 pending_status.effect_type,
                 pending_status.duration,
                 pending_status.magnitude,
                 pending_status.dot_dps,
+                pending_status.dot_origin,
                 &packet.source_id,
-            );
+            )
+            .with_crit_origin(pending_status.from_crit);
 
             // Add to unified effects (handles stacking internally)
             new_defender.add_effect(effect.clone());
@@ -258,33 +501,29 @@ pub fn resolve_damage_with_rng(
     (new_defender, result)
 }
 
-/// Map StatusEffect enum to config ID
-fn status_to_config_id(status: StatusEffect) -> &'static str {
-    match status {
-        StatusEffect::Poison => "poison",
-        StatusEffect::Bleed => "bleed",
-        StatusEffect::Burn => "burn",
-        StatusEffect::Freeze => "freeze",
-        StatusEffect::Chill => "chill",
-        StatusEffect::Static => "static",
-        StatusEffect::Fear => "fear",
-        StatusEffect::Slow => "slow",
-    }
+/// Roll the defender's chance to avoid a status that would otherwise apply
+fn avoided(rng: &mut impl Rng, avoid_chance: f64) -> bool {
+    avoid_chance > 0.0 && rng.gen::<f64>() < avoid_chance
 }
 
 /// Create an Effect from a pending status effect using config
 fn create_effect_from_status(
+    registry: &DotRegistry,
     status: StatusEffect,
     duration: f64,
     magnitude: f64,
     dot_dps: f64,
+    origin: Option<DotOrigin>,
     source_id: &str,
 ) -> Effect {
-    let config_id = status_to_config_id(status);
-    let registry = dot_registry();
+    let config_id = status_config_id(status);
 
     if let Some(config) = registry.get(config_id) {
-        Effect::from_config(config, status, duration, magnitude, dot_dps, source_id)
+        let effect = Effect::from_config(config, status, duration, magnitude, dot_dps, source_id);
+        match origin {
+            Some(origin) if config.recompute_on_buff => effect.with_dot_origin(origin),
+            _ => effect,
+        }
     } else {
         // Fallback if config not found (shouldn't happen with proper initialization)
         Effect::new_ailment(
@@ -296,6 +535,7 @@ fn create_effect_from_status(
             dot_dps,
             0.5, // default tick rate
             crate::types::AilmentStacking::StrongestOnly,
+            true,
             source_id,
         )
     }
@@ -305,7 +545,11 @@ fn create_effect_from_status(
 mod tests {
     use super::*;
     use crate::config::{ensure_constants_initialized, ensure_dot_registry_initialized};
-    use crate::damage::FinalDamage;
+    use crate::damage::{FinalDamage, PendingStatusEffect};
+    use crate::source::TemplateSource;
+    use loot_core::types::StatType;
+    use rand::SeedableRng;
+    use std::collections::HashSet;
 
     fn setup() {
         ensure_constants_initialized();
@@ -351,6 +595,70 @@ mod tests {
         assert!((result.damage_reduced_by_resists - 50.0).abs() < 1.0);
     }
 
+    #[test]
+    fn test_resistance_modifiers_apply_and_survive_rebuild() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.set_template(
+            TemplateSource::new("target")
+                .with_modifier(StatType::AddedLife, 450.0)
+                .with_modifier(StatType::FireResistance, 50.0),
+        );
+        defender.current_life = 500.0;
+        defender
+            .resistance_modifiers
+            .insert(DamageType::Fire, -25.0);
+        defender.rebuild();
+        assert_eq!(defender.current_life, 500.0);
+        assert_eq!(defender.resistance_modifiers[&DamageType::Fire], -25.0);
+
+        let packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+        // 50% resist - 25 from the modifier = 25% effective
+        assert!((result.total_damage - 75.0).abs() < 1e-9);
+
+        // Clamped to the configured floor after applying
+        defender
+            .resistance_modifiers
+            .insert(DamageType::Fire, -1000.0);
+        let min = constants().resistances.min_value;
+        assert_eq!(defender.modified_resistance(DamageType::Fire), min);
+        assert_eq!(defender.resistance(DamageType::Fire), 50.0);
+    }
+
+    #[test]
+    fn test_static_increases_damage_taken() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.current_life = 500.0;
+        let shock = |id: &str, magnitude: f64| {
+            Effect::new_ailment(
+                id,
+                id,
+                StatusEffect::Static,
+                2.0,
+                magnitude,
+                0.0,
+                0.5,
+                crate::types::AilmentStacking::Unlimited,
+                true,
+                "enemy",
+            )
+        };
+        defender.effects.push(shock("static_1", 1.0));
+        defender.effects.push(shock("static_2", 0.5));
+        // Only the strongest applies: +15%, not +22.5%
+        assert!((defender.incoming_damage_multiplier() - 1.15).abs() < 1e-9);
+
+        let packet = make_test_packet(vec![(DamageType::Chaos, 100.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.total_damage - 115.0).abs() < 1e-9);
+
+        defender.effects.push(shock("static_3", 100.0));
+        let cap = constants().shock.max_damage_taken;
+        assert!((defender.incoming_damage_multiplier() - (1.0 + cap)).abs() < 1e-9);
+    }
+
     #[test]
     fn test_armour_reduction() {
         setup();
@@ -367,6 +675,26 @@ mod tests {
         assert!(result.total_damage < 100.0);
     }
 
+    #[test]
+    fn test_physical_reduction_cap() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.current_life = 200.0;
+        defender.armour.base = 100_000.0;
+        defender.physical_damage_reduction = 90.0;
+
+        let packet = make_test_packet(vec![(DamageType::Physical, 100.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+
+        // Resolution agrees with the capped display value
+        let shown = defender.total_physical_reduction(100.0);
+        let cap = constants().armour.max_physical_reduction;
+        assert!((shown - cap).abs() < 1e-9);
+        assert!((result.total_damage - (100.0 - shown)).abs() < 1e-9);
+        let reduced = result.damage_reduced_by_armour + result.damage_reduced_by_physical_dr;
+        assert!((reduced - shown).abs() < 1e-9);
+    }
+
     #[test]
     fn test_evasion_cap() {
         setup();
@@ -405,6 +733,90 @@ mod tests {
         assert!((new_defender.current_life - 75.0).abs() < 1.0);
     }
 
+    #[test]
+    fn test_chaos_bypasses_es_by_default() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.current_life = 100.0;
+        defender.current_energy_shield = 50.0;
+        defender.max_energy_shield = 50.0;
+
+        let packet = make_test_packet(vec![(DamageType::Chaos, 30.0), (DamageType::Fire, 20.0)]);
+        let (new_defender, result) = resolve_damage(&defender, &packet);
+
+        // Fire is absorbed by ES, chaos goes straight to life
+        assert!((result.damage_blocked_by_es - 20.0).abs() < 1e-9);
+        assert!((new_defender.current_energy_shield - 30.0).abs() < 1e-9);
+        assert!((new_defender.current_life - 70.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_damage_taken_from_mana_first() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.damage_to_mana_percent = 30.0;
+        defender.current_life = 200.0;
+        defender.current_mana = 100.0;
+        defender.current_energy_shield = 20.0;
+        defender.max_energy_shield = 20.0;
+
+        let packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
+        let (new_defender, result) = resolve_damage(&defender, &packet);
+
+        // 30 to mana, 20 to ES, the remaining 50 to life
+        assert!((result.damage_taken_from_mana - 30.0).abs() < 1e-9);
+        assert!((new_defender.current_mana - 70.0).abs() < 1e-9);
+        assert!((result.damage_blocked_by_es - 20.0).abs() < 1e-9);
+        assert!((new_defender.current_life - 150.0).abs() < 1e-9);
+
+        // Mana runs out: the uncovered share overflows to ES/life
+        defender.current_mana = 10.0;
+        let (new_defender, result) = resolve_damage(&defender, &packet);
+        assert!((result.damage_taken_from_mana - 10.0).abs() < 1e-9);
+        assert!(new_defender.current_mana.abs() < 1e-9);
+        assert!((new_defender.current_life - 130.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stun_threshold_and_immunity() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.max_life.base = 100.0;
+        defender.current_life = 100.0;
+        let threshold = constants().stun.threshold * 100.0;
+
+        let big = make_test_packet(vec![(DamageType::Fire, threshold + 10.0)]);
+        let (_, result) = resolve_damage(&defender, &big);
+        assert!(result.stunned);
+        assert!((result.stun_magnitude - (threshold + 10.0) / 100.0).abs() < 1e-9);
+
+        let small = make_test_packet(vec![(DamageType::Fire, threshold - 10.0)]);
+        let (_, result) = resolve_damage(&defender, &small);
+        assert!(!result.stunned);
+
+        defender.stun_immune = true;
+        let (_, result) = resolve_damage(&defender, &big);
+        assert!(!result.stunned);
+        assert!(result.stun_magnitude > 0.0);
+    }
+
+    #[test]
+    fn test_chaos_hits_es_when_bypass_disabled() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.chaos_bypasses_es = false;
+        defender.current_life = 100.0;
+        defender.current_energy_shield = 50.0;
+        defender.max_energy_shield = 50.0;
+
+        let packet = make_test_packet(vec![(DamageType::Chaos, 30.0)]);
+        let (new_defender, result) = resolve_damage(&defender, &packet);
+
+        assert!((result.damage_blocked_by_es - 30.0).abs() < 1e-9);
+        assert!((new_defender.current_energy_shield - 20.0).abs() < 1e-9);
+        assert!((new_defender.current_life - 100.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_killing_blow() {
         setup();
@@ -420,6 +832,199 @@ mod tests {
         assert!(new_defender.current_life <= 0.0);
     }
 
+    #[test]
+    fn test_overkill_damage() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.max_life = crate::stat_block::StatValue::with_base(100.0);
+        defender.current_life = 100.0;
+
+        let packet = make_test_packet(vec![(DamageType::Fire, 500.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+
+        assert!(result.is_killing_blow);
+        assert!((result.overkill_damage - 400.0).abs() < 1e-9);
+
+        // Non-lethal hits report no overkill
+        let packet = make_test_packet(vec![(DamageType::Fire, 50.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!(!result.is_killing_blow);
+        assert!(result.overkill_damage.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_overkill_measured_from_remaining_life() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.max_life = crate::stat_block::StatValue::with_base(100.0);
+        defender.current_life = 50.0;
+
+        let packet = make_test_packet(vec![(DamageType::Chaos, 200.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.overkill_damage - 150.0).abs() < 1e-9);
+
+        // Culling strikes kill without exceeding remaining life
+        defender.current_life = 20.0;
+        let mut packet = make_test_packet(vec![(DamageType::Chaos, 10.0)]);
+        packet.culling_strike = 15.0;
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!(result.culled);
+        assert_eq!(result.overkill_damage, 0.0);
+    }
+
+    #[test]
+    fn test_mitigation_breakdown_sums_to_prevented() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.current_life = 10000.0;
+        defender.current_energy_shield = 20.0;
+        defender.max_energy_shield = 20.0;
+        defender.fire_resistance.base = 50.0;
+        defender.armour.base = 500.0;
+        defender.physical_damage_reduction = 10.0;
+        defender.reduced_damage_taken = 10.0;
+
+        let packet = make_test_packet(vec![
+            (DamageType::Physical, 200.0),
+            (DamageType::Fire, 100.0),
+        ]);
+        let (new_defender, result) = resolve_damage(&defender, &packet);
+        let breakdown = result.mitigation_breakdown();
+
+        assert!((breakdown.raw_damage - 300.0).abs() < 1e-9);
+        assert!(breakdown.resists.amount > 0.0);
+        assert!(breakdown.armour.amount > 0.0);
+        assert!(breakdown.physical_dr.amount > 0.0);
+        assert!(breakdown.damage_reduction.amount > 0.0);
+        assert!((breakdown.energy_shield.amount - 20.0).abs() < 1e-9);
+
+        let life_lost = defender.current_life - new_defender.current_life;
+        let prevented_percent = (300.0 - life_lost) / 300.0 * 100.0;
+        assert!((breakdown.total_percent() - prevented_percent).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_recompute_on_buff_from_dot_config() {
+        use crate::damage::PendingStatusEffect;
+        setup();
+        let mut registry =
+            crate::config::load_dot_configs(std::path::Path::new("../config/dots.toml")).unwrap();
+        let mut attacker = StatBlock::with_id("attacker");
+        let mut packet = make_test_packet(vec![(DamageType::Chaos, 10.0)]);
+        let mut poison =
+            PendingStatusEffect::new_with_dot(StatusEffect::Poison, 10.0, 2.0, 1.0, 20.0);
+        poison.fixed_chance = Some(1.0);
+        poison.dot_origin = Some(DotOrigin { base_dps: 20.0 });
+        packet.status_effects_to_apply.push(poison);
+
+        let poison_dps_after_buff = |registry: &DotRegistry, attacker: &mut StatBlock| {
+            attacker.dot_multiplier = 0.0;
+            let defender = StatBlock::new();
+            let (mut target, result) =
+                resolve_damage_with_registry(&defender, &packet, registry, &mut rand::thread_rng());
+            assert_eq!(result.effects_applied.len(), 1);
+            // +50% DoT multiplier after the poison was applied
+            attacker.dot_multiplier = 0.5;
+            target.refresh_dot_dps(attacker);
+            target.active_effects()[0].dps()
+        };
+
+        // The shipped poison config snapshots its DPS
+        assert!((poison_dps_after_buff(&registry, &mut attacker) - 20.0).abs() < 1e-9);
+
+        // With recompute_on_buff the applied poison follows the attacker
+        let mut config = registry.get("poison").unwrap().clone();
+        config.recompute_on_buff = true;
+        registry.register(config);
+        assert!((poison_dps_after_buff(&registry, &mut attacker) - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_damage_blocked_is_capped_by_hit() {
+        use crate::combat::DeterministicRng;
+        setup();
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+        defender.block_chance.base = 75.0;
+        defender.block_amount.base = 100.0;
+
+        // Block could absorb 100, but only 30 was left to block
+        let packet = make_test_packet(vec![(DamageType::Fire, 30.0)]);
+        let (_, result) = resolve_damage_with_rng(&defender, &packet, &mut DeterministicRng);
+        assert!(result.was_blocked);
+        assert!((result.damage_blocked - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_flat_block_absorb_ignores_block_roll() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.current_life = 1000.0;
+        defender.flat_block_absorb = 20.0;
+
+        let packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!(!result.was_blocked);
+        assert!((result.total_damage - 80.0).abs() < 1e-9);
+        assert!((result.damage_absorbed_by_flat_block - 20.0).abs() < 1e-9);
+
+        // Whether or not the block roll succeeds, the absorb still applies
+        defender.block_chance.base = 50.0;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut outcomes = HashSet::new();
+        for _ in 0..32 {
+            let (_, result) = resolve_damage_with_rng(&defender, &packet, &mut rng);
+            outcomes.insert(result.was_blocked);
+            assert!((result.total_damage - 80.0).abs() < 1e-9);
+        }
+        assert_eq!(outcomes.len(), 2);
+    }
+
+    #[test]
+    fn test_cull_requires_crit() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.max_life = crate::stat_block::StatValue::with_base(100.0);
+        defender.current_life = 20.0;
+
+        // 10 damage leaves the target at 10% life, under the 15% threshold
+        let mut packet = make_test_packet(vec![(DamageType::Fire, 10.0)]);
+        packet.culling_strike = 15.0;
+        packet.cull_requires_crit = true;
+
+        let (new_defender, result) = resolve_damage(&defender, &packet);
+        assert!(!result.culled);
+        assert!(new_defender.is_alive());
+
+        packet.is_critical = true;
+        let (new_defender, result) = resolve_damage(&defender, &packet);
+        assert!(result.culled);
+        assert!(!new_defender.is_alive());
+    }
+
+    #[test]
+    fn test_more_damage_vs_full_life_only_on_opener() {
+        use crate::damage::{BaseDamage, CritMode, DamagePacketGenerator};
+        setup();
+        let attacker = StatBlock::with_id("rogue");
+        let skill = DamagePacketGenerator {
+            id: "ambush".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            crit_override: Some(CritMode::Never),
+            more_vs_full_life: Some(1.0),
+            ..Default::default()
+        };
+        let mut defender = StatBlock::new();
+        defender.max_life = crate::stat_block::StatValue::with_base(1000.0);
+        defender.current_life = 1000.0;
+
+        let (defender, opener) = resolve_damage(&defender, &attacker.attack(&skill));
+        let (_, follow_up) = resolve_damage(&defender, &attacker.attack(&skill));
+        assert!((opener.total_damage - 200.0).abs() < 1e-9);
+        assert!((follow_up.total_damage - 100.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_penetration() {
         setup();
@@ -455,4 +1060,235 @@ mod tests {
         // Total: 50 + 75 = 125
         assert!((result.total_damage - 125.0).abs() < 1.0);
     }
+
+    #[test]
+    fn test_built_packet_resolves() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.max_life.base = 1000.0;
+        defender.current_life = 1000.0;
+
+        let packet = DamagePacket::builder("attacker", "scripted")
+            .damage(DamageType::Physical, 40.0)
+            .damage(DamageType::Fire, 60.0)
+            .penetration(DamageType::Fire, 10.0)
+            .accuracy(1_000_000.0)
+            .spell(true)
+            .status(PendingStatusEffect::new_with_dot(
+                StatusEffect::Burn,
+                5000.0,
+                4.0,
+                1.0,
+                25.0,
+            ))
+            .build();
+        assert_eq!(packet.damages.len(), 2);
+        assert_eq!(packet.fire_pen, 10.0);
+        assert!(packet.is_spell);
+
+        let (new_defender, result) = resolve_damage(&defender, &packet);
+        // 10% fire pen against 0% resistance: 40 + 60 * 1.1
+        assert!((result.total_damage - 106.0).abs() < 1e-9);
+        assert!((new_defender.current_life - 894.0).abs() < 1e-9);
+        assert_eq!(result.effects_applied.len(), 1);
+        assert_eq!(new_defender.effects_of_status(StatusEffect::Burn).len(), 1);
+    }
+
+    #[test]
+    fn test_ailment_avoid_blocks_status() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender
+            .set_template(TemplateSource::new("target").with_modifier(StatType::AddedLife, 50.0));
+        defender.current_life = 100.0;
+        defender
+            .ailment_avoid_chance
+            .insert(StatusEffect::Poison, 100.0);
+        defender.rebuild();
+        assert_eq!(defender.current_life, 100.0);
+
+        let guaranteed =
+            PendingStatusEffect::new_fixed_chance(StatusEffect::Poison, 1.0, 2.0, 1.0, 0.0);
+        let packet = DamagePacket::builder("attacker", "venom")
+            .damage(DamageType::Chaos, 1.0)
+            .status(PendingStatusEffect::new_with_dot(
+                StatusEffect::Poison,
+                1_000_000.0,
+                2.0,
+                1.0,
+                10.0,
+            ))
+            .status(guaranteed)
+            .status(PendingStatusEffect::new(
+                StatusEffect::Bleed,
+                1_000_000.0,
+                2.0,
+                1.0,
+            ))
+            .build();
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        for _ in 0..20 {
+            let (_, result) = resolve_damage_with_rng(&defender, &packet, &mut rng);
+            assert_eq!(result.effects_applied.len(), 1);
+            assert_eq!(
+                result.effects_applied[0].status(),
+                Some(StatusEffect::Bleed)
+            );
+        }
+
+        // Global avoidance covers every status
+        defender.all_ailment_avoid = 100.0;
+        let (_, result) = resolve_damage_with_rng(&defender, &packet, &mut rng);
+        assert!(result.effects_applied.is_empty());
+    }
+
+    #[test]
+    fn test_traced_resolution_records_each_stage() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.max_life.base = 1000.0;
+        defender.current_life = 1000.0;
+        defender.fire_resistance.base = 50.0;
+        defender.armour.base = 500.0;
+        defender.max_energy_shield = 30.0;
+        defender.current_energy_shield = 30.0;
+
+        let packet = DamagePacket::builder("attacker", "flame_strike")
+            .damage(DamageType::Physical, 100.0)
+            .damage(DamageType::Fire, 100.0)
+            .accuracy(1_000_000.0)
+            .build();
+
+        let (_, plain) = resolve_damage(&defender, &packet);
+        let (traced_defender, result, trace) = resolve_damage_traced(&defender, &packet);
+        assert!((result.total_damage - plain.total_damage).abs() < 1e-9);
+
+        let stages: Vec<TraceStage> = trace.steps.iter().map(|s| s.stage).collect();
+        assert_eq!(
+            stages,
+            vec![
+                TraceStage::Resistance,
+                TraceStage::Armour,
+                TraceStage::PhysicalDr,
+                TraceStage::Evasion,
+                TraceStage::Block,
+                TraceStage::DamageReduction,
+                TraceStage::Ward,
+                TraceStage::Mana,
+                TraceStage::EnergyShield,
+                TraceStage::Life,
+            ]
+        );
+        assert_eq!(trace.raw_damage, 200.0);
+        let resist = trace.step(TraceStage::Resistance).unwrap();
+        assert!((resist.after - 150.0).abs() < 1e-9);
+        let armour = trace.step(TraceStage::Armour).unwrap();
+        assert!((armour.reduced() - result.damage_reduced_by_armour).abs() < 1e-9);
+        assert!((trace.step(TraceStage::EnergyShield).unwrap().reduced() - 30.0).abs() < 1e-9);
+        let life = trace.step(TraceStage::Life).unwrap();
+        assert!((life.after - (1000.0 - traced_defender.current_life)).abs() < 1e-9);
+        assert!(trace.to_string().contains("Armour"));
+    }
+
+    #[test]
+    fn test_packet_json_round_trip_resolves_identically() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.max_life.base = 1000.0;
+        defender.current_life = 1000.0;
+        defender.fire_resistance.base = 40.0;
+
+        let packet = DamagePacket::builder("attacker", "flame_strike")
+            .damage(DamageType::Physical, 40.0)
+            .damage(DamageType::Fire, 60.0)
+            .crit(2.5)
+            .penetration(DamageType::Fire, 15.0)
+            .accuracy(1_000_000.0)
+            .status(PendingStatusEffect::new_with_dot(
+                StatusEffect::Burn,
+                5000.0,
+                4.0,
+                1.0,
+                25.0,
+            ))
+            .status(PendingStatusEffect::new_fixed_chance(
+                StatusEffect::Chill,
+                0.5,
+                2.0,
+                0.25,
+                0.0,
+            ))
+            .build();
+
+        let json = serde_json::to_string(&packet).unwrap();
+        let received: DamagePacket = serde_json::from_str(&json).unwrap();
+        assert_eq!(received, packet);
+
+        let mut local_rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut remote_rng = rand::rngs::StdRng::seed_from_u64(7);
+        let (local_defender, local) = resolve_damage_with_rng(&defender, &packet, &mut local_rng);
+        let (remote_defender, remote) =
+            resolve_damage_with_rng(&defender, &received, &mut remote_rng);
+
+        assert!(received.is_critical);
+        assert_eq!(
+            serde_json::to_value(&local).unwrap(),
+            serde_json::to_value(&remote).unwrap()
+        );
+        assert_eq!(local_defender.current_life, remote_defender.current_life);
+    }
+
+    #[test]
+    fn test_reflected_damage_from_raw_hit() {
+        setup();
+        let mut defender = StatBlock::new();
+        defender.max_life.base = 1000.0;
+        defender.current_life = 1000.0;
+        defender.armour.base = 5000.0;
+        defender.fire_resistance.add_flat(75.0);
+        defender.reflect_physical_percent = 20.0;
+        defender.reflect_elemental_percent = 10.0;
+        defender.reflect_basis = ReflectBasis::Raw;
+
+        let packet = make_test_packet(vec![
+            (DamageType::Physical, 100.0),
+            (DamageType::Fire, 100.0),
+            (DamageType::Chaos, 100.0),
+        ]);
+
+        // Raw damage, unaffected by armour and resistance; chaos isn't reflected
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.reflected_damage - 30.0).abs() < 1e-9);
+
+        // Capped at the defender's life
+        defender.current_life = 12.0;
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.reflected_damage - 12.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_landed_reflect_ignores_blocked_damage() {
+        use crate::combat::DeterministicRng;
+        setup();
+        let mut defender = StatBlock::new();
+        defender.max_life.base = 1000.0;
+        defender.current_life = 1000.0;
+        defender.fire_resistance.add_flat(50.0);
+        defender.reflect_elemental_percent = 10.0;
+        assert_eq!(defender.reflect_basis, ReflectBasis::Landed);
+
+        // Only the 50 fire left after resistance is reflected
+        let packet = make_test_packet(vec![(DamageType::Fire, 100.0)]);
+        let (_, result) = resolve_damage(&defender, &packet);
+        assert!((result.reflected_damage - 5.0).abs() < 1e-9);
+
+        // A fully blocked hit reflects nothing (the midpoint roll blocks at 75%)
+        defender.block_chance.base = 75.0;
+        defender.block_amount.base = 1000.0;
+        let (_, result) = resolve_damage_with_rng(&defender, &packet, &mut DeterministicRng);
+        assert!(result.was_blocked);
+        assert!(result.total_damage.abs() < 1e-9);
+        assert!(result.reflected_damage.abs() < 1e-9);
+    }
 }
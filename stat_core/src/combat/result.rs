@@ -17,6 +17,12 @@ pub struct CombatResult {
     // === Mitigation Info ===
     /// Damage absorbed by energy shield
     pub damage_blocked_by_es: f64,
+    /// Damage absorbed by ward before mana, ES and life
+    #[serde(default)]
+    pub damage_absorbed_by_ward: f64,
+    /// Damage taken from mana before ES/life (`damage_to_mana_percent`)
+    #[serde(default)]
+    pub damage_taken_from_mana: f64,
     /// Damage reduced by armour
     pub damage_reduced_by_armour: f64,
     /// Damage reduced by resistances
@@ -27,6 +33,9 @@ pub struct CombatResult {
     // === Effects Applied ===
     /// Effects that were applied (unified Effect system)
     pub effects_applied: Vec<Effect>,
+    /// Defender effects removed because they expire on hit
+    #[serde(default)]
+    pub effects_broken: Vec<Effect>,
 
     // === State Changes ===
     /// ES before damage
@@ -43,8 +52,12 @@ pub struct CombatResult {
     pub was_dodged: bool,
     /// Whether the hit was blocked
     pub was_blocked: bool,
-    /// Damage absorbed by block
+    /// Damage actually removed from the hit by block (never more than the
+    /// damage left to block, unlike the defender's block amount)
     pub damage_blocked: f64,
+    /// Damage absorbed by flat block absorb (applies to every hit)
+    #[serde(default)]
+    pub damage_absorbed_by_flat_block: f64,
 
     // === Additional Mitigation ===
     /// Damage reduced by physical damage reduction (%)
@@ -63,8 +76,25 @@ pub struct CombatResult {
     // === Flags ===
     /// Whether this was a killing blow
     pub is_killing_blow: bool,
+    /// Damage in excess of the target's remaining life + ES (killing blows only)
+    #[serde(default)]
+    pub overkill_damage: f64,
     /// Whether the evasion cap was triggered
     pub triggered_evasion_cap: bool,
+    /// Final hit damage as a fraction of the defender's max life
+    #[serde(default)]
+    pub stun_magnitude: f64,
+    /// Whether the hit exceeded the defender's stun threshold
+    #[serde(default)]
+    pub stunned: bool,
+    /// Whether the hit restarts the defender's ES recharge delay
+    #[serde(default)]
+    pub resets_es_recharge: bool,
+
+    // === Reflect ===
+    /// Damage reflected back at the attacker; the caller applies it
+    #[serde(default)]
+    pub reflected_damage: f64,
 }
 
 impl Default for CombatResult {
@@ -73,10 +103,13 @@ impl Default for CombatResult {
             damage_taken: Vec::new(),
             total_damage: 0.0,
             damage_blocked_by_es: 0.0,
+            damage_absorbed_by_ward: 0.0,
+            damage_taken_from_mana: 0.0,
             damage_reduced_by_armour: 0.0,
             damage_reduced_by_resists: 0.0,
             damage_prevented_by_evasion: 0.0,
             effects_applied: Vec::new(),
+            effects_broken: Vec::new(),
             es_before: 0.0,
             es_after: 0.0,
             life_before: 0.0,
@@ -84,13 +117,19 @@ impl Default for CombatResult {
             was_dodged: false,
             was_blocked: false,
             damage_blocked: 0.0,
+            damage_absorbed_by_flat_block: 0.0,
             damage_reduced_by_physical_dr: 0.0,
             damage_reduced_by_dr: 0.0,
             life_gained_on_kill: 0.0,
             mana_gained_on_kill: 0.0,
             culled: false,
             is_killing_blow: false,
+            overkill_damage: 0.0,
             triggered_evasion_cap: false,
+            stun_magnitude: 0.0,
+            stunned: false,
+            resets_es_recharge: false,
+            reflected_damage: 0.0,
         }
     }
 }
@@ -156,6 +195,13 @@ impl CombatResult {
             parts.push(format!("{:.0} blocked", self.damage_blocked));
         }
 
+        if self.damage_absorbed_by_flat_block > 0.0 {
+            parts.push(format!(
+                "{:.0} absorbed",
+                self.damage_absorbed_by_flat_block
+            ));
+        }
+
         if self.damage_reduced_by_physical_dr > 0.0 {
             parts.push(format!(
                 "{:.0} phys DR",
@@ -171,6 +217,10 @@ impl CombatResult {
             parts.push("CULLED".to_string());
         }
 
+        if self.reflected_damage > 0.0 {
+            parts.push(format!("{:.0} reflected", self.reflected_damage));
+        }
+
         if self.is_killing_blow {
             parts.push("FATAL".to_string());
         }
@@ -182,6 +232,30 @@ impl CombatResult {
         }
     }
 
+    /// Collect how much damage each mitigation layer prevented
+    pub fn mitigation_breakdown(&self) -> MitigationBreakdown {
+        let raw_damage = self.total_raw_damage();
+        let layer = |amount: f64| MitigationLayer {
+            amount,
+            percent_of_raw: if raw_damage > 0.0 {
+                amount / raw_damage * 100.0
+            } else {
+                0.0
+            },
+        };
+
+        MitigationBreakdown {
+            raw_damage,
+            resists: layer(self.damage_reduced_by_resists),
+            armour: layer(self.damage_reduced_by_armour),
+            physical_dr: layer(self.damage_reduced_by_physical_dr),
+            evasion: layer(self.damage_prevented_by_evasion),
+            block: layer(self.damage_blocked + self.damage_absorbed_by_flat_block),
+            damage_reduction: layer(self.damage_reduced_by_dr),
+            energy_shield: layer(self.damage_blocked_by_es),
+        }
+    }
+
     /// Get life change
     pub fn life_change(&self) -> f64 {
         self.life_after - self.life_before
@@ -193,6 +267,55 @@ impl CombatResult {
     }
 }
 
+/// Damage prevented by a single mitigation layer
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct MitigationLayer {
+    /// Damage prevented by this layer
+    pub amount: f64,
+    /// Share of raw incoming damage this layer accounted for (0-100)
+    pub percent_of_raw: f64,
+}
+
+/// Per-layer summary of how incoming damage was prevented
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MitigationBreakdown {
+    /// Total raw damage before mitigation
+    pub raw_damage: f64,
+    pub resists: MitigationLayer,
+    pub armour: MitigationLayer,
+    pub physical_dr: MitigationLayer,
+    pub evasion: MitigationLayer,
+    pub block: MitigationLayer,
+    pub damage_reduction: MitigationLayer,
+    /// Damage absorbed by energy shield before reaching life
+    pub energy_shield: MitigationLayer,
+}
+
+impl MitigationBreakdown {
+    /// All layers in resolution order, with display labels
+    pub fn layers(&self) -> [(&'static str, MitigationLayer); 7] {
+        [
+            ("Resists", self.resists),
+            ("Armour", self.armour),
+            ("Physical DR", self.physical_dr),
+            ("Evasion", self.evasion),
+            ("Block", self.block),
+            ("Damage Reduction", self.damage_reduction),
+            ("Energy Shield", self.energy_shield),
+        ]
+    }
+
+    /// Total damage prevented from reaching life
+    pub fn total_prevented(&self) -> f64 {
+        self.layers().iter().map(|(_, l)| l.amount).sum()
+    }
+
+    /// Share of raw incoming damage prevented from reaching life (0-100)
+    pub fn total_percent(&self) -> f64 {
+        self.layers().iter().map(|(_, l)| l.percent_of_raw).sum()
+    }
+}
+
 /// Damage breakdown for a single damage type
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DamageTaken {
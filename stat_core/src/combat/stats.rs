@@ -0,0 +1,140 @@
+//! CombatStats - Per-thread counters for profiling combat simulations
+
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+
+thread_local! {
+    static COMBAT_STATS_ENABLED: Cell<bool> = const { Cell::new(false) };
+    static COMBAT_STATS: Cell<CombatStats> = const { Cell::new(CombatStats::new()) };
+}
+
+/// How often key combat steps ran on the current thread
+///
+/// Counting is off by default; turn it on per thread with
+/// `set_combat_stats_enabled`. Counters then accumulate across every packet
+/// generated and resolved on the thread until read with `take_combat_stats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CombatStats {
+    /// Crit rolls made while generating damage packets
+    pub crit_rolls: u64,
+    /// Spell dodge rolls made during resolution
+    pub dodge_rolls: u64,
+    /// Hits checked against the defender's evasion cap
+    pub evasion_checks: u64,
+    /// Block rolls made during resolution
+    pub block_rolls: u64,
+    /// Chance-based status application rolls
+    pub status_rolls: u64,
+    /// Status effects actually applied to a defender
+    pub status_applications: u64,
+    /// StatBlock rebuilds
+    pub rebuilds: u64,
+}
+
+impl CombatStats {
+    const fn new() -> Self {
+        CombatStats {
+            crit_rolls: 0,
+            dodge_rolls: 0,
+            evasion_checks: 0,
+            block_rolls: 0,
+            status_rolls: 0,
+            status_applications: 0,
+            rebuilds: 0,
+        }
+    }
+
+    /// Total chance rolls (crit, dodge, block and status)
+    pub fn rng_calls(&self) -> u64 {
+        self.crit_rolls + self.dodge_rolls + self.block_rolls + self.status_rolls
+    }
+}
+
+/// Turn combat counting on or off for the current thread
+pub fn set_combat_stats_enabled(enabled: bool) {
+    COMBAT_STATS_ENABLED.with(|flag| flag.set(enabled));
+}
+
+/// Whether combat counting is on for the current thread
+pub fn combat_stats_enabled() -> bool {
+    COMBAT_STATS_ENABLED.with(Cell::get)
+}
+
+/// Read and reset the current thread's combat counters
+pub fn take_combat_stats() -> CombatStats {
+    COMBAT_STATS.with(|stats| stats.replace(CombatStats::new()))
+}
+
+/// Increment a counter on the current thread
+///
+/// A no-op unless counting is enabled, so untraced simulations only pay
+/// for the flag check.
+#[inline]
+pub(crate) fn record(update: impl FnOnce(&mut CombatStats)) {
+    if !combat_stats_enabled() {
+        return;
+    }
+    COMBAT_STATS.with(|stats| {
+        let mut current = stats.get();
+        update(&mut current);
+        stats.set(current);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::resolve_damage;
+    use crate::config::{ensure_constants_initialized, ensure_dot_registry_initialized};
+    use crate::damage::{BaseDamage, DamagePacketGenerator};
+    use crate::source::TemplateSource;
+    use crate::stat_block::StatBlock;
+    use crate::types::SkillTag;
+    use loot_core::types::{DamageType, StatType};
+
+    #[test]
+    fn test_counters_track_resolved_hits() {
+        ensure_constants_initialized();
+        ensure_dot_registry_initialized();
+        let attacker = StatBlock::with_id("player");
+        let skill = DamagePacketGenerator {
+            id: "fireball".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 10.0, 10.0)],
+            weapon_effectiveness: 0.0,
+            tags: vec![SkillTag::Spell],
+            ..Default::default()
+        };
+        let mut defender = StatBlock::with_id("dummy");
+        defender.set_template(
+            TemplateSource::new("dummy")
+                .with_modifier(StatType::BlockChance, 10.0)
+                .with_modifier(StatType::SpellDodgeChance, 10.0),
+        );
+        defender.rebuild();
+        set_combat_stats_enabled(true);
+        take_combat_stats();
+
+        for _ in 0..5 {
+            resolve_damage(&defender, &attacker.attack(&skill));
+        }
+        let stats = take_combat_stats();
+
+        assert_eq!(stats.crit_rolls, 5);
+        // Every dodged spell skips the evasion and block steps
+        assert_eq!(stats.dodge_rolls, 5);
+        assert_eq!(stats.evasion_checks, stats.block_rolls);
+        assert!(stats.block_rolls <= 5);
+        assert_eq!(stats.rebuilds, 0);
+        assert!(stats.rng_calls() >= 10);
+        assert_eq!(take_combat_stats(), CombatStats::default());
+
+        let mut rebuilt = StatBlock::with_id("rebuilt");
+        rebuilt.rebuild_from_sources(&[]);
+        assert_eq!(take_combat_stats().rebuilds, 1);
+
+        set_combat_stats_enabled(false);
+        resolve_damage(&defender, &attacker.attack(&skill));
+        rebuilt.rebuild_from_sources(&[]);
+        assert_eq!(take_combat_stats(), CombatStats::default());
+    }
+}
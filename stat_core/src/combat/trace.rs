@@ -0,0 +1,104 @@
+//! CombatTrace - Step-by-step record of a hit's resolution
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A stage of damage resolution, in the order resolution runs them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceStage {
+    /// Spell dodge (only recorded when the hit was dodged)
+    Dodge,
+    Resistance,
+    Armour,
+    PhysicalDr,
+    Evasion,
+    /// Block roll plus flat block absorb
+    Block,
+    DamageReduction,
+    /// Damage absorbed by ward
+    Ward,
+    /// Share of the hit taken from mana
+    Mana,
+    EnergyShield,
+    /// Damage that reached life; `after` is the life actually lost
+    Life,
+}
+
+impl fmt::Display for TraceStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            TraceStage::Dodge => "Dodge",
+            TraceStage::Resistance => "Resistance",
+            TraceStage::Armour => "Armour",
+            TraceStage::PhysicalDr => "Physical DR",
+            TraceStage::Evasion => "Evasion",
+            TraceStage::Block => "Block",
+            TraceStage::DamageReduction => "Damage Reduction",
+            TraceStage::Ward => "Ward",
+            TraceStage::Mana => "Mana",
+            TraceStage::EnergyShield => "Energy Shield",
+            TraceStage::Life => "Life",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Damage total entering and leaving one stage
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TraceStep {
+    pub stage: TraceStage,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl TraceStep {
+    /// Damage removed by this stage (negative if the stage added damage,
+    /// e.g. negative resistance)
+    pub fn reduced(&self) -> f64 {
+        self.before - self.after
+    }
+}
+
+/// Every stage a hit went through, built by `resolve_damage_traced`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CombatTrace {
+    /// Total packet damage before any stage
+    pub raw_damage: f64,
+    /// Stages in resolution order
+    pub steps: Vec<TraceStep>,
+}
+
+impl CombatTrace {
+    /// Look up the step for a stage
+    pub fn step(&self, stage: TraceStage) -> Option<&TraceStep> {
+        self.steps.iter().find(|s| s.stage == stage)
+    }
+
+    /// Record a stage, taking its input from the previous stage's output
+    pub(crate) fn record(&mut self, stage: TraceStage, after: f64) {
+        let before = self.steps.last().map_or(self.raw_damage, |s| s.after);
+        self.steps.push(TraceStep {
+            stage,
+            before,
+            after,
+        });
+    }
+}
+
+impl fmt::Display for CombatTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Raw: {:.1}", self.raw_damage)?;
+        for step in &self.steps {
+            writeln!(
+                f,
+                "  {}: {:.1} -> {:.1} ({:+.1})",
+                step.stage,
+                step.before,
+                step.after,
+                -step.reduced()
+            )?;
+        }
+        Ok(())
+    }
+}
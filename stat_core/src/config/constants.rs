@@ -52,7 +52,7 @@ pub fn ensure_constants_initialized() {
 }
 
 /// Tunable game constants
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GameConstants {
     #[serde(default)]
     pub resistances: ResistanceConstants,
@@ -66,19 +66,12 @@ pub struct GameConstants {
     pub leech: LeechConstants,
     #[serde(default)]
     pub energy_shield: EnergyShieldConstants,
-}
-
-impl Default for GameConstants {
-    fn default() -> Self {
-        GameConstants {
-            resistances: ResistanceConstants::default(),
-            armour: ArmourConstants::default(),
-            evasion: EvasionConstants::default(),
-            crit: CritConstants::default(),
-            leech: LeechConstants::default(),
-            energy_shield: EnergyShieldConstants::default(),
-        }
-    }
+    #[serde(default)]
+    pub stun: StunConstants,
+    #[serde(default)]
+    pub slow: SlowConstants,
+    #[serde(default)]
+    pub shock: ShockConstants,
 }
 
 impl GameConstants {
@@ -128,12 +121,16 @@ pub struct ArmourConstants {
     /// Formula constant: reduction = armour / (armour + constant * damage)
     #[serde(default = "default_damage_constant")]
     pub damage_constant: f64,
+    /// Cap on the combined armour and flat physical reduction percentage
+    #[serde(default = "default_max_physical_reduction")]
+    pub max_physical_reduction: f64,
 }
 
 impl Default for ArmourConstants {
     fn default() -> Self {
         ArmourConstants {
             damage_constant: 5.0,
+            max_physical_reduction: 90.0,
         }
     }
 }
@@ -142,6 +139,10 @@ fn default_damage_constant() -> f64 {
     5.0
 }
 
+fn default_max_physical_reduction() -> f64 {
+    90.0
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvasionConstants {
     /// Scaling factor for evasion formula: cap = accuracy / (1 + evasion / scale_factor)
@@ -208,12 +209,20 @@ pub struct EnergyShieldConstants {
     /// Whether ES takes damage before life
     #[serde(default = "default_damage_priority")]
     pub damage_priority: String,
+    /// Fraction of max ES recharged per second once recharge starts
+    #[serde(default = "default_es_recharge_rate")]
+    pub recharge_rate: f64,
+    /// Seconds without being hit before ES starts recharging
+    #[serde(default = "default_es_recharge_delay")]
+    pub recharge_delay: f64,
 }
 
 impl Default for EnergyShieldConstants {
     fn default() -> Self {
         EnergyShieldConstants {
             damage_priority: "first".to_string(),
+            recharge_rate: default_es_recharge_rate(),
+            recharge_delay: default_es_recharge_delay(),
         }
     }
 }
@@ -222,6 +231,87 @@ fn default_damage_priority() -> String {
     "first".to_string()
 }
 
+fn default_es_recharge_rate() -> f64 {
+    0.33
+}
+
+fn default_es_recharge_delay() -> f64 {
+    2.0
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StunConstants {
+    /// A hit stuns when its final damage exceeds this fraction of max life
+    #[serde(default = "default_stun_threshold")]
+    pub threshold: f64,
+}
+
+impl Default for StunConstants {
+    fn default() -> Self {
+        StunConstants {
+            threshold: default_stun_threshold(),
+        }
+    }
+}
+
+fn default_stun_threshold() -> f64 {
+    0.25
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlowConstants {
+    /// Fraction of action speed a chill or slow removes per point of magnitude
+    #[serde(default = "default_slow_per_magnitude")]
+    pub reduction_per_magnitude: f64,
+    /// Most action speed a chill or slow can remove
+    #[serde(default = "default_max_slow")]
+    pub max_reduction: f64,
+}
+
+impl Default for SlowConstants {
+    fn default() -> Self {
+        SlowConstants {
+            reduction_per_magnitude: default_slow_per_magnitude(),
+            max_reduction: default_max_slow(),
+        }
+    }
+}
+
+fn default_slow_per_magnitude() -> f64 {
+    0.3
+}
+
+fn default_max_slow() -> f64 {
+    0.7
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShockConstants {
+    /// Extra damage taken per point of Static magnitude
+    #[serde(default = "default_shock_per_magnitude")]
+    pub damage_taken_per_magnitude: f64,
+    /// Most extra damage taken a Static can cause
+    #[serde(default = "default_max_shock")]
+    pub max_damage_taken: f64,
+}
+
+impl Default for ShockConstants {
+    fn default() -> Self {
+        ShockConstants {
+            damage_taken_per_magnitude: default_shock_per_magnitude(),
+            max_damage_taken: default_max_shock(),
+        }
+    }
+}
+
+fn default_shock_per_magnitude() -> f64 {
+    0.15
+}
+
+fn default_max_shock() -> f64 {
+    0.5
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -232,6 +322,9 @@ mod tests {
         assert!((constants.resistances.max_cap - 100.0).abs() < f64::EPSILON);
         assert!((constants.armour.damage_constant - 5.0).abs() < f64::EPSILON);
         assert!((constants.crit.base_multiplier - 1.5).abs() < f64::EPSILON);
+        assert!((constants.stun.threshold - 0.25).abs() < f64::EPSILON);
+        assert!((constants.slow.reduction_per_magnitude - 0.3).abs() < f64::EPSILON);
+        assert!((constants.shock.damage_taken_per_magnitude - 0.15).abs() < f64::EPSILON);
     }
 
     #[test]
@@ -0,0 +1,348 @@
+//! Enemy template loading
+//!
+//! Enemy templates describe ready-to-fight stat blocks (life, defenses,
+//! weapon damage, granted skills) in TOML:
+//!
+//! ```toml
+//! [[enemies]]
+//! id = "goblin"
+//! name = "Goblin"
+//! life = 80.0
+//! armour = 40.0
+//! skills = ["basic_attack"]
+//!
+//! [enemies.resistances]
+//! fire = 25.0
+//!
+//! [enemies.damage]
+//! physical = [4.0, 9.0]
+//! attack_speed = 1.3
+//!
+//! [enemies.stats]
+//! increased_physical_damage = 20.0
+//! ```
+
+use super::ConfigError;
+use crate::source::TemplateSource;
+use crate::stat_block::StatBlock;
+use loot_core::types::{DamageType, StatType};
+use serde::de::IntoDeserializer;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Container for enemy template configurations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnemiesConfig {
+    #[serde(default)]
+    pub enemies: Vec<EnemyConfig>,
+}
+
+/// A single enemy template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EnemyConfig {
+    pub id: String,
+    pub name: String,
+    /// Base maximum life
+    pub life: f64,
+    /// Base maximum mana
+    #[serde(default)]
+    pub mana: f64,
+    #[serde(default)]
+    pub energy_shield: f64,
+    /// Whether chaos damage skips this enemy's energy shield
+    #[serde(default = "default_chaos_bypasses_es")]
+    pub chaos_bypasses_es: bool,
+    #[serde(default)]
+    pub armour: f64,
+    #[serde(default)]
+    pub evasion: f64,
+    /// Overrides the default accuracy when set
+    #[serde(default)]
+    pub accuracy: Option<f64>,
+    #[serde(default)]
+    pub resistances: EnemyResistances,
+    #[serde(default)]
+    pub damage: EnemyDamage,
+    /// Skill IDs this enemy can use
+    #[serde(default)]
+    pub skills: Vec<String>,
+    /// Additional modifiers keyed by stat name (e.g. `increased_physical_damage`)
+    #[serde(default)]
+    pub stats: HashMap<String, f64>,
+}
+
+/// Base resistances for an enemy template
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EnemyResistances {
+    #[serde(default)]
+    pub fire: f64,
+    #[serde(default)]
+    pub cold: f64,
+    #[serde(default)]
+    pub lightning: f64,
+    #[serde(default)]
+    pub chaos: f64,
+}
+
+/// Weapon damage for an enemy template, as `[min, max]` ranges
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EnemyDamage {
+    #[serde(default)]
+    pub physical: [f64; 2],
+    #[serde(default)]
+    pub fire: [f64; 2],
+    #[serde(default)]
+    pub cold: [f64; 2],
+    #[serde(default)]
+    pub lightning: [f64; 2],
+    #[serde(default)]
+    pub chaos: [f64; 2],
+    #[serde(default = "default_attack_speed")]
+    pub attack_speed: f64,
+    #[serde(default)]
+    pub crit_chance: f64,
+}
+
+fn default_chaos_bypasses_es() -> bool {
+    true
+}
+
+fn default_attack_speed() -> f64 {
+    1.0
+}
+
+impl Default for EnemyDamage {
+    fn default() -> Self {
+        EnemyDamage {
+            physical: [0.0, 0.0],
+            fire: [0.0, 0.0],
+            cold: [0.0, 0.0],
+            lightning: [0.0, 0.0],
+            chaos: [0.0, 0.0],
+            attack_speed: default_attack_speed(),
+            crit_chance: 0.0,
+        }
+    }
+}
+
+impl EnemyConfig {
+    /// Resolve the `stats` table into typed stat modifiers
+    pub fn parsed_stats(&self) -> Result<Vec<(StatType, f64)>, ConfigError> {
+        let mut parsed = Vec::with_capacity(self.stats.len());
+        for (name, value) in &self.stats {
+            let stat = StatType::deserialize(name.as_str().into_deserializer()).map_err(
+                |_: serde::de::value::Error| {
+                    ConfigError::ValidationError(format!(
+                        "Enemy '{}' has unknown stat '{}'",
+                        self.id, name
+                    ))
+                },
+            )?;
+            parsed.push((stat, *value));
+        }
+        Ok(parsed)
+    }
+
+    /// Build the template stats as a persistent source
+    ///
+    /// Values are stored relative to the StatBlock defaults, so applying the
+    /// source to a fresh block gives exactly the configured values.
+    pub fn template_source(&self) -> Result<TemplateSource, ConfigError> {
+        let defaults = StatBlock::with_id(self.id.clone());
+        let accuracy = self
+            .accuracy
+            .map_or(0.0, |accuracy| accuracy - defaults.accuracy.base);
+
+        let mut template = TemplateSource::new(self.id.clone())
+            .with_modifier(StatType::AddedLife, self.life - defaults.max_life.base)
+            .with_modifier(StatType::AddedMana, self.mana - defaults.max_mana.base)
+            .with_modifier(StatType::AddedEnergyShield, self.energy_shield)
+            .with_modifier(StatType::AddedArmour, self.armour - defaults.armour.base)
+            .with_modifier(StatType::AddedEvasion, self.evasion - defaults.evasion.base)
+            .with_modifier(StatType::AddedAccuracy, accuracy)
+            .with_modifier(StatType::FireResistance, self.resistances.fire)
+            .with_modifier(StatType::ColdResistance, self.resistances.cold)
+            .with_modifier(StatType::LightningResistance, self.resistances.lightning)
+            .with_modifier(StatType::ChaosResistance, self.resistances.chaos)
+            .with_weapon_damage(
+                DamageType::Physical,
+                self.damage.physical[0],
+                self.damage.physical[1],
+            )
+            .with_weapon_damage(DamageType::Fire, self.damage.fire[0], self.damage.fire[1])
+            .with_weapon_damage(DamageType::Cold, self.damage.cold[0], self.damage.cold[1])
+            .with_weapon_damage(
+                DamageType::Lightning,
+                self.damage.lightning[0],
+                self.damage.lightning[1],
+            )
+            .with_weapon_damage(
+                DamageType::Chaos,
+                self.damage.chaos[0],
+                self.damage.chaos[1],
+            );
+        template.weapon_attack_speed = self.damage.attack_speed;
+        template.weapon_crit_chance = self.damage.crit_chance;
+
+        for (stat, value) in self.parsed_stats()? {
+            template = template.with_modifier(stat, value);
+        }
+        Ok(template)
+    }
+
+    /// Build a ready-to-fight StatBlock with current resources at max
+    ///
+    /// The template is stored on the block as a `TemplateSource`, so its
+    /// stats are reapplied on every rebuild.
+    pub fn build(&self) -> Result<StatBlock, ConfigError> {
+        let mut block = StatBlock::with_id(self.id.clone());
+        block.chaos_bypasses_es = self.chaos_bypasses_es;
+        block.set_template(self.template_source()?);
+
+        block.current_life = block.max_life.compute();
+        block.current_mana = block.max_mana.compute();
+        block.current_energy_shield = block.max_energy_shield;
+
+        Ok(block)
+    }
+}
+
+/// Registry of enemy templates, loaded from TOML files
+#[derive(Debug, Clone, Default)]
+pub struct EnemyRegistry {
+    templates: HashMap<String, EnemyConfig>,
+}
+
+impl EnemyRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load all enemy templates from `*.toml` files in a directory (recursively)
+    pub fn load(dir: &Path) -> Result<Self, ConfigError> {
+        let mut registry = Self::new();
+        registry.load_dir(dir)?;
+        Ok(registry)
+    }
+
+    /// Parse enemy templates from a TOML string
+    pub fn parse(content: &str) -> Result<Self, ConfigError> {
+        let mut registry = Self::new();
+        let config: EnemiesConfig = super::parse_toml(content)?;
+        for enemy in config.enemies {
+            registry.register(enemy)?;
+        }
+        Ok(registry)
+    }
+
+    fn load_dir(&mut self, dir: &Path) -> Result<(), ConfigError> {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                self.load_dir(&path)?;
+            } else if path.extension().is_some_and(|ext| ext == "toml") {
+                let config: EnemiesConfig = super::load_toml(&path)?;
+                for enemy in config.enemies {
+                    self.register(enemy)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Register an enemy template, validating its stat names
+    pub fn register(&mut self, config: EnemyConfig) -> Result<(), ConfigError> {
+        config.parsed_stats()?;
+        self.templates.insert(config.id.clone(), config);
+        Ok(())
+    }
+
+    /// Get an enemy template by ID
+    pub fn get(&self, id: &str) -> Option<&EnemyConfig> {
+        self.templates.get(id)
+    }
+
+    /// List all enemy template IDs
+    pub fn enemy_ids(&self) -> impl Iterator<Item = &str> {
+        self.templates.keys().map(|s| s.as_str())
+    }
+
+    /// Spawn a fresh StatBlock for an enemy template, at full life
+    pub fn spawn(&self, id: &str) -> Option<StatBlock> {
+        self.get(id)
+            .map(|config| config.build().expect("enemy stats validated on register"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Effect, StatMod};
+
+    #[test]
+    fn test_load_and_spawn_goblin() {
+        let registry = EnemyRegistry::load(Path::new("../config/enemies")).unwrap();
+        let goblin = registry.spawn("goblin").expect("goblin template");
+
+        assert!((goblin.max_life.compute() - 80.0).abs() < f64::EPSILON);
+        assert!((goblin.current_life - 80.0).abs() < f64::EPSILON);
+        assert!((goblin.fire_resistance.compute() - 25.0).abs() < f64::EPSILON);
+        assert!((goblin.cold_resistance.compute() - 10.0).abs() < f64::EPSILON);
+        assert!(goblin.weapon_physical_max > goblin.weapon_physical_min);
+        assert_eq!(
+            registry.get("goblin").unwrap().skills,
+            vec!["basic_attack".to_string()]
+        );
+        assert!(registry.spawn("dragon").is_none());
+    }
+
+    #[test]
+    fn test_spawned_enemy_keeps_template_after_rebuild() {
+        let registry = EnemyRegistry::load(Path::new("../config/enemies")).unwrap();
+        let mut goblin = registry.spawn("goblin").expect("goblin template");
+        goblin.add_effect(Effect::new_stat_modifier(
+            "war_cry",
+            "War Cry",
+            1.0,
+            false,
+            vec![StatMod::new(StatType::IncreasedPhysicalDamage, 20.0, false)],
+            "goblin",
+        ));
+
+        // The effect expires, which rebuilds the block
+        let (goblin, _) = goblin.tick_effects(2.0);
+        assert!(goblin.effects.is_empty());
+        assert!((goblin.computed_max_life() - 80.0).abs() < 1e-9);
+        assert!((goblin.current_life - 80.0).abs() < 1e-9);
+        assert!((goblin.fire_resistance.compute() - 25.0).abs() < 1e-9);
+        assert!((goblin.armour.compute() - 40.0).abs() < 1e-9);
+        assert!((goblin.weapon_attack_speed - 1.3).abs() < 1e-9);
+
+        // The template is saved with the block
+        let json = serde_json::to_string(&goblin).unwrap();
+        let mut loaded: StatBlock = serde_json::from_str(&json).unwrap();
+        loaded.finalize_after_load();
+        assert!((loaded.computed_max_life() - 80.0).abs() < 1e-9);
+        assert_eq!(loaded.template(), goblin.template());
+    }
+
+    #[test]
+    fn test_unknown_stat_rejected() {
+        let toml = r#"
+[[enemies]]
+id = "bad"
+name = "Bad"
+life = 10.0
+
+[enemies.stats]
+added_nonsense = 5.0
+"#;
+        let err = EnemyRegistry::parse(toml).unwrap_err();
+        assert!(matches!(err, ConfigError::ValidationError(_)));
+    }
+}
@@ -2,18 +2,24 @@
 
 mod constants;
 mod dots;
+mod enemies;
 mod skills;
 
 pub use constants::{
     constants, constants_initialized, ensure_constants_initialized, init_constants,
     init_constants_default, ArmourConstants, CritConstants, EnergyShieldConstants,
-    EvasionConstants, GameConstants, LeechConstants, ResistanceConstants,
+    EvasionConstants, GameConstants, LeechConstants, ResistanceConstants, SlowConstants,
+    StunConstants,
 };
 pub use dots::{
     dot_registry, dot_registry_initialized, ensure_dot_registry_initialized, init_dot_registry,
-    init_dot_registry_default, load_dot_configs,
+    init_dot_registry_default, load_dot_configs, parse_dot_configs,
+};
+pub use enemies::{EnemiesConfig, EnemyConfig, EnemyDamage, EnemyRegistry, EnemyResistances};
+pub use skills::{
+    default_skills, load_skill_configs, parse_skill_configs, SkillConfig, SkillConfigError,
+    SkillsConfig,
 };
-pub use skills::{default_skills, load_skill_configs};
 
 use std::fs;
 use std::path::Path;
@@ -28,6 +34,8 @@ pub enum ConfigError {
     ParseError(#[from] toml::de::Error),
     #[error("Configuration validation error: {0}")]
     ValidationError(String),
+    #[error("Invalid skill: {0}")]
+    InvalidSkill(#[from] SkillConfigError),
 }
 
 /// Load a TOML file and deserialize it
@@ -1,44 +1,425 @@
 //! Skill configuration loading
 
 use super::ConfigError;
-use crate::damage::DamagePacketGenerator;
+use crate::damage::{
+    BaseDamage, CritMode, DamageConversions, DamagePacketGenerator, DamageTypeEffectiveness,
+    SkillStatusConversions,
+};
+use crate::types::SkillTag;
+use loot_core::types::{DamageType, StatusEffect};
+use serde::de::IntoDeserializer;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
+use thiserror::Error;
 
 /// Container for skill configurations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkillsConfig {
     #[serde(rename = "skills")]
-    pub skills: Vec<DamagePacketGenerator>,
+    pub skills: Vec<SkillConfig>,
 }
 
-/// Load skill configurations from a TOML file
-pub fn load_skill_configs(
-    path: &Path,
-) -> Result<HashMap<String, DamagePacketGenerator>, ConfigError> {
-    let config: SkillsConfig = super::load_toml(path)?;
+/// Raw skill definition as written in TOML
+///
+/// Fields are deliberately loosely typed (string tags, signed counts) so that
+/// malformed values reach validation instead of failing deep inside serde.
+/// Convert with `DamagePacketGenerator::try_from`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SkillConfig {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub base_damages: Vec<BaseDamage>,
+    #[serde(default)]
+    pub weapon_effectiveness: f64,
+    #[serde(default = "default_one")]
+    pub damage_effectiveness: f64,
+    #[serde(default = "default_one")]
+    pub aoe_damage_effectiveness: f64,
+    #[serde(default = "default_one")]
+    pub attack_speed_modifier: f64,
+    #[serde(default)]
+    pub base_crit_chance: f64,
+    #[serde(default)]
+    pub crit_multiplier_bonus: f64,
+    #[serde(default)]
+    pub crit_override: Option<CritMode>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub status_conversions: SkillStatusConversions,
+    #[serde(default)]
+    pub damage_conversions: DamageConversions,
+    #[serde(default)]
+    pub random_element_conversion: Option<f64>,
+    #[serde(default)]
+    pub type_effectiveness: DamageTypeEffectiveness,
+    #[serde(default)]
+    pub more_multipliers: Vec<(DamageType, f64)>,
+    #[serde(default)]
+    pub status_chance_increased: HashMap<String, f64>,
+    #[serde(default)]
+    pub on_hit_random_ailment: Option<(Vec<StatusEffect>, f64)>,
+    #[serde(default = "default_hits")]
+    pub hits_per_attack: i64,
+    #[serde(default)]
+    pub can_chain: bool,
+    #[serde(default)]
+    pub chain_count: i64,
+    #[serde(default)]
+    pub pierce_chance: f64,
+    #[serde(default)]
+    pub cull_requires_crit: bool,
+    #[serde(default)]
+    pub more_vs_full_life: Option<f64>,
+    #[serde(default)]
+    pub base_radius: f64,
+    #[serde(default)]
+    pub base_projectiles: i64,
+    #[serde(default)]
+    pub mana_cost: f64,
+    #[serde(default)]
+    pub cooldown: f64,
+}
 
+fn default_one() -> f64 {
+    1.0
+}
+
+fn default_hits() -> i64 {
+    1
+}
+
+/// Why a skill definition was rejected
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SkillConfigError {
+    #[error("skill has an empty id")]
+    EmptyId,
+    #[error("skill '{skill}': {field} must be a finite number")]
+    NotFinite { skill: String, field: String },
+    #[error("skill '{skill}': {field} = {value} is outside {min}..={max}")]
+    OutOfRange {
+        skill: String,
+        field: String,
+        value: f64,
+        min: f64,
+        max: f64,
+    },
+    #[error("skill '{skill}': hits_per_attack = {value} must be between 1 and {max}", max = u32::MAX)]
+    InvalidHitCount { skill: String, value: i64 },
+    #[error("skill '{skill}': chain_count = {value} must be between 0 and {max}", max = u32::MAX)]
+    InvalidChainCount { skill: String, value: i64 },
+    #[error("skill '{skill}': base_projectiles = {value} must be between 0 and {max}", max = u32::MAX)]
+    InvalidProjectileCount { skill: String, value: i64 },
+    #[error("skill '{skill}': unknown tag '{tag}'")]
+    UnknownTag { skill: String, tag: String },
+    #[error("skill '{skill}': unknown status effect '{status}' in status_chance_increased")]
+    UnknownStatus { skill: String, status: String },
+    #[error("skill '{skill}': on_hit_random_ailment lists no ailments")]
+    EmptyAilmentList { skill: String },
+    #[error("skill '{skill}': base damage min {min} exceeds max {max}")]
+    InvertedDamageRange { skill: String, min: f64, max: f64 },
+}
+
+/// Check that a value is finite and within `min..=max`
+fn check_range(
+    skill: &str,
+    field: &str,
+    value: f64,
+    min: f64,
+    max: f64,
+) -> Result<(), SkillConfigError> {
+    if !value.is_finite() {
+        return Err(SkillConfigError::NotFinite {
+            skill: skill.to_string(),
+            field: field.to_string(),
+        });
+    }
+    if value < min || value > max {
+        return Err(SkillConfigError::OutOfRange {
+            skill: skill.to_string(),
+            field: field.to_string(),
+            value,
+            min,
+            max,
+        });
+    }
+    Ok(())
+}
+
+impl TryFrom<SkillConfig> for DamagePacketGenerator {
+    type Error = SkillConfigError;
+
+    fn try_from(config: SkillConfig) -> Result<Self, Self::Error> {
+        if config.id.trim().is_empty() {
+            return Err(SkillConfigError::EmptyId);
+        }
+        let id = config.id.as_str();
+        let unbounded = f64::INFINITY;
+
+        for damage in &config.base_damages {
+            check_range(id, "base_damages.min", damage.min, 0.0, unbounded)?;
+            check_range(id, "base_damages.max", damage.max, 0.0, unbounded)?;
+            if damage.min > damage.max {
+                return Err(SkillConfigError::InvertedDamageRange {
+                    skill: id.to_string(),
+                    min: damage.min,
+                    max: damage.max,
+                });
+            }
+        }
+
+        check_range(
+            id,
+            "weapon_effectiveness",
+            config.weapon_effectiveness,
+            0.0,
+            unbounded,
+        )?;
+        check_range(
+            id,
+            "damage_effectiveness",
+            config.damage_effectiveness,
+            0.0,
+            unbounded,
+        )?;
+        check_range(
+            id,
+            "aoe_damage_effectiveness",
+            config.aoe_damage_effectiveness,
+            0.0,
+            unbounded,
+        )?;
+        check_range(
+            id,
+            "attack_speed_modifier",
+            config.attack_speed_modifier,
+            f64::MIN_POSITIVE,
+            unbounded,
+        )?;
+        check_range(id, "base_crit_chance", config.base_crit_chance, 0.0, 100.0)?;
+        check_range(
+            id,
+            "crit_multiplier_bonus",
+            config.crit_multiplier_bonus,
+            -unbounded,
+            unbounded,
+        )?;
+        check_range(id, "pierce_chance", config.pierce_chance, 0.0, 1.0)?;
+        check_range(id, "base_radius", config.base_radius, 0.0, unbounded)?;
+        check_range(id, "mana_cost", config.mana_cost, 0.0, unbounded)?;
+        check_range(id, "cooldown", config.cooldown, 0.0, unbounded)?;
+
+        let status = &config.status_conversions;
+        for (field, value) in [
+            (
+                "status_conversions.physical_to_poison",
+                status.physical_to_poison,
+            ),
+            ("status_conversions.chaos_to_poison", status.chaos_to_poison),
+            (
+                "status_conversions.physical_to_bleed",
+                status.physical_to_bleed,
+            ),
+            ("status_conversions.fire_to_burn", status.fire_to_burn),
+            ("status_conversions.cold_to_freeze", status.cold_to_freeze),
+            ("status_conversions.cold_to_chill", status.cold_to_chill),
+            (
+                "status_conversions.lightning_to_static",
+                status.lightning_to_static,
+            ),
+            ("status_conversions.chaos_to_fear", status.chaos_to_fear),
+            (
+                "status_conversions.physical_to_slow",
+                status.physical_to_slow,
+            ),
+            ("status_conversions.cold_to_slow", status.cold_to_slow),
+        ] {
+            check_range(id, field, value, 0.0, 1.0)?;
+        }
+
+        let conversions = &config.damage_conversions;
+        for (field, value) in [
+            (
+                "damage_conversions.physical_to_fire",
+                conversions.physical_to_fire,
+            ),
+            (
+                "damage_conversions.physical_to_cold",
+                conversions.physical_to_cold,
+            ),
+            (
+                "damage_conversions.physical_to_lightning",
+                conversions.physical_to_lightning,
+            ),
+            (
+                "damage_conversions.physical_to_chaos",
+                conversions.physical_to_chaos,
+            ),
+            (
+                "damage_conversions.lightning_to_fire",
+                conversions.lightning_to_fire,
+            ),
+            (
+                "damage_conversions.lightning_to_cold",
+                conversions.lightning_to_cold,
+            ),
+            ("damage_conversions.cold_to_fire", conversions.cold_to_fire),
+            (
+                "damage_conversions.fire_to_chaos",
+                conversions.fire_to_chaos,
+            ),
+        ] {
+            check_range(id, field, value, 0.0, 1.0)?;
+        }
+        if let Some(fraction) = config.random_element_conversion {
+            check_range(id, "random_element_conversion", fraction, 0.0, 1.0)?;
+        }
+
+        let effectiveness = &config.type_effectiveness;
+        for (field, value) in [
+            ("type_effectiveness.physical", effectiveness.physical),
+            ("type_effectiveness.fire", effectiveness.fire),
+            ("type_effectiveness.cold", effectiveness.cold),
+            ("type_effectiveness.lightning", effectiveness.lightning),
+            ("type_effectiveness.chaos", effectiveness.chaos),
+        ] {
+            check_range(id, field, value, 0.0, unbounded)?;
+        }
+
+        for (damage_type, more) in &config.more_multipliers {
+            check_range(
+                id,
+                &format!("more_multipliers.{}", damage_type).to_lowercase(),
+                *more,
+                -1.0,
+                unbounded,
+            )?;
+        }
+
+        if let Some(more) = config.more_vs_full_life {
+            check_range(id, "more_vs_full_life", more, -1.0, unbounded)?;
+        }
+
+        if let Some((ailments, chance)) = &config.on_hit_random_ailment {
+            if ailments.is_empty() {
+                return Err(SkillConfigError::EmptyAilmentList {
+                    skill: id.to_string(),
+                });
+            }
+            check_range(id, "on_hit_random_ailment", *chance, 0.0, 1.0)?;
+        }
+
+        for (status, value) in &config.status_chance_increased {
+            StatusEffect::deserialize(status.as_str().into_deserializer()).map_err(
+                |_: serde::de::value::Error| SkillConfigError::UnknownStatus {
+                    skill: id.to_string(),
+                    status: status.clone(),
+                },
+            )?;
+            check_range(
+                id,
+                &format!("status_chance_increased.{}", status),
+                *value,
+                -1.0,
+                unbounded,
+            )?;
+        }
+
+        let tags = config
+            .tags
+            .iter()
+            .map(|tag| {
+                SkillTag::deserialize(tag.as_str().into_deserializer()).map_err(
+                    |_: serde::de::value::Error| SkillConfigError::UnknownTag {
+                        skill: id.to_string(),
+                        tag: tag.clone(),
+                    },
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let hits_per_attack = u32::try_from(config.hits_per_attack)
+            .ok()
+            .filter(|&hits| hits >= 1)
+            .ok_or_else(|| SkillConfigError::InvalidHitCount {
+                skill: id.to_string(),
+                value: config.hits_per_attack,
+            })?;
+        let chain_count =
+            u32::try_from(config.chain_count).map_err(|_| SkillConfigError::InvalidChainCount {
+                skill: id.to_string(),
+                value: config.chain_count,
+            })?;
+        let base_projectiles = u32::try_from(config.base_projectiles).map_err(|_| {
+            SkillConfigError::InvalidProjectileCount {
+                skill: id.to_string(),
+                value: config.base_projectiles,
+            }
+        })?;
+
+        Ok(DamagePacketGenerator {
+            id: config.id,
+            name: config.name,
+            base_damages: config.base_damages,
+            weapon_effectiveness: config.weapon_effectiveness,
+            damage_effectiveness: config.damage_effectiveness,
+            aoe_damage_effectiveness: config.aoe_damage_effectiveness,
+            attack_speed_modifier: config.attack_speed_modifier,
+            base_crit_chance: config.base_crit_chance,
+            crit_multiplier_bonus: config.crit_multiplier_bonus,
+            crit_override: config.crit_override,
+            tags,
+            status_conversions: config.status_conversions,
+            damage_conversions: config.damage_conversions,
+            random_element_conversion: config.random_element_conversion,
+            type_effectiveness: config.type_effectiveness,
+            more_multipliers: config.more_multipliers,
+            status_chance_increased: config.status_chance_increased,
+            on_hit_random_ailment: config.on_hit_random_ailment,
+            hits_per_attack,
+            can_chain: config.can_chain,
+            chain_count,
+            pierce_chance: config.pierce_chance,
+            cull_requires_crit: config.cull_requires_crit,
+            more_vs_full_life: config.more_vs_full_life,
+            base_radius: config.base_radius,
+            base_projectiles,
+            mana_cost: config.mana_cost,
+            cooldown: config.cooldown,
+        })
+    }
+}
+
+/// Validate every skill in a config and key them by ID
+fn validate_skills(
+    config: SkillsConfig,
+) -> Result<HashMap<String, DamagePacketGenerator>, ConfigError> {
     let mut map = HashMap::new();
     for skill in config.skills {
+        let skill = DamagePacketGenerator::try_from(skill)?;
         map.insert(skill.id.clone(), skill);
     }
 
     Ok(map)
 }
 
+/// Load skill configurations from a TOML file
+pub fn load_skill_configs(
+    path: &Path,
+) -> Result<HashMap<String, DamagePacketGenerator>, ConfigError> {
+    let config: SkillsConfig = super::load_toml(path)?;
+    validate_skills(config)
+}
+
 /// Load skill configurations from a TOML string
 pub fn parse_skill_configs(
     content: &str,
 ) -> Result<HashMap<String, DamagePacketGenerator>, ConfigError> {
     let config: SkillsConfig = super::parse_toml(content)?;
-
-    let mut map = HashMap::new();
-    for skill in config.skills {
-        map.insert(skill.id.clone(), skill);
-    }
-
-    Ok(map)
+    validate_skills(config)
 }
 
 /// Get default skill configurations
@@ -89,6 +470,88 @@ fire_to_burn = 0.50
         assert!((fireball.status_conversions.fire_to_burn - 0.50).abs() < f64::EPSILON);
     }
 
+    fn skill_error(body: &str) -> SkillConfigError {
+        let toml = format!("[[skills]]\nid = \"broken\"\nname = \"Broken\"\n{}", body);
+        match parse_skill_configs(&toml) {
+            Err(ConfigError::InvalidSkill(err)) => err,
+            other => panic!("expected InvalidSkill, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_malformed_skills_rejected() {
+        assert_eq!(
+            skill_error("hits_per_attack = -2"),
+            SkillConfigError::InvalidHitCount {
+                skill: "broken".to_string(),
+                value: -2,
+            }
+        );
+        assert_eq!(
+            skill_error("hits_per_attack = 0"),
+            SkillConfigError::InvalidHitCount {
+                skill: "broken".to_string(),
+                value: 0,
+            }
+        );
+        assert_eq!(
+            skill_error("damage_effectiveness = nan"),
+            SkillConfigError::NotFinite {
+                skill: "broken".to_string(),
+                field: "damage_effectiveness".to_string(),
+            }
+        );
+        assert_eq!(
+            skill_error("tags = [\"attack\", \"frost\"]"),
+            SkillConfigError::UnknownTag {
+                skill: "broken".to_string(),
+                tag: "frost".to_string(),
+            }
+        );
+        assert!(matches!(
+            skill_error("pierce_chance = 1.5"),
+            SkillConfigError::OutOfRange { field, .. } if field == "pierce_chance"
+        ));
+        assert!(matches!(
+            skill_error("[skills.damage_conversions]\nphysical_to_fire = -0.5"),
+            SkillConfigError::OutOfRange { field, .. } if field == "damage_conversions.physical_to_fire"
+        ));
+        assert!(matches!(
+            skill_error("more_multipliers = [[\"fire\", -1.5]]"),
+            SkillConfigError::OutOfRange { field, .. } if field == "more_multipliers.fire"
+        ));
+        assert_eq!(
+            skill_error("on_hit_random_ailment = [[], 0.5]"),
+            SkillConfigError::EmptyAilmentList {
+                skill: "broken".to_string(),
+            }
+        );
+        assert!(matches!(
+            skill_error("more_vs_full_life = -2.0"),
+            SkillConfigError::OutOfRange { field, .. } if field == "more_vs_full_life"
+        ));
+        assert_eq!(
+            skill_error("[skills.status_chance_increased]\nignite = 0.2"),
+            SkillConfigError::UnknownStatus {
+                skill: "broken".to_string(),
+                status: "ignite".to_string(),
+            }
+        );
+        assert!(matches!(
+            skill_error("[[skills.base_damages]]\ntype = \"fire\"\nmin = 50\nmax = 10"),
+            SkillConfigError::InvertedDamageRange { .. }
+        ));
+    }
+
+    #[test]
+    fn test_unknown_skill_field_is_parse_error() {
+        let toml = "[[skills]]\nid = \"typo\"\nname = \"Typo\"\nhits_per_atack = 2\n";
+        assert!(matches!(
+            parse_skill_configs(toml),
+            Err(ConfigError::ParseError(_))
+        ));
+    }
+
     #[test]
     fn test_default_skills_loads_all() {
         let skills = super::default_skills();
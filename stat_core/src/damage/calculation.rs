@@ -1,11 +1,114 @@
 //! Damage calculation - turning a skill + stats into a DamagePacket
 
-use super::{DamagePacket, DamagePacketGenerator, PendingStatusEffect, SkillStatusConversions};
-use crate::config::dot_registry;
-use crate::stat_block::{StatBlock, StatusEffectData, StatusEffectStats};
+use super::{
+    CritMode, DamageConversions, DamagePacket, DamagePacketGenerator, DamageRange, DamageTypeRange,
+    PendingStatusEffect, SkillStatusConversions,
+};
+use crate::combat::record;
+use crate::config::{dot_registry, dot_registry_initialized};
+use crate::dot::DotRegistry;
+use crate::stat_block::{StatBlock, StatValue, StatusEffectData, StatusEffectStats};
+use crate::types::DotOrigin;
 use loot_core::types::{DamageType, StatusEffect};
+use rand::seq::SliceRandom;
 use rand::Rng;
 use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Every damage type, in display order
+const DAMAGE_TYPES: [DamageType; 5] = [
+    DamageType::Physical,
+    DamageType::Fire,
+    DamageType::Cold,
+    DamageType::Lightning,
+    DamageType::Chaos,
+];
+
+/// Position of a damage type in `DAMAGE_TYPES`, for a stable damage order
+fn damage_type_order(damage_type: DamageType) -> usize {
+    DAMAGE_TYPES
+        .iter()
+        .position(|&d| d == damage_type)
+        .unwrap_or(DAMAGE_TYPES.len())
+}
+
+/// Elements a random element conversion can pick from
+const RANDOM_CONVERSION_ELEMENTS: [DamageType; 3] =
+    [DamageType::Fire, DamageType::Cold, DamageType::Lightning];
+
+/// Move fractions of physical base damage to other damage types
+fn convert_physical(damages: &mut HashMap<DamageType, f64>, conversions: &[(DamageType, f64)]) {
+    let Some(&physical) = damages.get(&DamageType::Physical) else {
+        return;
+    };
+    for &(to, fraction) in conversions {
+        let amount = physical * fraction.clamp(0.0, 1.0);
+        *damages.entry(DamageType::Physical).or_insert(0.0) -= amount;
+        *damages.entry(to).or_insert(0.0) += amount;
+    }
+}
+
+/// Apply random element and skill conversions to each base damage type on
+/// its own, returning `(origin, destination, amount)` so scaling can tell
+/// converted damage from unconverted
+///
+/// Results are sorted by destination then origin in `DAMAGE_TYPES` order, so
+/// packets list damage types the same way for the same rolls.
+fn convert_by_origin(
+    base_damages: HashMap<DamageType, f64>,
+    random_conversions: &[(DamageType, f64)],
+    conversions: &DamageConversions,
+) -> Vec<(DamageType, DamageType, f64)> {
+    let mut result = Vec::new();
+    for (origin, amount) in base_damages {
+        let mut damages = HashMap::from([(origin, amount)]);
+        convert_physical(&mut damages, random_conversions);
+        if conversions.has_conversions() {
+            damages = conversions.apply(&damages);
+        }
+        result.extend(damages.into_iter().map(|(to, amount)| (origin, to, amount)));
+    }
+    result.sort_by_key(|&(origin, to, _)| (damage_type_order(to), damage_type_order(origin)));
+    result
+}
+
+/// The attacker's global damage stat for a damage type
+fn damage_stat(attacker: &StatBlock, damage_type: DamageType) -> &StatValue {
+    match damage_type {
+        DamageType::Physical => &attacker.global_physical_damage,
+        DamageType::Fire => &attacker.global_fire_damage,
+        DamageType::Cold => &attacker.global_cold_damage,
+        DamageType::Lightning => &attacker.global_lightning_damage,
+        DamageType::Chaos => &attacker.global_chaos_damage,
+    }
+}
+
+/// Increased multiplier for damage that started as `origin` and was
+/// converted to `damage_type`
+///
+/// With `converted_damage_double_scaling` converted damage counts as both
+/// types, multiplying the origin's increased modifiers into the destination's.
+fn increased_multiplier(attacker: &StatBlock, origin: DamageType, damage_type: DamageType) -> f64 {
+    let increased = damage_stat(attacker, damage_type).total_increased_multiplier();
+    if attacker.converted_damage_double_scaling && origin != damage_type {
+        increased * damage_stat(attacker, origin).total_increased_multiplier()
+    } else {
+        increased
+    }
+}
+
+/// The global DoT registry, or an empty one if none has been loaded
+///
+/// An empty registry applies every built-in status with default durations
+/// and no DoT damage, so damage calculation never needs a loaded config.
+fn loaded_dot_registry() -> &'static DotRegistry {
+    static EMPTY: OnceLock<DotRegistry> = OnceLock::new();
+    if dot_registry_initialized() {
+        dot_registry()
+    } else {
+        EMPTY.get_or_init(DotRegistry::new)
+    }
+}
 
 /// Calculate damage from a skill and attacker's stats
 pub fn calculate_damage(
@@ -13,6 +116,18 @@ pub fn calculate_damage(
     skill: &DamagePacketGenerator,
     source_id: String,
     rng: &mut impl Rng,
+) -> DamagePacket {
+    calculate_damage_with_registry(attacker, skill, source_id, loaded_dot_registry(), rng)
+}
+
+/// Calculate damage using `registry` for status effect configs instead of the
+/// global DoT registry (e.g. for tools or tests with custom DoT configs)
+pub fn calculate_damage_with_registry(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+    source_id: String,
+    registry: &DotRegistry,
+    rng: &mut impl Rng,
 ) -> DamagePacket {
     let mut packet = DamagePacket::new(source_id, skill.id.clone());
 
@@ -31,13 +146,7 @@ pub fn calculate_damage(
 
     // Weapon damage if this is an attack skill
     if skill.is_attack() && skill.weapon_effectiveness > 0.0 {
-        for damage_type in [
-            DamageType::Physical,
-            DamageType::Fire,
-            DamageType::Cold,
-            DamageType::Lightning,
-            DamageType::Chaos,
-        ] {
+        for damage_type in DAMAGE_TYPES {
             let (min, max) = attacker.weapon_damage(damage_type);
             if max > 0.0 {
                 let scaled_min = min * skill.weapon_effectiveness;
@@ -52,29 +161,32 @@ pub fn calculate_damage(
         }
     }
 
+    // Step 1b: Convert physical to one element picked per hit
+    let random_conversion = skill.random_element_conversion.map(|fraction| {
+        let element = *RANDOM_CONVERSION_ELEMENTS.choose(rng).unwrap();
+        (element, fraction)
+    });
+
     // Step 2: Apply damage type conversions (before scaling)
-    let converted_damages = if skill.damage_conversions.has_conversions() {
-        skill.damage_conversions.apply(&base_damages)
-    } else {
-        base_damages
-    };
+    let converted_damages = convert_by_origin(
+        base_damages,
+        random_conversion.as_slice(),
+        &skill.damage_conversions,
+    );
 
     // Step 3: Apply damage scaling to each type
-    for (damage_type, base_amount) in converted_damages {
+    let movement_mult = attacker.movement_damage_multiplier();
+    let delivery_mult = attacker.delivery_damage_multiplier(skill);
+    for (origin, damage_type, base_amount) in converted_damages {
         if base_amount <= 0.0 {
             continue;
         }
 
-        let damage_stat = match damage_type {
-            DamageType::Physical => &attacker.global_physical_damage,
-            DamageType::Fire => &attacker.global_fire_damage,
-            DamageType::Cold => &attacker.global_cold_damage,
-            DamageType::Lightning => &attacker.global_lightning_damage,
-            DamageType::Chaos => &attacker.global_chaos_damage,
-        };
-
-        let increased_mult = damage_stat.total_increased_multiplier();
-        let more_mult = damage_stat.total_more_multiplier();
+        let increased_mult = increased_multiplier(attacker, origin, damage_type);
+        let more_mult = damage_stat(attacker, damage_type).total_more_multiplier()
+            * movement_mult
+            * delivery_mult
+            * skill.more_multiplier(damage_type);
         let type_eff = skill.type_effectiveness.get(damage_type);
 
         let scaled_damage =
@@ -86,26 +198,35 @@ pub fn calculate_damage(
 
     // Step 4: Calculate crit
     let crit_chance = calculate_crit_chance(attacker, skill);
+    record(|s| s.crit_rolls += 1);
     packet.is_critical = rng.gen::<f64>() < crit_chance / 100.0;
 
     if packet.is_critical {
-        packet.crit_multiplier = attacker.computed_crit_multiplier() + skill.crit_multiplier_bonus;
+        packet.crit_multiplier = attacker.crit_multiplier_for(skill);
         // Apply crit multiplier to all damages
         for damage in &mut packet.damages {
             damage.amount *= packet.crit_multiplier;
         }
     }
 
-    // Step 4: Set penetration from attacker stats (including physical)
-    packet.fire_pen = attacker.fire_penetration.compute();
-    packet.cold_pen = attacker.cold_penetration.compute();
-    packet.lightning_pen = attacker.lightning_penetration.compute();
-    packet.chaos_pen = attacker.chaos_penetration.compute();
+    // Step 4: Set penetration from attacker stats and active penetration effects
+    packet.fire_pen =
+        attacker.fire_penetration.compute() + attacker.effect_penetration(DamageType::Fire);
+    packet.cold_pen =
+        attacker.cold_penetration.compute() + attacker.effect_penetration(DamageType::Cold);
+    packet.lightning_pen = attacker.lightning_penetration.compute()
+        + attacker.effect_penetration(DamageType::Lightning);
+    packet.chaos_pen =
+        attacker.chaos_penetration.compute() + attacker.effect_penetration(DamageType::Chaos);
 
     // Step 5: Set accuracy and metadata from attacker stats
     packet.accuracy = attacker.accuracy.compute();
     packet.is_spell = skill.is_spell();
     packet.culling_strike = attacker.culling_strike;
+    packet.cull_requires_crit = skill.cull_requires_crit;
+    packet.more_vs_full_life = skill.more_vs_full_life;
+    packet.radius = skill.effective_radius(attacker.area_of_effect_increased);
+    packet.projectiles = skill.effective_projectiles(attacker.additional_projectiles);
     packet.life_on_kill = attacker.life_on_kill;
     packet.mana_on_kill = attacker.mana_on_kill;
 
@@ -113,22 +234,17 @@ pub fn calculate_damage(
     // Status damage is converted from hit damage (combining skill + player conversions)
     // Status damage determines: chance to apply = status_damage / target_max_health
     // For damaging DoTs: DoT DPS = base_dot_percent * status_damage
+    // Conversions read the hit damage without removing it, so the same
+    // physical damage can feed bleed and poison at full value each
     let damages_vec: Vec<(DamageType, f64)> = packet
         .damages
         .iter()
         .map(|d| (d.damage_type, d.amount))
         .collect();
 
-    for status in [
-        StatusEffect::Poison,
-        StatusEffect::Bleed,
-        StatusEffect::Burn,
-        StatusEffect::Freeze,
-        StatusEffect::Chill,
-        StatusEffect::Static,
-        StatusEffect::Fear,
-        StatusEffect::Slow,
-    ] {
+    // Statuses come from the configured DoT types rather than a fixed list;
+    // an empty registry applies every built-in status
+    for status in registry.statuses() {
         // Combine skill conversions + player stat conversions
         let status_damage = calculate_combined_status_damage(
             status,
@@ -138,51 +254,32 @@ pub fn calculate_damage(
         );
 
         if status_damage > 0.0 {
-            let registry = dot_registry();
-            let stats = attacker.status_effect_stats.get_stats(status);
-            let base_duration = registry.get_base_duration(status);
-            let duration = base_duration * (1.0 + stats.duration_increased);
-
-            // Apply increased status damage (per-type + global already folded in during aggregation)
-            let status_damage = status_damage * (1.0 + stats.status_damage_increased);
-
-            // If crit, apply crit-specific status damage bonus
-            let status_damage = if packet.is_critical {
-                status_damage
-                    * (1.0
-                        + attacker
-                            .status_effect_stats
-                            .status_damage_on_crit_increased)
-            } else {
-                status_damage
-            };
-
-            // Magnitude: base + crit bonus
-            let magnitude = 1.0
-                + stats.magnitude
-                + if packet.is_critical {
-                    attacker.status_effect_stats.status_magnitude_on_crit
-                } else {
-                    0.0
-                };
-
-            // For damaging DoTs, calculate DoT DPS based on status damage
-            let base_dot_percent = registry.get_base_damage_percent(status);
-            let dot_dps = calculate_status_dot_dps(
-                base_dot_percent,
+            let pending = build_pending_status(
+                attacker,
+                skill,
+                registry,
+                status,
                 status_damage,
-                &stats,
-                attacker.dot_multiplier,
+                packet.is_critical,
             );
+            packet.status_effects_to_apply.push(pending);
+        }
+    }
 
-            let mut pending = PendingStatusEffect::new_with_dot(
+    // Step 7: Random on-hit ailment, scaled from the hit's total damage.
+    // The skill's chance already rolled, so the ailment always applies
+    if let Some((ailments, chance)) = &skill.on_hit_random_ailment {
+        if !ailments.is_empty() && rng.gen::<f64>() < *chance {
+            let status = *ailments.choose(rng).unwrap();
+            let mut pending = build_pending_status(
+                attacker,
+                skill,
+                registry,
                 status,
-                status_damage,
-                duration,
-                magnitude,
-                dot_dps,
+                packet.total_damage(),
+                packet.is_critical,
             );
-            pending.apply_chance_increased = skill.status_chance_for(status);
+            pending.fixed_chance = Some(1.0);
             packet.status_effects_to_apply.push(pending);
         }
     }
@@ -193,6 +290,77 @@ pub fn calculate_damage(
     packet
 }
 
+/// Build a pending status application from converted status damage
+///
+/// Applies the attacker's duration, status damage, magnitude and DoT scaling
+/// (including crit bonuses).
+fn build_pending_status(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+    registry: &DotRegistry,
+    status: StatusEffect,
+    status_damage: f64,
+    is_critical: bool,
+) -> PendingStatusEffect {
+    let stats = attacker.status_effect_stats.get_stats(status);
+    let base_duration = registry.get_base_duration(status);
+    let duration = base_duration * (1.0 + stats.duration_increased);
+
+    // Apply increased status damage (per-type + global already folded in during aggregation)
+    let status_damage = status_damage * (1.0 + stats.status_damage_increased);
+
+    // If crit, apply crit-specific status damage bonus
+    let status_damage = if is_critical {
+        status_damage * (1.0 + attacker.status_effect_stats.status_damage_on_crit_increased)
+    } else {
+        status_damage
+    };
+
+    // Magnitude: base + crit bonus
+    let magnitude = 1.0
+        + stats.magnitude
+        + if is_critical {
+            attacker.status_effect_stats.status_magnitude_on_crit
+        } else {
+            0.0
+        };
+
+    // Freeze deals no damage; its magnitude instead scales how long
+    // the target stays frozen
+    let duration = if status == StatusEffect::Freeze {
+        duration * magnitude
+    } else {
+        duration
+    };
+
+    // For damaging DoTs, calculate DoT DPS based on status damage
+    let base_dps = registry.get_base_damage_percent(status) * status_damage;
+    let dot_dps = scaled_status_dot_dps(attacker, status, base_dps, is_critical);
+
+    let mut pending =
+        PendingStatusEffect::new_with_dot(status, status_damage, duration, magnitude, dot_dps);
+    pending.apply_chance_increased = skill.status_chance_for(status);
+    pending.from_crit = is_critical;
+    if base_dps > 0.0 {
+        pending.dot_origin = Some(DotOrigin { base_dps });
+    }
+    pending
+}
+
+/// Scale a status DoT's base DPS by the attacker's DoT modifiers
+///
+/// Used at application and again when a recomputing DoT is refreshed.
+pub(crate) fn scaled_status_dot_dps(
+    attacker: &StatBlock,
+    status: StatusEffect,
+    base_dps: f64,
+    is_critical: bool,
+) -> f64 {
+    let stats = attacker.status_effect_stats.get_stats(status);
+    calculate_status_dot_dps(1.0, base_dps, &stats, attacker.dot_multiplier)
+        * crit_ailment_dot_multiplier(&attacker.status_effect_stats, is_critical)
+}
+
 /// Calculate combined status damage from skill conversions + player stat conversions
 fn calculate_combined_status_damage(
     status: StatusEffect,
@@ -233,8 +401,24 @@ fn calculate_status_dot_dps(
     base_dot_percent * status_damage * (1.0 + stats.dot_increased) * (1.0 + dot_multiplier)
 }
 
+/// "More" DoT multiplier for ailments applied by a critical strike
+fn crit_ailment_dot_multiplier(data: &StatusEffectData, is_critical: bool) -> f64 {
+    if is_critical {
+        1.0 + data.crit_ailment_dot_more
+    } else {
+        1.0
+    }
+}
+
 /// Calculate critical strike chance
 fn calculate_crit_chance(attacker: &StatBlock, skill: &DamagePacketGenerator) -> f64 {
+    // Skill overrides win over any rolled chance
+    match skill.crit_override {
+        Some(CritMode::Never) => return 0.0,
+        Some(CritMode::Always) => return 100.0,
+        None => {}
+    }
+
     // Base crit = skill base + weapon base (for attacks)
     let base_crit = if skill.is_attack() {
         skill.base_crit_chance + attacker.weapon_crit_chance
@@ -260,26 +444,20 @@ pub fn calculate_skill_dps(attacker: &StatBlock, skill: &DamagePacketGenerator)
 
     // Calculate crit contribution
     let crit_chance = calculate_crit_chance(attacker, skill) / 100.0;
-    let crit_mult = attacker.computed_crit_multiplier() + skill.crit_multiplier_bonus;
+    let crit_mult = attacker.crit_multiplier_for(skill);
     let crit_dps_mult = 1.0 + (crit_mult - 1.0) * crit_chance;
 
     // Get attack/cast speed
-    let speed = if skill.is_attack() {
-        attacker.computed_attack_speed() * skill.attack_speed_modifier
-    } else {
-        attacker.computed_cast_speed() * skill.attack_speed_modifier
-    };
+    let speed = attacker.skill_use_rate(skill);
 
     // Calculate hit DPS (before crit scaling on avg damages)
     let hit_dps = total_avg_damage * crit_dps_mult * speed * skill.hits_per_attack as f64;
 
-    // Calculate status DoT DPS contribution from damaging statuses (Poison, Bleed, Burn)
+    // Calculate status DoT DPS contribution from each configured status;
+    // non-damaging statuses have no base DoT damage and add nothing
+    let registry = loaded_dot_registry();
     let mut dot_dps = 0.0;
-    for status in [
-        StatusEffect::Poison,
-        StatusEffect::Bleed,
-        StatusEffect::Burn,
-    ] {
+    for status in registry.statuses() {
         let status_damage = calculate_combined_status_damage(
             status,
             &avg_damages,
@@ -288,7 +466,6 @@ pub fn calculate_skill_dps(attacker: &StatBlock, skill: &DamagePacketGenerator)
         );
 
         if status_damage > 0.0 {
-            let registry = dot_registry();
             let stats = attacker.status_effect_stats.get_stats(status);
 
             // Apply increased status damage
@@ -309,6 +486,9 @@ pub fn calculate_skill_dps(attacker: &StatBlock, skill: &DamagePacketGenerator)
                 &stats,
                 attacker.dot_multiplier,
             );
+            // Weight the crit ailment DoT bonus by crit chance
+            let status_dot_dps = status_dot_dps
+                * (1.0 + attacker.status_effect_stats.crit_ailment_dot_more * crit_chance);
             // Scale by attack speed (more hits = more DoT applications)
             dot_dps += status_dot_dps * speed;
         }
@@ -317,72 +497,157 @@ pub fn calculate_skill_dps(attacker: &StatBlock, skill: &DamagePacketGenerator)
     hit_dps + dot_dps
 }
 
+/// Average number of hits of a skill needed to kill a defender
+///
+/// Divides the defender's current life and energy shield by the average
+/// damage landed per hit, after crit weighting, penetration and the
+/// defender's deterministic mitigation (see `StatBlock::effective_health_vs`).
+/// Chance-based defenses are ignored. Returns `f64::INFINITY` if the hit
+/// can't damage the defender.
+pub fn calculate_hits_to_kill(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+    defender: &StatBlock,
+) -> f64 {
+    let crit_chance = calculate_crit_chance(attacker, skill) / 100.0;
+    let crit_mult = attacker.crit_multiplier_for(skill);
+    let crit_dps_mult = 1.0 + (crit_mult - 1.0) * crit_chance;
+
+    let mut landed = 0.0;
+    let mut landed_chaos = 0.0;
+    for (damage_type, amount) in calculate_average_damage_by_type(attacker, skill) {
+        let hit = amount * crit_dps_mult;
+        let penetration = match damage_type {
+            DamageType::Physical => 0.0,
+            _ => attacker.penetration(damage_type) + attacker.effect_penetration(damage_type),
+        };
+        let taken = hit * defender.damage_taken_fraction(damage_type, hit, penetration);
+        landed += taken;
+        if damage_type == DamageType::Chaos {
+            landed_chaos += taken;
+        }
+    }
+    if landed <= 0.0 {
+        return f64::INFINITY;
+    }
+
+    let life = defender.current_life.max(0.0);
+    let es = defender.current_energy_shield.max(0.0);
+    if defender.chaos_bypasses_es && landed_chaos > 0.0 {
+        // Chaos drains life directly; if that kills before the rest of the
+        // hit breaks energy shield, ES never contributes to the pool
+        let chaos_hits = life / landed_chaos;
+        let other = landed - landed_chaos;
+        if other <= 0.0 || chaos_hits * other <= es {
+            return chaos_hits;
+        }
+    }
+    (life + es) / landed
+}
+
 /// Calculate average damage by type (non-random)
 /// Returns Vec of (DamageType, scaled_amount) after conversions and scaling
 pub fn calculate_average_damage_by_type(
     attacker: &StatBlock,
     skill: &DamagePacketGenerator,
 ) -> Vec<(DamageType, f64)> {
-    // Step 1: Gather base damage averages (pre-conversion, pre-scaling)
+    scaled_damage_by_type(attacker, skill, |min, max| (min + max) / 2.0)
+}
+
+/// Calculate the min, max and average damage per type (non-random)
+///
+/// Bounds come from the lowest and highest skill and weapon rolls, carried
+/// through the same conversions and scaling as the average.
+pub fn calculate_damage_range(attacker: &StatBlock, skill: &DamagePacketGenerator) -> DamageRange {
+    let min: HashMap<_, _> = scaled_damage_by_type(attacker, skill, |min, _| min)
+        .into_iter()
+        .collect();
+    let max: HashMap<_, _> = scaled_damage_by_type(attacker, skill, |_, max| max)
+        .into_iter()
+        .collect();
+    let average: HashMap<_, _> = calculate_average_damage_by_type(attacker, skill)
+        .into_iter()
+        .collect();
+
+    let damages = DAMAGE_TYPES
+        .into_iter()
+        .filter(|dt| max.contains_key(dt))
+        .map(|damage_type| DamageTypeRange {
+            damage_type,
+            min: min.get(&damage_type).copied().unwrap_or(0.0),
+            max: max[&damage_type],
+            average: average.get(&damage_type).copied().unwrap_or(0.0),
+        })
+        .collect();
+    DamageRange { damages }
+}
+
+/// Damage per type after conversions and scaling, with each base roll
+/// reduced to a single value by `roll(min, max)`
+fn scaled_damage_by_type(
+    attacker: &StatBlock,
+    skill: &DamagePacketGenerator,
+    roll: fn(f64, f64) -> f64,
+) -> Vec<(DamageType, f64)> {
+    // Step 1: Gather base damage rolls (pre-conversion, pre-scaling)
     let mut base_damages: HashMap<DamageType, f64> = HashMap::new();
 
     // Skill base damages
     for base_dmg in &skill.base_damages {
-        let avg = (base_dmg.min + base_dmg.max) / 2.0;
-        *base_damages.entry(base_dmg.damage_type).or_insert(0.0) += avg;
+        let rolled = roll(base_dmg.min, base_dmg.max);
+        *base_damages.entry(base_dmg.damage_type).or_insert(0.0) += rolled;
     }
 
     // Weapon damages for attacks
     if skill.is_attack() && skill.weapon_effectiveness > 0.0 {
-        for damage_type in [
-            DamageType::Physical,
-            DamageType::Fire,
-            DamageType::Cold,
-            DamageType::Lightning,
-            DamageType::Chaos,
-        ] {
+        for damage_type in DAMAGE_TYPES {
             let (min, max) = attacker.weapon_damage(damage_type);
             if max > 0.0 {
-                let avg = (min + max) / 2.0 * skill.weapon_effectiveness;
-                *base_damages.entry(damage_type).or_insert(0.0) += avg;
+                let rolled = roll(min, max) * skill.weapon_effectiveness;
+                *base_damages.entry(damage_type).or_insert(0.0) += rolled;
             }
         }
     }
 
+    // Step 1b: Random element conversion, split evenly for expected value
+    let random_conversions = skill.random_element_conversion.map(|fraction| {
+        let share = fraction / RANDOM_CONVERSION_ELEMENTS.len() as f64;
+        RANDOM_CONVERSION_ELEMENTS.map(|element| (element, share))
+    });
+
     // Step 2: Apply damage type conversions
-    let converted_damages = if skill.damage_conversions.has_conversions() {
-        skill.damage_conversions.apply(&base_damages)
-    } else {
-        base_damages
-    };
+    let converted_damages = convert_by_origin(
+        base_damages,
+        random_conversions.as_ref().map_or(&[], |c| c.as_slice()),
+        &skill.damage_conversions,
+    );
 
     // Step 3: Apply damage scaling to each type
-    let mut result: Vec<(DamageType, f64)> = Vec::new();
+    let mut result: HashMap<DamageType, f64> = HashMap::new();
+    let movement_mult = attacker.movement_damage_multiplier();
+    let delivery_mult = attacker.delivery_damage_multiplier(skill);
 
-    for (damage_type, base_amount) in converted_damages {
+    for (origin, damage_type, base_amount) in converted_damages {
         if base_amount <= 0.0 {
             continue;
         }
 
-        let damage_stat = match damage_type {
-            DamageType::Physical => &attacker.global_physical_damage,
-            DamageType::Fire => &attacker.global_fire_damage,
-            DamageType::Cold => &attacker.global_cold_damage,
-            DamageType::Lightning => &attacker.global_lightning_damage,
-            DamageType::Chaos => &attacker.global_chaos_damage,
-        };
-
-        let increased_mult = damage_stat.total_increased_multiplier();
-        let more_mult = damage_stat.total_more_multiplier();
+        let increased_mult = increased_multiplier(attacker, origin, damage_type);
+        let more_mult = damage_stat(attacker, damage_type).total_more_multiplier()
+            * movement_mult
+            * delivery_mult
+            * skill.more_multiplier(damage_type);
         let type_eff = skill.type_effectiveness.get(damage_type);
 
         let scaled =
             base_amount * increased_mult * more_mult * skill.damage_effectiveness * type_eff;
         if scaled > 0.0 {
-            result.push((damage_type, scaled));
+            *result.entry(damage_type).or_insert(0.0) += scaled;
         }
     }
 
+    let mut result: Vec<(DamageType, f64)> = result.into_iter().collect();
+    result.sort_by_key(|&(damage_type, _)| damage_type_order(damage_type));
     result
 }
 
@@ -435,6 +700,92 @@ mod tests {
         assert!((packet.total_damage() - 150.0).abs() < 1.0);
     }
 
+    #[test]
+    fn test_converted_damage_double_scaling() {
+        crate::config::ensure_dot_registry_initialized();
+        let mut attacker = StatBlock::new();
+        attacker.global_physical_damage.add_increased(0.50);
+        attacker.global_fire_damage.add_increased(1.00);
+        let skill = DamagePacketGenerator {
+            weapon_effectiveness: 0.0,
+            crit_override: Some(CritMode::Never),
+            ..DamagePacketGenerator::new("test")
+        }
+        .with_base_damage(BaseDamage::new(DamageType::Physical, 100.0, 100.0))
+        .with_conversion(DamageType::Physical, DamageType::Fire, 0.5);
+
+        // By default converted damage only uses the destination's scaling
+        let packet = calculate_damage(&attacker, &skill, "a".to_string(), &mut make_test_rng());
+        assert!((packet.damage_of_type(DamageType::Physical) - 75.0).abs() < 1e-9);
+        assert!((packet.damage_of_type(DamageType::Fire) - 100.0).abs() < 1e-9);
+
+        // Counting as both: 50 * 1.5 * 2.0, unconverted physical unchanged
+        attacker.converted_damage_double_scaling = true;
+        let packet = calculate_damage(&attacker, &skill, "a".to_string(), &mut make_test_rng());
+        assert!((packet.damage_of_type(DamageType::Physical) - 75.0).abs() < 1e-9);
+        assert!((packet.damage_of_type(DamageType::Fire) - 150.0).abs() < 1e-9);
+        let average: HashMap<DamageType, f64> = calculate_average_damage_by_type(&attacker, &skill)
+            .into_iter()
+            .collect();
+        assert!((average[&DamageType::Fire] - 150.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_area_and_projectiles_on_packet() {
+        crate::config::ensure_dot_registry_initialized();
+        let mut attacker = StatBlock::new();
+        attacker.area_of_effect_increased = 0.10;
+        attacker.additional_projectiles = 2;
+        let skill = DamagePacketGenerator {
+            weapon_effectiveness: 0.0,
+            base_radius: 10.0,
+            base_projectiles: 1,
+            ..DamagePacketGenerator::new("test")
+        };
+
+        // Area scales by 1.1, so radius by sqrt(1.1)
+        let packet = calculate_damage(&attacker, &skill, "a".to_string(), &mut make_test_rng());
+        assert!((packet.radius - 10.488).abs() < 1e-3);
+        assert_eq!(packet.projectiles, 3);
+
+        // Non-projectile skills don't gain projectiles; negatives floor at 1
+        let nova = DamagePacketGenerator {
+            base_projectiles: 0,
+            ..skill.clone()
+        };
+        assert_eq!(nova.effective_projectiles(2), 0);
+        assert_eq!(skill.effective_projectiles(-3), 1);
+    }
+
+    #[test]
+    fn test_hits_to_kill() {
+        crate::config::ensure_constants_initialized();
+        crate::config::ensure_dot_registry_initialized();
+        let attacker = StatBlock::new();
+        let skill = DamagePacketGenerator {
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            crit_override: Some(CritMode::Never),
+            ..DamagePacketGenerator::new("test")
+        };
+        let mut dummy = StatBlock::new();
+        dummy.current_life = 350.0;
+        dummy.current_energy_shield = 0.0;
+
+        assert!((calculate_hits_to_kill(&attacker, &skill, &dummy) - 3.5).abs() < 1e-9);
+
+        // Resistance halves the landed damage, doubling the hits
+        dummy.fire_resistance.base = 50.0;
+        assert!((attacker.hits_to_kill(&skill, &dummy) - 7.0).abs() < 1e-9);
+
+        // A skill with no damage never kills
+        let harmless = DamagePacketGenerator {
+            base_damages: vec![],
+            ..skill.clone()
+        };
+        assert!(calculate_hits_to_kill(&attacker, &harmless, &dummy).is_infinite());
+    }
+
     #[test]
     fn test_weapon_damage() {
         let mut attacker = StatBlock::new();
@@ -541,6 +892,290 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_freeze_magnitude_extends_freeze_duration() {
+        crate::config::ensure_dot_registry_initialized();
+        let skill = DamagePacketGenerator {
+            id: "ice_skill".to_string(),
+            name: "Ice Skill".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Cold, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            status_conversions: SkillStatusConversions {
+                cold_to_freeze: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let freeze_duration = |attacker: &StatBlock| {
+            let mut rng = make_test_rng();
+            let packet = calculate_damage(attacker, &skill, "player".to_string(), &mut rng);
+            packet
+                .status_effects_to_apply
+                .iter()
+                .find(|s| s.effect_type == StatusEffect::Freeze)
+                .expect("should have a freeze status effect")
+                .duration
+        };
+
+        let base = StatBlock::new();
+        let mut empowered = StatBlock::new();
+        empowered.status_effect_stats.set_stats(
+            StatusEffect::Freeze,
+            StatusEffectStats {
+                magnitude: 0.5,
+                ..Default::default()
+            },
+        );
+
+        let base_duration = freeze_duration(&base);
+        let empowered_duration = freeze_duration(&empowered);
+        assert!(empowered_duration > base_duration);
+        assert!((empowered_duration / base_duration - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_penetration_effect_adds_to_packet() {
+        use crate::types::Effect;
+        let skill = DamagePacketGenerator {
+            id: "fire_skill".to_string(),
+            name: "Fire Skill".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            ..Default::default()
+        };
+
+        let mut attacker = StatBlock::new();
+        attacker.fire_penetration.add_flat(10.0);
+
+        let mut rng = make_test_rng();
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        assert!((packet.fire_pen - 10.0).abs() < f64::EPSILON);
+
+        attacker.add_effect(Effect::new_penetration(
+            "flame_banner",
+            "Flame Banner",
+            DamageType::Fire,
+            15.0,
+            8.0,
+            "ally",
+        ));
+
+        let mut rng = make_test_rng();
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        assert!((packet.fire_pen - 25.0).abs() < f64::EPSILON);
+        assert!(packet.cold_pen.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_crit_ailments_deal_more_dot() {
+        crate::config::ensure_dot_registry_initialized();
+        let mut data = StatusEffectData::default();
+        data.crit_ailment_dot_more = 0.5;
+
+        let base = calculate_status_dot_dps(0.2, 100.0, &StatusEffectStats::default(), 0.0);
+        let non_crit = base * crit_ailment_dot_multiplier(&data, false);
+        let crit = base * crit_ailment_dot_multiplier(&data, true);
+        assert!(crit > non_crit);
+        assert!((crit / non_crit - 1.5).abs() < 1e-9);
+
+        // Statuses from a crit are marked as such
+        let mut attacker = StatBlock::new();
+        attacker.critical_chance.add_flat(100.0);
+        let skill = DamagePacketGenerator {
+            id: "venom".to_string(),
+            name: "Venom".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Chaos, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            status_conversions: SkillStatusConversions {
+                chaos_to_poison: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut rng = make_test_rng();
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        assert!(packet.is_critical);
+        assert!(packet.status_effects_to_apply.iter().all(|s| s.from_crit));
+    }
+
+    #[test]
+    fn test_stationary_damage_only_when_not_moving() {
+        let mut attacker = StatBlock::new();
+        let mut acc = crate::stat_block::StatAccumulator::new();
+        acc.apply_stat_type(loot_core::types::StatType::MoreDamageWhileStationary, 50.0);
+        acc.apply_to(&mut attacker);
+
+        let skill = DamagePacketGenerator {
+            id: "bolt".to_string(),
+            name: "Bolt".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+            tags: vec![SkillTag::Spell],
+            ..Default::default()
+        };
+
+        let mut rng = make_test_rng();
+        let stationary = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        attacker.is_moving = true;
+        let mut rng = make_test_rng();
+        let moving = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+
+        let moving_dps = calculate_skill_dps(&attacker, &skill);
+        attacker.is_moving = false;
+        let stationary_dps = calculate_skill_dps(&attacker, &skill);
+
+        assert!((stationary.total_damage() - moving.total_damage() * 1.5).abs() < 1e-9);
+        assert!((stationary_dps - moving_dps * 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_totem_damage_only_for_totem_skills() {
+        let plain = StatBlock::new();
+        let mut attacker = StatBlock::new();
+        let mut acc = crate::stat_block::StatAccumulator::new();
+        acc.apply_stat_type(loot_core::types::StatType::MoreTotemDamage, 40.0);
+        acc.apply_to(&mut attacker);
+
+        let totem = DamagePacketGenerator {
+            id: "searing_totem".to_string(),
+            name: "Searing Totem".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+            tags: vec![SkillTag::Spell, SkillTag::Totem],
+            ..Default::default()
+        };
+        let attack = DamagePacketGenerator {
+            id: "slash".to_string(),
+            name: "Slash".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 100.0, 100.0)],
+            tags: vec![SkillTag::Attack],
+            ..Default::default()
+        };
+
+        let boosted_totem = calculate_skill_dps(&attacker, &totem);
+        assert!((boosted_totem - calculate_skill_dps(&plain, &totem) * 1.4).abs() < 1e-9);
+        let boosted_attack = calculate_skill_dps(&attacker, &attack);
+        assert!((boosted_attack - calculate_skill_dps(&plain, &attack)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_physical_feeds_bleed_and_poison_without_reducing_hit() {
+        crate::config::ensure_dot_registry_initialized();
+        let attacker = StatBlock::new();
+        let skill = DamagePacketGenerator {
+            id: "rend".to_string(),
+            name: "Rend".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 100.0, 100.0)],
+            tags: vec![SkillTag::Spell],
+            status_conversions: SkillStatusConversions {
+                physical_to_bleed: 0.5,
+                physical_to_poison: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut rng = make_test_rng();
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        assert!(!packet.is_critical);
+        assert!((packet.damage_of_type(DamageType::Physical) - 100.0).abs() < 1e-9);
+
+        for status in [StatusEffect::Bleed, StatusEffect::Poison] {
+            let pending = packet
+                .status_effects_to_apply
+                .iter()
+                .find(|s| s.effect_type == status)
+                .unwrap_or_else(|| panic!("missing {:?}", status));
+            assert!((pending.status_damage - 50.0).abs() < 1e-9);
+        }
+    }
+
+    fn crit_test_skill(
+        base_crit_chance: f64,
+        crit_override: Option<CritMode>,
+    ) -> DamagePacketGenerator {
+        DamagePacketGenerator {
+            id: "strike".to_string(),
+            name: "Strike".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 100.0, 100.0)],
+            tags: vec![SkillTag::Spell],
+            base_crit_chance,
+            crit_override,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_crit_override_never() {
+        let attacker = StatBlock::new();
+        let skill = crit_test_skill(100.0, Some(CritMode::Never));
+
+        let mut rng = make_test_rng();
+        for _ in 0..20 {
+            let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+            assert!(!packet.is_critical);
+        }
+        let no_crit_dps = calculate_skill_dps(&attacker, &crit_test_skill(0.0, None));
+        assert!((calculate_skill_dps(&attacker, &skill) - no_crit_dps).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_crit_override_always() {
+        let attacker = StatBlock::new();
+        let skill = crit_test_skill(0.0, Some(CritMode::Always));
+
+        let mut rng = make_test_rng();
+        for _ in 0..20 {
+            let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+            assert!(packet.is_critical);
+        }
+        let no_crit_dps = calculate_skill_dps(&attacker, &crit_test_skill(0.0, None));
+        let expected = no_crit_dps * attacker.computed_crit_multiplier();
+        assert!((calculate_skill_dps(&attacker, &skill) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_skill_more_multipliers_stack_multiplicatively() {
+        let mut attacker = StatBlock::new();
+        attacker.global_fire_damage.add_increased(1.0);
+        let mut skill = crit_test_skill(0.0, Some(CritMode::Never));
+        skill.base_damages = vec![
+            BaseDamage::new(DamageType::Fire, 100.0, 100.0),
+            BaseDamage::new(DamageType::Cold, 100.0, 100.0),
+        ];
+        skill.more_multipliers = vec![(DamageType::Fire, 0.5), (DamageType::Fire, 0.5)];
+
+        // 100% increased, then 1.5 * 1.5 more: 100 * 2 * 2.25
+        let mut rng = make_test_rng();
+        let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+        assert!((packet.damage_of_type(DamageType::Fire) - 450.0).abs() < 1e-9);
+        assert!((packet.damage_of_type(DamageType::Cold) - 100.0).abs() < 1e-9);
+
+        let average: HashMap<_, _> = calculate_average_damage_by_type(&attacker, &skill)
+            .into_iter()
+            .collect();
+        assert!((average[&DamageType::Fire] - 450.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_type_scoped_crit_multiplier() {
+        let mut attacker = StatBlock::new();
+        let mut accumulator = crate::stat_block::StatAccumulator::new();
+        accumulator.apply_stat_type(loot_core::StatType::IncreasedFireCriticalDamage, 50.0);
+        accumulator.apply_to(&mut attacker);
+
+        let physical = crit_test_skill(0.0, Some(CritMode::Always));
+        let mut fire = physical.clone();
+        fire.base_damages = vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)];
+
+        let mut rng = make_test_rng();
+        let physical_hit = calculate_damage(&attacker, &physical, "player".to_string(), &mut rng);
+        let fire_hit = calculate_damage(&attacker, &fire, "player".to_string(), &mut rng);
+        assert!((physical_hit.crit_multiplier - 1.5).abs() < 1e-9);
+        assert!((fire_hit.crit_multiplier - 2.0).abs() < 1e-9);
+        assert!((physical_hit.total_damage() - 150.0).abs() < 1e-9);
+        assert!((fire_hit.total_damage() - 200.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_skill_dps() {
         let mut attacker = StatBlock::new();
@@ -565,4 +1200,68 @@ mod tests {
         assert!(dps > 100.0);
         assert!(dps < 110.0);
     }
+
+    #[test]
+    fn test_random_element_conversion() {
+        crate::config::ensure_dot_registry_initialized();
+        let attacker = StatBlock::new();
+        let mut skill = crit_test_skill(0.0, Some(CritMode::Never));
+        skill.random_element_conversion = Some(0.9);
+
+        // Expected value splits the converted portion evenly
+        let average: HashMap<DamageType, f64> = calculate_average_damage_by_type(&attacker, &skill)
+            .into_iter()
+            .collect();
+        assert!((average[&DamageType::Physical] - 10.0).abs() < 1e-9);
+        for element in RANDOM_CONVERSION_ELEMENTS {
+            assert!((average[&element] - 30.0).abs() < 1e-9);
+        }
+
+        // A seeded hit converts everything to one element, the same one each time
+        let packet = calculate_damage(&attacker, &skill, "a".to_string(), &mut make_test_rng());
+        let elements: Vec<_> = packet
+            .damages
+            .iter()
+            .filter(|d| RANDOM_CONVERSION_ELEMENTS.contains(&d.damage_type))
+            .collect();
+        assert_eq!(elements.len(), 1);
+        assert!((elements[0].amount - 90.0).abs() < 1e-9);
+        let again = calculate_damage(&attacker, &skill, "a".to_string(), &mut make_test_rng());
+        assert!((again.damage_of_type(elements[0].damage_type) - 90.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_on_hit_random_ailment() {
+        crate::config::ensure_constants_initialized();
+        crate::config::ensure_dot_registry_initialized();
+        let attacker = StatBlock::new();
+        let mut skill = crit_test_skill(0.0, Some(CritMode::Never));
+        skill.on_hit_random_ailment = Some((
+            vec![
+                StatusEffect::Poison,
+                StatusEffect::Chill,
+                StatusEffect::Fear,
+            ],
+            1.0,
+        ));
+
+        let packet = calculate_damage(&attacker, &skill, "a".to_string(), &mut make_test_rng());
+        let picked: Vec<_> = packet
+            .status_effects_to_apply
+            .iter()
+            .filter(|s| s.fixed_chance == Some(1.0))
+            .collect();
+        assert_eq!(picked.len(), 1);
+        assert_eq!(picked[0].effect_type, StatusEffect::Chill);
+
+        // 100 damage against 10000 life would almost never roll an application
+        let mut defender = StatBlock::new();
+        defender.max_life.base = 10000.0;
+        defender.current_life = 10000.0;
+        let (_, result) = crate::combat::resolve_damage(&defender, &packet);
+        assert!(result
+            .effects_applied
+            .iter()
+            .any(|e| e.status() == Some(StatusEffect::Chill)));
+    }
 }
@@ -27,6 +27,10 @@ pub struct DamagePacketGenerator {
     /// How much added damage applies (1.0 = 100%)
     #[serde(default = "default_damage_effectiveness")]
     pub damage_effectiveness: f64,
+    /// Extra effectiveness multiplier when hitting multiple targets at once
+    /// (1.0 = no penalty). Single-target hits ignore it.
+    #[serde(default = "default_damage_effectiveness")]
+    pub aoe_damage_effectiveness: f64,
     /// Multiplier to attack/cast speed
     #[serde(default = "default_speed_modifier")]
     pub attack_speed_modifier: f64,
@@ -38,6 +42,9 @@ pub struct DamagePacketGenerator {
     /// Added to base crit multiplier
     #[serde(default)]
     pub crit_multiplier_bonus: f64,
+    /// Forces hits to never or always crit, ignoring crit chance
+    #[serde(default)]
+    pub crit_override: Option<CritMode>,
 
     // === Tags ===
     /// Skill tags for categorization and scaling
@@ -55,12 +62,20 @@ pub struct DamagePacketGenerator {
     /// Applied before damage scaling
     #[serde(default)]
     pub damage_conversions: DamageConversions,
+    /// Fraction of physical damage converted to one randomly picked element
+    /// (fire, cold or lightning) per hit, before `damage_conversions`
+    #[serde(default)]
+    pub random_element_conversion: Option<f64>,
 
     // === Per-Type Effectiveness ===
     /// Damage effectiveness multiplier for each damage type
     /// Defaults to 1.0 (100%) for all types if not specified
     #[serde(default)]
     pub type_effectiveness: DamageTypeEffectiveness,
+    /// Skill-granted more multipliers per damage type (0.4 = 40% more),
+    /// each stacking multiplicatively with stat-derived more damage
+    #[serde(default)]
+    pub more_multipliers: Vec<(DamageType, f64)>,
 
     // === Status Effect Chance Scaling ===
     /// Per-status-effect increased chance to apply
@@ -68,6 +83,10 @@ pub struct DamagePacketGenerator {
     /// Values are increased multipliers (0.2 = 20% increased chance)
     #[serde(default)]
     pub status_chance_increased: HashMap<String, f64>,
+    /// On hit, with the given chance (0.0 to 1.0), apply one ailment picked
+    /// at random from the list. The picked ailment always applies.
+    #[serde(default)]
+    pub on_hit_random_ailment: Option<(Vec<StatusEffect>, f64)>,
 
     // === Special Mechanics ===
     /// Number of hits per attack (for multi-hit skills)
@@ -82,6 +101,18 @@ pub struct DamagePacketGenerator {
     /// Chance to pierce targets (0.0 to 1.0)
     #[serde(default)]
     pub pierce_chance: f64,
+    /// Culling strike from this skill only triggers on critical hits
+    #[serde(default)]
+    pub cull_requires_crit: bool,
+    /// More damage against targets at full life (0.5 = 50% more)
+    #[serde(default)]
+    pub more_vs_full_life: Option<f64>,
+    /// Base area radius (0.0 = no area)
+    #[serde(default)]
+    pub base_radius: f64,
+    /// Base projectiles fired (0 = not a projectile skill)
+    #[serde(default)]
+    pub base_projectiles: u32,
 
     // === Resource Cost ===
     /// Mana cost to use this skill
@@ -92,6 +123,16 @@ pub struct DamagePacketGenerator {
     pub cooldown: f64,
 }
 
+/// Crit behavior override for a skill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CritMode {
+    /// Hits can never crit
+    Never,
+    /// Hits always crit
+    Always,
+}
+
 /// Skill-specific status effect conversions
 /// Values are percentages (0.0 to 1.0) of damage converted to status damage
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -168,6 +209,26 @@ impl SkillStatusConversions {
             _ => 0.0,
         }
     }
+
+    /// Set the conversion from a damage type to a status effect
+    /// (ignored for combinations `get_conversion` doesn't support)
+    pub fn set_conversion(&mut self, from: DamageType, to: StatusEffect, value: f64) {
+        use loot_core::types::StatusEffect::*;
+        let field = match (from, to) {
+            (DamageType::Physical, Poison) => &mut self.physical_to_poison,
+            (DamageType::Chaos, Poison) => &mut self.chaos_to_poison,
+            (DamageType::Physical, Bleed) => &mut self.physical_to_bleed,
+            (DamageType::Fire, Burn) => &mut self.fire_to_burn,
+            (DamageType::Cold, Freeze) => &mut self.cold_to_freeze,
+            (DamageType::Cold, Chill) => &mut self.cold_to_chill,
+            (DamageType::Lightning, Static) => &mut self.lightning_to_static,
+            (DamageType::Chaos, Fear) => &mut self.chaos_to_fear,
+            (DamageType::Physical, Slow) => &mut self.physical_to_slow,
+            (DamageType::Cold, Slow) => &mut self.cold_to_slow,
+            _ => return,
+        };
+        *field = value;
+    }
 }
 
 /// Damage type conversion configuration
@@ -217,6 +278,23 @@ impl DamageConversions {
         }
     }
 
+    /// Set the conversion from one damage type to another
+    /// (ignored for pairs that break the conversion order)
+    pub fn set_conversion(&mut self, from: DamageType, to: DamageType, value: f64) {
+        let field = match (from, to) {
+            (DamageType::Physical, DamageType::Fire) => &mut self.physical_to_fire,
+            (DamageType::Physical, DamageType::Cold) => &mut self.physical_to_cold,
+            (DamageType::Physical, DamageType::Lightning) => &mut self.physical_to_lightning,
+            (DamageType::Physical, DamageType::Chaos) => &mut self.physical_to_chaos,
+            (DamageType::Lightning, DamageType::Fire) => &mut self.lightning_to_fire,
+            (DamageType::Lightning, DamageType::Cold) => &mut self.lightning_to_cold,
+            (DamageType::Cold, DamageType::Fire) => &mut self.cold_to_fire,
+            (DamageType::Fire, DamageType::Chaos) => &mut self.fire_to_chaos,
+            _ => return,
+        };
+        *field = value;
+    }
+
     /// Check if there are any conversions defined
     pub fn has_conversions(&self) -> bool {
         self.physical_to_fire > 0.0
@@ -369,18 +447,27 @@ impl Default for DamagePacketGenerator {
             base_damages: vec![],
             weapon_effectiveness: 1.0,
             damage_effectiveness: 1.0,
+            aoe_damage_effectiveness: 1.0,
             attack_speed_modifier: 1.0,
             base_crit_chance: 0.0,
             crit_multiplier_bonus: 0.0,
+            crit_override: None,
             tags: vec![SkillTag::Attack],
             status_conversions: SkillStatusConversions::default(),
             damage_conversions: DamageConversions::default(),
+            random_element_conversion: None,
             type_effectiveness: DamageTypeEffectiveness::default(),
+            more_multipliers: Vec::new(),
             status_chance_increased: HashMap::new(),
+            on_hit_random_ailment: None,
             hits_per_attack: 1,
             can_chain: false,
             chain_count: 0,
             pierce_chance: 0.0,
+            cull_requires_crit: false,
+            more_vs_full_life: None,
+            base_radius: 0.0,
+            base_projectiles: 0,
             mana_cost: 0.0,
             cooldown: 0.0,
         }
@@ -388,6 +475,59 @@ impl Default for DamagePacketGenerator {
 }
 
 impl DamagePacketGenerator {
+    /// Create a skill with default settings, for use with the `with_*` builders
+    pub fn new(id: impl Into<String>) -> Self {
+        let id = id.into();
+        DamagePacketGenerator {
+            name: id.clone(),
+            id,
+            ..Default::default()
+        }
+    }
+
+    /// Add a base damage entry
+    pub fn with_base_damage(mut self, base_damage: BaseDamage) -> Self {
+        self.base_damages.push(base_damage);
+        self
+    }
+
+    /// Convert a fraction of one damage type to another
+    pub fn with_conversion(mut self, from: DamageType, to: DamageType, value: f64) -> Self {
+        self.damage_conversions.set_conversion(from, to, value);
+        self
+    }
+
+    /// Convert a fraction of a damage type's hit damage to status damage
+    pub fn with_status_conversion(
+        mut self,
+        from: DamageType,
+        to: StatusEffect,
+        value: f64,
+    ) -> Self {
+        self.status_conversions.set_conversion(from, to, value);
+        self
+    }
+
+    /// Add a tag (duplicates are ignored)
+    pub fn with_tag(mut self, tag: SkillTag) -> Self {
+        if !self.tags.contains(&tag) {
+            self.tags.push(tag);
+        }
+        self
+    }
+
+    /// Set the number of hits per use
+    pub fn with_hits(mut self, hits: u32) -> Self {
+        self.hits_per_attack = hits;
+        self
+    }
+
+    /// Set the skill's bonus critical strike multiplier
+    pub fn with_crit_multiplier_bonus(mut self, bonus: f64) -> Self {
+        self.crit_multiplier_bonus = bonus;
+        self
+    }
+
     /// Create a basic melee attack
     pub fn basic_attack() -> Self {
         DamagePacketGenerator {
@@ -396,18 +536,27 @@ impl DamagePacketGenerator {
             base_damages: vec![],
             weapon_effectiveness: 1.0,
             damage_effectiveness: 1.0,
+            aoe_damage_effectiveness: 1.0,
             attack_speed_modifier: 1.0,
             base_crit_chance: 0.0,
             crit_multiplier_bonus: 0.0,
+            crit_override: None,
             tags: vec![SkillTag::Attack, SkillTag::Melee],
             status_conversions: SkillStatusConversions::default(),
             damage_conversions: DamageConversions::default(),
+            random_element_conversion: None,
             type_effectiveness: DamageTypeEffectiveness::default(),
+            more_multipliers: Vec::new(),
             status_chance_increased: HashMap::new(),
+            on_hit_random_ailment: None,
             hits_per_attack: 1,
             can_chain: false,
             chain_count: 0,
             pierce_chance: 0.0,
+            cull_requires_crit: false,
+            more_vs_full_life: None,
+            base_radius: 0.0,
+            base_projectiles: 0,
             mana_cost: 0.0,
             cooldown: 0.0,
         }
@@ -437,6 +586,34 @@ impl DamagePacketGenerator {
         (self.mana_cost * (1.0 - reduced_mana_cost)).max(0.0)
     }
 
+    /// Copy of this skill with area effectiveness folded into damage effectiveness,
+    /// for hits that land on multiple targets
+    pub fn for_multiple_targets(&self) -> Self {
+        let mut skill = self.clone();
+        skill.damage_effectiveness *= self.aoe_damage_effectiveness;
+        skill.aoe_damage_effectiveness = 1.0;
+        skill
+    }
+
+    /// Get the effective area radius after increased area of effect
+    ///
+    /// Increased area scales the area, so the radius grows with its square
+    /// root (10% increased area is about 4.9% more radius).
+    pub fn effective_radius(&self, area_increased: f64) -> f64 {
+        self.base_radius * (1.0 + area_increased).max(0.0).sqrt()
+    }
+
+    /// Get the projectile count after additional projectiles
+    ///
+    /// Skills without base projectiles never gain any; projectile skills
+    /// always fire at least one.
+    pub fn effective_projectiles(&self, additional: i32) -> u32 {
+        if self.base_projectiles == 0 {
+            return 0;
+        }
+        (self.base_projectiles as i64 + additional as i64).clamp(1, u32::MAX as i64) as u32
+    }
+
     /// Get the effective cooldown after CDR
     pub fn effective_cooldown(&self, cooldown_reduction: f64) -> f64 {
         (self.cooldown * (1.0 - cooldown_reduction)).max(0.0)
@@ -452,6 +629,15 @@ impl DamagePacketGenerator {
         self.tags.contains(&SkillTag::Spell)
     }
 
+    /// Combined skill more multiplier for a damage type
+    pub fn more_multiplier(&self, damage_type: DamageType) -> f64 {
+        self.more_multipliers
+            .iter()
+            .filter(|(dt, _)| *dt == damage_type)
+            .map(|(_, more)| 1.0 + more)
+            .product()
+    }
+
     /// Check if this skill deals a specific damage type
     pub fn deals_damage_type(&self, damage_type: DamageType) -> bool {
         self.base_damages
@@ -470,6 +656,25 @@ impl DamagePacketGenerator {
     pub fn effective_speed(&self, base_speed: f64) -> f64 {
         base_speed * self.attack_speed_modifier
     }
+
+    /// Nonzero damage conversions as `(from, to, fraction)`, in conversion
+    /// order, for tooltips like "50% of Physical converted to Fire"
+    pub fn conversion_summary(&self) -> Vec<(DamageType, DamageType, f64)> {
+        [
+            (DamageType::Physical, DamageType::Fire),
+            (DamageType::Physical, DamageType::Cold),
+            (DamageType::Physical, DamageType::Lightning),
+            (DamageType::Physical, DamageType::Chaos),
+            (DamageType::Lightning, DamageType::Fire),
+            (DamageType::Lightning, DamageType::Cold),
+            (DamageType::Cold, DamageType::Fire),
+            (DamageType::Fire, DamageType::Chaos),
+        ]
+        .into_iter()
+        .map(|(from, to)| (from, to, self.damage_conversions.get_conversion(from, to)))
+        .filter(|&(_, _, fraction)| fraction > 0.0)
+        .collect()
+    }
 }
 
 /// Base damage for a skill
@@ -682,6 +887,58 @@ mod tests {
         assert!((result.get(&DamageType::Fire).unwrap_or(&0.0) - 12.5).abs() < 0.001);
     }
 
+    #[test]
+    fn test_conversion_summary_lists_nonzero_conversions() {
+        let skill = DamagePacketGenerator {
+            damage_conversions: DamageConversions {
+                physical_to_fire: 0.5,
+                physical_to_cold: 0.25,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            skill.conversion_summary(),
+            vec![
+                (DamageType::Physical, DamageType::Fire, 0.5),
+                (DamageType::Physical, DamageType::Cold, 0.25),
+            ]
+        );
+        assert!(DamagePacketGenerator::default()
+            .conversion_summary()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_builder_populates_fields() {
+        let skill = DamagePacketGenerator::new("frost_slam")
+            .with_base_damage(BaseDamage::new(DamageType::Physical, 10.0, 20.0))
+            .with_conversion(DamageType::Physical, DamageType::Cold, 0.5)
+            .with_status_conversion(DamageType::Cold, StatusEffect::Chill, 0.3)
+            .with_tag(SkillTag::Melee)
+            .with_tag(SkillTag::Melee)
+            .with_hits(2)
+            .with_crit_multiplier_bonus(0.4);
+
+        assert_eq!(skill.id, "frost_slam");
+        assert_eq!(skill.base_damages.len(), 1);
+        assert_eq!(skill.damage_conversions.physical_to_cold, 0.5);
+        assert_eq!(skill.status_conversions.cold_to_chill, 0.3);
+        assert_eq!(skill.tags, vec![SkillTag::Attack, SkillTag::Melee]);
+        assert_eq!(skill.hits_per_attack, 2);
+        assert_eq!(skill.crit_multiplier_bonus, 0.4);
+
+        // Unsupported pairs are ignored
+        let conversions = skill
+            .with_conversion(DamageType::Chaos, DamageType::Fire, 1.0)
+            .damage_conversions;
+        assert_eq!(
+            conversions.get_conversion(DamageType::Chaos, DamageType::Fire),
+            0.0
+        );
+    }
+
     #[test]
     fn test_type_effectiveness() {
         let eff = DamageTypeEffectiveness {
@@ -4,6 +4,16 @@ mod calculation;
 mod generator;
 mod packet;
 
-pub use calculation::{calculate_damage, calculate_skill_dps};
-pub use generator::{BaseDamage, DamagePacketGenerator, DotApplication, SkillStatusConversions};
-pub use packet::{DamagePacket, FinalDamage, PendingDoT, PendingStatusEffect};
+pub(crate) use calculation::scaled_status_dot_dps;
+pub use calculation::{
+    calculate_damage, calculate_damage_range, calculate_damage_with_registry,
+    calculate_hits_to_kill, calculate_skill_dps,
+};
+pub use generator::{
+    BaseDamage, CritMode, DamageConversions, DamagePacketGenerator, DamageTypeEffectiveness,
+    DotApplication, SkillStatusConversions,
+};
+pub use packet::{
+    DamagePacket, DamagePacketBuilder, DamageRange, DamageTypeRange, FinalDamage, PendingDoT,
+    PendingStatusEffect,
+};
@@ -1,10 +1,14 @@
 //! DamagePacket - The output of damage calculation
 
+use crate::types::DotOrigin;
 use loot_core::types::{DamageType, StatusEffect};
 use serde::{Deserialize, Serialize};
 
 /// The result of a StatBlock generating damage with a skill
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Packets are plain data and round-trip through serde, so they can be
+/// generated in one process and resolved with `resolve_damage` in another.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DamagePacket {
     // === Source Info ===
     /// Who dealt this damage
@@ -51,6 +55,18 @@ pub struct DamagePacket {
     pub is_spell: bool,
     /// Attacker's culling strike threshold (% of max life)
     pub culling_strike: f64,
+    /// Culling strike only triggers if this hit is a critical strike
+    #[serde(default)]
+    pub cull_requires_crit: bool,
+    /// More damage if the defender is at full life when hit (0.5 = 50% more)
+    #[serde(default)]
+    pub more_vs_full_life: Option<f64>,
+    /// Area radius of the skill after increased area (0.0 = no area)
+    #[serde(default)]
+    pub radius: f64,
+    /// Projectiles fired by the skill (0 = not a projectile skill)
+    #[serde(default)]
+    pub projectiles: u32,
     /// Life gained on kill
     pub life_on_kill: f64,
     /// Mana gained on kill
@@ -77,6 +93,10 @@ impl Default for DamagePacket {
             can_apply_on_hit: true,
             is_spell: false,
             culling_strike: 0.0,
+            cull_requires_crit: false,
+            more_vs_full_life: None,
+            radius: 0.0,
+            projectiles: 0,
             life_on_kill: 0.0,
             mana_on_kill: 0.0,
         }
@@ -93,6 +113,16 @@ impl DamagePacket {
         }
     }
 
+    /// Start building a packet by hand (tests and scripted damage)
+    pub fn builder(
+        source_id: impl Into<String>,
+        skill_id: impl Into<String>,
+    ) -> DamagePacketBuilder {
+        DamagePacketBuilder {
+            packet: DamagePacket::new(source_id.into(), skill_id.into()),
+        }
+    }
+
     /// Get total damage (sum of all types)
     pub fn total_damage(&self) -> f64 {
         self.damages.iter().map(|d| d.amount).sum()
@@ -149,8 +179,66 @@ impl DamagePacket {
     }
 }
 
+/// Builder for hand-made damage packets
+///
+/// Values are taken as final: no attacker scaling is applied.
+#[derive(Debug, Clone)]
+pub struct DamagePacketBuilder {
+    packet: DamagePacket,
+}
+
+impl DamagePacketBuilder {
+    /// Add damage of a type (merges with existing damage of that type)
+    pub fn damage(mut self, damage_type: DamageType, amount: f64) -> Self {
+        self.packet.add_damage(damage_type, amount);
+        self
+    }
+
+    /// Mark the hit as a critical strike with the given multiplier
+    pub fn crit(mut self, multiplier: f64) -> Self {
+        self.packet.is_critical = true;
+        self.packet.crit_multiplier = multiplier;
+        self
+    }
+
+    /// Set penetration for a damage type (physical is ignored)
+    pub fn penetration(mut self, damage_type: DamageType, percent: f64) -> Self {
+        match damage_type {
+            DamageType::Physical => {}
+            DamageType::Fire => self.packet.fire_pen = percent,
+            DamageType::Cold => self.packet.cold_pen = percent,
+            DamageType::Lightning => self.packet.lightning_pen = percent,
+            DamageType::Chaos => self.packet.chaos_pen = percent,
+        }
+        self
+    }
+
+    /// Set the attacker's accuracy rating
+    pub fn accuracy(mut self, accuracy: f64) -> Self {
+        self.packet.accuracy = accuracy;
+        self
+    }
+
+    /// Set whether the hit is a spell
+    pub fn spell(mut self, is_spell: bool) -> Self {
+        self.packet.is_spell = is_spell;
+        self
+    }
+
+    /// Add a status effect to attempt on hit
+    pub fn status(mut self, status: PendingStatusEffect) -> Self {
+        self.packet.status_effects_to_apply.push(status);
+        self
+    }
+
+    /// Finish the packet
+    pub fn build(self) -> DamagePacket {
+        self.packet
+    }
+}
+
 /// Final damage value for a single damage type
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FinalDamage {
     pub damage_type: DamageType,
     pub amount: f64,
@@ -165,8 +253,51 @@ impl FinalDamage {
     }
 }
 
+/// Min, max and average damage per type for a skill (see `StatBlock::preview_damage`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DamageRange {
+    /// Damage types the skill deals, in a fixed order
+    pub damages: Vec<DamageTypeRange>,
+}
+
+impl DamageRange {
+    /// Whether the skill deals no damage
+    pub fn is_empty(&self) -> bool {
+        self.damages.is_empty()
+    }
+
+    /// Range for a specific damage type
+    pub fn of_type(&self, damage_type: DamageType) -> Option<&DamageTypeRange> {
+        self.damages.iter().find(|d| d.damage_type == damage_type)
+    }
+
+    /// Sum of per-type minimums
+    pub fn total_min(&self) -> f64 {
+        self.damages.iter().map(|d| d.min).sum()
+    }
+
+    /// Sum of per-type maximums
+    pub fn total_max(&self) -> f64 {
+        self.damages.iter().map(|d| d.max).sum()
+    }
+
+    /// Sum of per-type averages
+    pub fn total_average(&self) -> f64 {
+        self.damages.iter().map(|d| d.average).sum()
+    }
+}
+
+/// Damage bounds for a single damage type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DamageTypeRange {
+    pub damage_type: DamageType,
+    pub min: f64,
+    pub max: f64,
+    pub average: f64,
+}
+
 /// A DoT effect pending application
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PendingDoT {
     /// DoT type ID
     pub dot_type: String,
@@ -194,7 +325,7 @@ impl PendingDoT {
 /// A status effect pending application
 /// Status effects don't deal direct damage but have a chance to apply
 /// based on status_damage / target_max_health
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PendingStatusEffect {
     /// The type of status effect
     pub effect_type: StatusEffect,
@@ -211,6 +342,17 @@ pub struct PendingStatusEffect {
     /// Increased chance to apply (from skill). 0.0 = no bonus, 0.2 = 20% increased.
     /// Final chance = (status_damage / target_max_health) * (1.0 + apply_chance_increased)
     pub apply_chance_increased: f64,
+    /// Whether the hit that produced this status was a critical strike
+    #[serde(default)]
+    pub from_crit: bool,
+    /// Unscaled DoT DPS, kept by ailments whose config recomputes on buff
+    #[serde(default)]
+    pub dot_origin: Option<DotOrigin>,
+    /// Literal application chance (0.0-1.0) used instead of the
+    /// status_damage / max_health formula (e.g. "30% chance to freeze on hit").
+    /// Skips buildup; `Some(1.0)` always applies unless the target avoids it
+    #[serde(default)]
+    pub fixed_chance: Option<f64>,
 }
 
 impl PendingStatusEffect {
@@ -227,6 +369,9 @@ impl PendingStatusEffect {
             magnitude,
             dot_dps: 0.0,
             apply_chance_increased: 0.0,
+            from_crit: false,
+            dot_origin: None,
+            fixed_chance: None,
         }
     }
 
@@ -245,12 +390,32 @@ impl PendingStatusEffect {
             magnitude,
             dot_dps,
             apply_chance_increased: 0.0,
+            from_crit: false,
+            dot_origin: None,
+            fixed_chance: None,
         }
     }
 
+    /// Create a status effect that applies with a fixed chance, ignoring
+    /// status damage and the target's max health
+    pub fn new_fixed_chance(
+        effect_type: StatusEffect,
+        chance: f64,
+        duration: f64,
+        magnitude: f64,
+        dot_dps: f64,
+    ) -> Self {
+        let mut pending = Self::new_with_dot(effect_type, 0.0, duration, magnitude, dot_dps);
+        pending.fixed_chance = Some(chance);
+        pending
+    }
+
     /// Calculate the chance to apply this status effect
     /// Returns a value between 0.0 and 1.0
     pub fn calculate_apply_chance(&self, target_max_health: f64) -> f64 {
+        if let Some(chance) = self.fixed_chance {
+            return chance.clamp(0.0, 1.0);
+        }
         if target_max_health <= 0.0 {
             return 0.0;
         }
@@ -311,4 +476,15 @@ mod tests {
         let dot = PendingDoT::new("ignite".to_string(), 25.0, 4.0);
         assert!((dot.total_damage() - 100.0).abs() < f64::EPSILON);
     }
+
+    #[test]
+    fn test_fixed_chance_ignores_max_health() {
+        let freeze =
+            PendingStatusEffect::new_fixed_chance(StatusEffect::Freeze, 0.3, 1.0, 1.0, 0.0);
+        assert!((freeze.calculate_apply_chance(100.0) - 0.3).abs() < f64::EPSILON);
+        assert!((freeze.calculate_apply_chance(1_000_000.0) - 0.3).abs() < f64::EPSILON);
+
+        let scaled = PendingStatusEffect::new(StatusEffect::Freeze, 30.0, 1.0, 1.0);
+        assert!(scaled.calculate_apply_chance(1_000.0) < 0.3);
+    }
 }
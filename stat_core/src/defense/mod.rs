@@ -3,7 +3,17 @@
 mod armour;
 mod evasion;
 mod resistance;
+mod survivability;
 
-pub use armour::calculate_armour_reduction;
-pub use evasion::{apply_evasion_cap, calculate_damage_cap};
-pub use resistance::{calculate_effective_resistance, calculate_resistance_mitigation};
+pub use armour::{
+    armour_needed_for_reduction, armour_reduction_percent, calculate_armour_reduction,
+};
+pub use evasion::{
+    apply_evasion_cap, calculate_damage_cap, evasion_effectiveness, evasion_needed_for_cap,
+    triggered_evasion_cap,
+};
+pub use resistance::{
+    calculate_effective_resistance, calculate_resistance_mitigation, is_resistance_capped,
+    penetration_needed, resistance_needed_for_reduction, resistance_reduction_percent,
+};
+pub use survivability::{survivability_requirements, SurvivabilityRequirements};
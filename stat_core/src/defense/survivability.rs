@@ -0,0 +1,182 @@
+//! Survivability - Analytical "what would it take to survive this hit"
+
+use super::{
+    armour_needed_for_reduction, calculate_armour_reduction, calculate_resistance_mitigation,
+};
+use crate::config::constants;
+use crate::damage::DamagePacket;
+use crate::stat_block::StatBlock;
+use loot_core::types::DamageType;
+use std::collections::HashMap;
+
+/// Defenses needed to survive a specific damage packet
+///
+/// Each requirement holds every other defense at its current value.
+/// Unreachable requirements are `f64::INFINITY`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurvivabilityRequirements {
+    /// Life lost to the hit at current defenses (after energy shield)
+    pub life_damage: f64,
+    /// Whether the defender survives the hit as-is
+    pub survives: bool,
+    /// Current life must exceed this to survive
+    pub life_needed: f64,
+    /// Armour at which the hit is survivable (0 when physical isn't the problem)
+    pub armour_needed: f64,
+    /// Resistance at which the hit is survivable, per non-physical type in the packet
+    pub resistance_needed: HashMap<DamageType, f64>,
+}
+
+/// Compute the defenses a defender needs to survive a damage packet
+///
+/// Applies resistances, armour, physical and generic damage reduction, then
+/// energy shield (respecting `chaos_bypasses_es`). Chance-based layers
+/// (evasion cap, block, dodge) are ignored, so the result is the worst case.
+/// No RNG is used.
+pub fn survivability_requirements(
+    defender: &StatBlock,
+    packet: &DamagePacket,
+) -> SurvivabilityRequirements {
+    let life = defender.current_life;
+    let es = defender.current_energy_shield.max(0.0);
+    let phys_dr = 1.0 - defender.physical_damage_reduction.clamp(0.0, 90.0) / 100.0;
+    let dr = 1.0 - defender.reduced_damage_taken.clamp(0.0, 90.0) / 100.0;
+    let bypasses_es = |dt: DamageType| dt == DamageType::Chaos && defender.chaos_bypasses_es;
+
+    // Raw and post-mitigation damage per type
+    let mut raw: HashMap<DamageType, f64> = HashMap::new();
+    for damage in &packet.damages {
+        *raw.entry(damage.damage_type).or_insert(0.0) += damage.amount;
+    }
+    let mitigated: HashMap<DamageType, f64> = raw
+        .iter()
+        .map(|(&dt, &amount)| {
+            let after = if dt == DamageType::Physical {
+                calculate_armour_reduction(defender.armour.compute(), amount) * phys_dr
+            } else {
+                calculate_resistance_mitigation(
+                    amount,
+                    defender.resistance(dt),
+                    packet.penetration(dt),
+                )
+            };
+            (dt, after * dr)
+        })
+        .collect();
+
+    // Life lost, given each type's post-mitigation damage
+    let life_loss = |damages: &HashMap<DamageType, f64>| {
+        let (bypass, absorbable) = damages.iter().fold((0.0, 0.0), |(b, a), (&dt, &d)| {
+            if bypasses_es(dt) {
+                (b + d, a)
+            } else {
+                (b, a + d)
+            }
+        });
+        bypass + (absorbable - es).max(0.0)
+    };
+    let life_damage = life_loss(&mitigated);
+
+    // Largest post-mitigation damage of `dt` that still leaves life above zero
+    let allowed_damage = |dt: DamageType| {
+        let mut others = mitigated.clone();
+        others.remove(&dt);
+        let other_bypass: f64 = others
+            .iter()
+            .filter(|(&t, _)| bypasses_es(t))
+            .map(|(_, d)| d)
+            .sum();
+        let other_absorbable: f64 = others
+            .iter()
+            .filter(|(&t, _)| !bypasses_es(t))
+            .map(|(_, d)| d)
+            .sum();
+        if bypasses_es(dt) {
+            life - other_bypass - (other_absorbable - es).max(0.0)
+        } else {
+            life - other_bypass + es - other_absorbable
+        }
+    };
+
+    let mut armour_needed = 0.0;
+    let mut resistance_needed = HashMap::new();
+    let res_constants = &constants().resistances;
+
+    for (&dt, &amount) in &raw {
+        if amount <= 0.0 {
+            continue;
+        }
+        let allowed = allowed_damage(dt) / dr;
+
+        if dt == DamageType::Physical {
+            let allowed_after_armour = allowed / phys_dr;
+            armour_needed = if allowed_after_armour >= amount {
+                0.0
+            } else if allowed_after_armour <= 0.0 {
+                f64::INFINITY
+            } else {
+                armour_needed_for_reduction(amount, (1.0 - allowed_after_armour / amount) * 100.0)
+            };
+            continue;
+        }
+
+        let pen = packet.penetration(dt);
+        let effective_needed = ((1.0 - allowed / amount) * 100.0).max(res_constants.min_value);
+        let needed = if effective_needed + pen < res_constants.max_cap {
+            effective_needed + pen
+        } else if effective_needed
+            <= res_constants.max_cap - pen * res_constants.penetration_vs_capped
+        {
+            res_constants.max_cap
+        } else {
+            f64::INFINITY
+        };
+        resistance_needed.insert(dt, needed);
+    }
+
+    SurvivabilityRequirements {
+        life_damage,
+        survives: life > life_damage,
+        life_needed: life_damage,
+        armour_needed,
+        resistance_needed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::resolve_damage;
+    use crate::config::{ensure_constants_initialized, ensure_dot_registry_initialized};
+    use crate::damage::FinalDamage;
+
+    #[test]
+    fn test_fire_resistance_needed_for_big_hit() {
+        ensure_constants_initialized();
+        ensure_dot_registry_initialized();
+        let mut defender = StatBlock::new();
+        defender.current_life = 100.0;
+
+        let mut packet = DamagePacket::new("boss".to_string(), "meteor".to_string());
+        packet
+            .damages
+            .push(FinalDamage::new(DamageType::Fire, 150.0));
+        packet.fire_pen = 25.0;
+
+        let req = survivability_requirements(&defender, &packet);
+        assert!(!req.survives);
+        // 0% res penetrated to -25%: 150 * 1.25
+        assert!((req.life_needed - 187.5).abs() < 1e-9);
+
+        // 150 fire must be cut below 100: 33.3% effective, plus 25% pen
+        let needed = req.resistance_needed[&DamageType::Fire];
+        assert!((needed - (100.0 / 3.0 + 25.0)).abs() < 1e-9);
+        assert_eq!(req.armour_needed, 0.0);
+
+        // Just above the requirement survives resolution, just below doesn't
+        defender.fire_resistance.add_flat(needed + 0.5);
+        assert!(resolve_damage(&defender, &packet).0.is_alive());
+        defender.fire_resistance.add_flat(-1.0);
+        assert!(!resolve_damage(&defender, &packet).0.is_alive());
+    }
+}
@@ -8,8 +8,31 @@ pub use active::ActiveDoT;
 pub use tick::apply_dot;
 pub use types::{DotConfig, DotStacking, StatusApplication};
 
+use loot_core::types::StatusEffect;
 use std::collections::HashMap;
 
+/// Config ID a built-in status effect is registered under
+pub fn status_config_id(status: StatusEffect) -> &'static str {
+    match status {
+        StatusEffect::Poison => "poison",
+        StatusEffect::Bleed => "bleed",
+        StatusEffect::Burn => "burn",
+        StatusEffect::Freeze => "freeze",
+        StatusEffect::Chill => "chill",
+        StatusEffect::Static => "static",
+        StatusEffect::Fear => "fear",
+        StatusEffect::Slow => "slow",
+    }
+}
+
+/// Built-in status effect for a config ID, if it maps to one
+pub fn status_for_config_id(id: &str) -> Option<StatusEffect> {
+    StatusEffect::all()
+        .iter()
+        .copied()
+        .find(|&status| status_config_id(status) == id)
+}
+
 /// DoT type registry
 #[derive(Debug, Clone, Default)]
 pub struct DotRegistry {
@@ -35,35 +58,83 @@ impl DotRegistry {
         self.configs.get(id)
     }
 
+    /// Iterate registered DoT types as `(id, config)`, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &DotConfig)> {
+        self.configs
+            .iter()
+            .map(|(id, config)| (id.as_str(), config))
+    }
+
+    /// Status effects with a registered config, in `StatusEffect::all` order
+    ///
+    /// Configs whose ID doesn't map to a built-in status are skipped. An
+    /// empty registry (e.g. in tests) yields every built-in status.
+    pub fn statuses(&self) -> impl Iterator<Item = StatusEffect> + '_ {
+        StatusEffect::all().iter().copied().filter(|&status| {
+            self.configs.is_empty() || self.configs.contains_key(status_config_id(status))
+        })
+    }
+
     /// Get the base damage percent for a status effect
-    pub fn get_base_damage_percent(&self, status: loot_core::types::StatusEffect) -> f64 {
-        use loot_core::types::StatusEffect;
-        let id = match status {
-            StatusEffect::Poison => "poison",
-            StatusEffect::Bleed => "bleed",
-            StatusEffect::Burn => "burn",
-            StatusEffect::Freeze => "freeze",
-            StatusEffect::Chill => "chill",
-            StatusEffect::Static => "static",
-            StatusEffect::Fear => "fear",
-            StatusEffect::Slow => "slow",
-        };
-        self.get(id).map(|c| c.base_damage_percent).unwrap_or(0.0)
+    pub fn get_base_damage_percent(&self, status: StatusEffect) -> f64 {
+        self.get(status_config_id(status))
+            .map(|c| c.base_damage_percent)
+            .unwrap_or(0.0)
     }
 
     /// Get the base duration for a status effect
-    pub fn get_base_duration(&self, status: loot_core::types::StatusEffect) -> f64 {
-        use loot_core::types::StatusEffect;
-        let id = match status {
-            StatusEffect::Poison => "poison",
-            StatusEffect::Bleed => "bleed",
-            StatusEffect::Burn => "burn",
-            StatusEffect::Freeze => "freeze",
-            StatusEffect::Chill => "chill",
-            StatusEffect::Static => "static",
-            StatusEffect::Fear => "fear",
-            StatusEffect::Slow => "slow",
-        };
-        self.get(id).map(|c| c.base_duration).unwrap_or(2.0)
+    pub fn get_base_duration(&self, status: StatusEffect) -> f64 {
+        self.get(status_config_id(status))
+            .map(|c| c.base_duration)
+            .unwrap_or(2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use loot_core::types::DamageType;
+
+    fn config(id: &str) -> DotConfig {
+        DotConfig {
+            id: id.to_string(),
+            name: id.to_string(),
+            damage_type: DamageType::Chaos,
+            stacking: DotStacking::Unlimited,
+            base_duration: 2.0,
+            tick_rate: 0.5,
+            base_damage_percent: 0.1,
+            max_stacks: 1,
+            stack_effectiveness: 1.0,
+            moving_multiplier: 1.0,
+            scales_with_missing_life: 0.0,
+            recompute_on_buff: false,
+            lethal: true,
+            application: StatusApplication::default(),
+        }
+    }
+
+    #[test]
+    fn test_statuses_follow_registered_configs() {
+        assert_eq!(
+            DotRegistry::new().statuses().collect::<Vec<_>>(),
+            StatusEffect::all()
+        );
+
+        let mut registry = DotRegistry::new();
+        for id in ["slow", "rot", "poison", "burn"] {
+            registry.register(config(id));
+        }
+        assert_eq!(registry.iter().count(), 4);
+        assert!(registry.iter().any(|(id, _)| id == "rot"));
+        // Custom IDs are skipped; built-ins come back in canonical order
+        assert_eq!(
+            registry.statuses().collect::<Vec<_>>(),
+            vec![StatusEffect::Poison, StatusEffect::Burn, StatusEffect::Slow]
+        );
+        assert_eq!(status_for_config_id("rot"), None);
+        for &status in StatusEffect::all() {
+            assert_eq!(status_for_config_id(status_config_id(status)), Some(status));
+        }
     }
 }
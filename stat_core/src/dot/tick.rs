@@ -35,11 +35,14 @@ impl DotTickResult {
 
 /// Process a single tick for all active DoTs
 ///
+/// `life_percent` is the bearer's current life (0-100, see
+/// `StatBlock::life_percent`), used by DoTs that scale with missing life.
 /// Returns the total damage dealt and updates the DoT list.
 pub fn process_dot_tick(
     dots: &mut Vec<ActiveDoT>,
     delta_time: f64,
     is_moving: bool,
+    life_percent: f64,
     configs: &std::collections::HashMap<String, DotConfig>,
 ) -> DotTickResult {
     let mut result = DotTickResult::new();
@@ -54,11 +57,12 @@ pub fn process_dot_tick(
             // Calculate damage for this tick
             let mut tick_damage = dot.damage_per_tick * dot.effectiveness;
 
-            // Apply moving multiplier if applicable
-            if is_moving {
-                if let Some(config) = configs.get(&dot.dot_type) {
+            if let Some(config) = configs.get(&dot.dot_type) {
+                // Apply moving multiplier if applicable
+                if is_moving {
                     tick_damage *= config.moving_multiplier;
                 }
+                tick_damage *= config.missing_life_multiplier(life_percent);
             }
 
             result.add_damage(dot.damage_type, tick_damage);
@@ -202,6 +206,9 @@ mod tests {
             max_stacks: 1,
             stack_effectiveness: 1.0,
             moving_multiplier: 1.0,
+            scales_with_missing_life: 0.0,
+            recompute_on_buff: false,
+            lethal: true,
             application: StatusApplication::default(),
         }
     }
@@ -221,6 +228,9 @@ mod tests {
             max_stacks: 8,
             stack_effectiveness: 0.5,
             moving_multiplier: 2.0,
+            scales_with_missing_life: 0.0,
+            recompute_on_buff: false,
+            lethal: true,
             application: StatusApplication::default(),
         }
     }
@@ -307,7 +317,7 @@ mod tests {
         )];
 
         let configs = HashMap::new();
-        let result = process_dot_tick(&mut dots, 0.5, false, &configs);
+        let result = process_dot_tick(&mut dots, 0.5, false, 100.0, &configs);
 
         // Should have dealt 50 damage
         assert!((result.total_damage - 50.0).abs() < 0.01);
@@ -328,14 +338,58 @@ mod tests {
         configs.insert("bleed".to_string(), make_bleed_config());
 
         // Not moving
-        let result1 = process_dot_tick(&mut dots.clone(), 1.0, false, &configs);
+        let result1 = process_dot_tick(&mut dots.clone(), 1.0, false, 100.0, &configs);
         assert!((result1.total_damage - 100.0).abs() < 0.01);
 
         // Moving - should deal double
-        let result2 = process_dot_tick(&mut dots, 1.0, true, &configs);
+        let result2 = process_dot_tick(&mut dots, 1.0, true, 100.0, &configs);
         assert!((result2.total_damage - 200.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_poison_scales_with_missing_life() {
+        let dots = vec![ActiveDoT::new(
+            "poison".to_string(),
+            "player".to_string(),
+            DamageType::Chaos,
+            100.0,
+            1.0,
+            5.0,
+        )];
+
+        let poison = DotConfig {
+            id: "poison".to_string(),
+            name: "Poison".to_string(),
+            damage_type: DamageType::Chaos,
+            stacking: DotStacking::Unlimited,
+            base_duration: 2.0,
+            tick_rate: 1.0,
+            base_damage_percent: 0.20,
+            max_stacks: 0,
+            stack_effectiveness: 1.0,
+            moving_multiplier: 1.0,
+            scales_with_missing_life: 0.5,
+            recompute_on_buff: false,
+            lethal: true,
+            application: StatusApplication::default(),
+        };
+        assert!((poison.missing_life_multiplier(100.0) - 1.0).abs() < 1e-9);
+        assert!((poison.missing_life_multiplier(50.0) - 1.25).abs() < 1e-9);
+        assert!((poison.missing_life_multiplier(0.0) - 1.5).abs() < 1e-9);
+
+        let mut configs = HashMap::new();
+        configs.insert("poison".to_string(), poison);
+        let tick_at = |life_percent: f64| {
+            process_dot_tick(&mut dots.clone(), 1.0, false, life_percent, &configs).total_damage
+        };
+
+        assert!((tick_at(100.0) - 100.0).abs() < 0.01);
+        // 1 + 0.5 * 0.5 = 1.25x at half life
+        assert!((tick_at(50.0) - 125.0).abs() < 0.01);
+        // 1 + 0.5 * 0.8 = 1.4x at 20% life
+        assert!((tick_at(20.0) - 140.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_total_dot_dps() {
         let dots = vec![
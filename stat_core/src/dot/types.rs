@@ -58,11 +58,26 @@ pub struct DotConfig {
     /// Damage multiplier while target is moving (for bleed)
     #[serde(default = "default_moving_multiplier")]
     pub moving_multiplier: f64,
+    /// Extra damage per missing life fraction ("execute" DoTs):
+    /// ticks deal `1 + factor * (1 - life%)` times damage
+    #[serde(default)]
+    pub scales_with_missing_life: f64,
+    /// Recompute applied DPS from the source's current stats on
+    /// `StatBlock::refresh_dot_dps` instead of snapshotting it at application
+    #[serde(default)]
+    pub recompute_on_buff: bool,
+    /// Whether ticks can kill; non-lethal DoTs stop at 1 life (e.g. PvP)
+    #[serde(default = "default_lethal")]
+    pub lethal: bool,
     /// How this status effect is applied (chance-based or buildup-based)
     #[serde(default)]
     pub application: StatusApplication,
 }
 
+fn default_lethal() -> bool {
+    true
+}
+
 fn default_max_stacks() -> u32 {
     1
 }
@@ -85,6 +100,12 @@ impl DotConfig {
     pub fn actual_duration(&self) -> f64 {
         self.base_tick_count() as f64 * self.tick_rate
     }
+
+    /// Tick damage multiplier for a bearer at `life_percent` (0-100) life
+    pub fn missing_life_multiplier(&self, life_percent: f64) -> f64 {
+        let missing = 1.0 - (life_percent / 100.0).clamp(0.0, 1.0);
+        1.0 + self.scales_with_missing_life * missing
+    }
 }
 
 #[cfg(test)]
@@ -114,6 +135,9 @@ mod tests {
             max_stacks: 1,
             stack_effectiveness: 1.0,
             moving_multiplier: 1.0,
+            scales_with_missing_life: 0.0,
+            recompute_on_buff: false,
+            lethal: true,
             application: StatusApplication::default(),
         };
 
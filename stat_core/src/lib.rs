@@ -40,6 +40,9 @@ pub mod source;
 pub mod stat_block;
 pub mod types;
 
+#[cfg(test)]
+mod test_fixtures;
+
 // Core API - what most users need
 pub use combat::CombatResult;
 pub use damage::{BaseDamage, DamagePacket, DamagePacketGenerator};
@@ -47,7 +50,7 @@ pub use stat_block::StatBlock;
 pub use types::{Effect, EquipmentSlot};
 
 // Configuration
-pub use config::{default_skills, init_constants, init_constants_default};
+pub use config::{default_skills, init_constants, init_constants_default, EnemyRegistry};
 pub use dot::{DotRegistry, StatusApplication};
 
 // Advanced: Custom stat sources
@@ -86,6 +86,18 @@ impl BuffSource {
     pub fn is_active(&self) -> bool {
         self.duration_remaining > 0.0 && self.stacks > 0
     }
+
+    /// Raw total this buff adds to `stat` (not counting "more" modifiers)
+    pub(crate) fn stat_total(&self, stat: StatType) -> f64 {
+        if !self.is_active() {
+            return 0.0;
+        }
+        self.modifiers
+            .iter()
+            .filter(|m| m.stat == stat && !m.is_more)
+            .map(|m| m.value_per_stack * self.stacks as f64)
+            .sum()
+    }
 }
 
 impl StatSource for BuffSource {
@@ -109,18 +121,7 @@ impl StatSource for BuffSource {
 
             if modifier.is_more {
                 // "More" multipliers
-                match modifier.stat {
-                    StatType::IncreasedPhysicalDamage => {
-                        stats.physical_damage_more.push(total_value / 100.0);
-                    }
-                    StatType::IncreasedAttackSpeed => {
-                        // Attack speed more would need special handling
-                        stats.attack_speed_increased += total_value / 100.0;
-                    }
-                    _ => {
-                        stats.apply_stat_type(modifier.stat, total_value);
-                    }
-                }
+                stats.apply_more_stat_type(modifier.stat, total_value);
             } else {
                 stats.apply_stat_type(modifier.stat, total_value);
             }
@@ -184,7 +185,7 @@ mod tests {
 
     #[test]
     fn test_buff_stacks() {
-        let mut buff = BuffSource::new("test".to_string(), "Test".to_string(), 5.0, false)
+        let buff = BuffSource::new("test".to_string(), "Test".to_string(), 5.0, false)
             .with_modifier(StatType::IncreasedPhysicalDamage, 10.0, false)
             .with_stacks(3);
 
@@ -195,6 +196,22 @@ mod tests {
         assert!((acc.physical_damage_increased - 0.30).abs() < 0.01);
     }
 
+    #[test]
+    fn test_more_attack_speed_multiplies_increased() {
+        let buff = BuffSource::new("test".to_string(), "Test".to_string(), 5.0, false)
+            .with_modifier(StatType::IncreasedAttackSpeed, 10.0, true)
+            .with_modifier(StatType::IncreasedAttackSpeed, 10.0, false);
+
+        let mut acc = StatAccumulator::new();
+        buff.apply(&mut acc);
+
+        let mut block = crate::stat_block::StatBlock::new();
+        acc.apply_to(&mut block);
+
+        // 1.10 increased * 1.10 more = 1.21, not 1.20
+        assert!((block.computed_attack_speed() - 1.21).abs() < 1e-9);
+    }
+
     #[test]
     fn test_buff_refresh() {
         let mut buff = BuffSource::new("test".to_string(), "Test".to_string(), 5.0, false);
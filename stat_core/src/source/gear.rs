@@ -21,6 +21,54 @@ impl GearSource {
         GearSource { slot, item }
     }
 
+    /// Whether a local weapon modifier of `stat` adds to the weapon itself
+    /// rather than the character (the stats `apply_modifier` handles locally)
+    fn is_local_weapon_stat(stat: StatType) -> bool {
+        matches!(
+            stat,
+            StatType::AddedPhysicalDamage
+                | StatType::AddedFireDamage
+                | StatType::AddedColdDamage
+                | StatType::AddedLightningDamage
+                | StatType::AddedChaosDamage
+                | StatType::IncreasedPhysicalDamage
+                | StatType::IncreasedFireDamage
+                | StatType::IncreasedColdDamage
+                | StatType::IncreasedLightningDamage
+                | StatType::IncreasedChaosDamage
+                | StatType::IncreasedElementalDamage
+        )
+    }
+
+    /// Raw total this item adds to `stat` as a character stat
+    ///
+    /// Counts base defenses and global modifiers; local weapon and
+    /// attribute-scaled modifiers are left out.
+    pub(crate) fn stat_total(&self, stat: StatType) -> f64 {
+        let is_weapon = self.item.damage.is_some() && matches!(self.slot, EquipmentSlot::MainHand);
+        let modifiers: f64 = self
+            .item
+            .implicit
+            .iter()
+            .chain(&self.item.prefixes)
+            .chain(&self.item.suffixes)
+            .filter(|m| m.stat == stat && m.scaling.is_none())
+            .filter(|m| {
+                !(is_weapon && m.scope == AffixScope::Local && Self::is_local_weapon_stat(m.stat))
+            })
+            .map(|m| m.value as f64)
+            .sum();
+
+        let defenses = &self.item.defenses;
+        let defense = match stat {
+            StatType::AddedArmour => defenses.armour,
+            StatType::AddedEvasion => defenses.evasion,
+            StatType::AddedEnergyShield => defenses.energy_shield,
+            _ => None,
+        };
+        modifiers + defense.unwrap_or(0) as f64
+    }
+
     /// Apply a modifier, handling local scope for weapons
     fn apply_modifier(&self, stats: &mut StatAccumulator, modifier: &Modifier, is_weapon: bool) {
         // Attribute-scaled modifiers are deferred to Phase 1.5
@@ -75,6 +123,21 @@ impl GearSource {
                 StatType::IncreasedPhysicalDamage => {
                     stats.weapon_physical_increased += modifier.value as f64 / 100.0;
                 }
+                StatType::IncreasedFireDamage => {
+                    stats.weapon_fire_increased += modifier.value as f64 / 100.0;
+                }
+                StatType::IncreasedColdDamage => {
+                    stats.weapon_cold_increased += modifier.value as f64 / 100.0;
+                }
+                StatType::IncreasedLightningDamage => {
+                    stats.weapon_lightning_increased += modifier.value as f64 / 100.0;
+                }
+                StatType::IncreasedChaosDamage => {
+                    stats.weapon_chaos_increased += modifier.value as f64 / 100.0;
+                }
+                StatType::IncreasedElementalDamage => {
+                    stats.weapon_elemental_increased += modifier.value as f64 / 100.0;
+                }
                 // Other local stats fall through to global handling
                 _ => {
                     stats.apply_stat_type(modifier.stat, modifier.value as f64);
@@ -116,13 +179,13 @@ impl StatSource for GearSource {
 
         // Apply base defenses
         if let Some(armour) = self.item.defenses.armour {
-            stats.armour_flat += armour as f64;
+            stats.apply_stat_type(StatType::AddedArmour, armour as f64);
         }
         if let Some(evasion) = self.item.defenses.evasion {
-            stats.evasion_flat += evasion as f64;
+            stats.apply_stat_type(StatType::AddedEvasion, evasion as f64);
         }
         if let Some(es) = self.item.defenses.energy_shield {
-            stats.energy_shield_flat += es as f64;
+            stats.apply_stat_type(StatType::AddedEnergyShield, es as f64);
         }
 
         // Apply weapon damage (if weapon)
@@ -154,29 +217,44 @@ impl StatSource for GearSource {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_fixtures::{item, modifier};
+    use loot_core::types::ItemClass;
 
     #[test]
     fn test_gear_source_id() {
-        // Create a minimal item for testing
-        let item = Item {
-            seed: 12345,
-            operations: vec![],
-            base_type_id: "test_sword".to_string(),
-            name: "Test Sword".to_string(),
-            base_name: "Sword".to_string(),
-            class: loot_core::types::ItemClass::OneHandSword,
-            rarity: "normal".to_string(),
-            tags: vec![],
-            requirements: loot_core::types::Requirements::default(),
-            implicit: None,
-            prefixes: vec![],
-            suffixes: vec![],
-            defenses: loot_core::item::Defenses::default(),
-            damage: None,
-            granted_skills: vec![],
-        };
+        let item = item("test_sword", ItemClass::OneHandSword);
 
         let source = GearSource::new(EquipmentSlot::MainHand, item);
         assert_eq!(source.id(), "test_sword");
     }
+
+    #[test]
+    fn test_local_increased_fire_scales_weapon_fire() {
+        let mut item = item("ember_blade", ItemClass::OneHandSword);
+        item.prefixes = vec![
+            modifier(StatType::AddedFireDamage, AffixScope::Local, 10, Some(20)),
+            modifier(StatType::IncreasedFireDamage, AffixScope::Local, 50, None),
+        ];
+        item.damage = Some(loot_core::item::WeaponDamage {
+            damages: vec![loot_core::item::DamageValue {
+                damage_type: DamageType::Fire,
+                min: 2,
+                max: 4,
+            }],
+            attack_speed: 1.2,
+            critical_chance: 5.0,
+            spell_efficiency: 0.0,
+        });
+
+        let mut acc = StatAccumulator::new();
+        GearSource::new(EquipmentSlot::MainHand, item).apply(&mut acc);
+        let mut block = crate::stat_block::StatBlock::new();
+        acc.apply_to(&mut block);
+
+        // (2 + 10) * 1.5 = 18, (4 + 20) * 1.5 = 36
+        assert!((block.weapon_fire_min - 18.0).abs() < 1e-9);
+        assert!((block.weapon_fire_max - 36.0).abs() < 1e-9);
+        // Local increased fire does not leak into global fire damage
+        assert!(block.global_fire_damage.increased.abs() < f64::EPSILON);
+    }
 }
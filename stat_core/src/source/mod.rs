@@ -4,11 +4,13 @@ mod base_stats;
 mod buff;
 mod gear;
 mod skill_tree;
+mod template;
 
 pub use base_stats::BaseStatsSource;
-pub use buff::BuffSource;
+pub use buff::{BuffPresets, BuffSource};
 pub use gear::GearSource;
 pub use skill_tree::SkillTreeSource;
+pub use template::TemplateSource;
 
 use crate::stat_block::StatAccumulator;
 
@@ -0,0 +1,85 @@
+//! TemplateSource - Fixed base stats from an enemy template
+
+use crate::source::StatSource;
+use crate::stat_block::StatAccumulator;
+use loot_core::types::{DamageType, StatType};
+use serde::{Deserialize, Serialize};
+
+/// Base stats from a template (e.g. an enemy definition)
+///
+/// Stored on the StatBlock and applied before gear on every rebuild, so
+/// template life, defenses and weapon damage survive buffs and effects
+/// expiring.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TemplateSource {
+    /// Template identifier
+    pub template_id: String,
+    /// Flat stat modifiers on top of the StatBlock defaults
+    pub modifiers: Vec<(StatType, f64)>,
+    /// Base weapon damage as `(type, min, max)`
+    pub weapon_damages: Vec<(DamageType, f64, f64)>,
+    /// Weapon attacks per second (0 keeps the default)
+    pub weapon_attack_speed: f64,
+    /// Weapon base crit chance
+    pub weapon_crit_chance: f64,
+}
+
+impl TemplateSource {
+    /// Create an empty template source
+    pub fn new(template_id: impl Into<String>) -> Self {
+        TemplateSource {
+            template_id: template_id.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a flat stat modifier (zero values are skipped)
+    pub fn with_modifier(mut self, stat: StatType, value: f64) -> Self {
+        if value != 0.0 {
+            self.modifiers.push((stat, value));
+        }
+        self
+    }
+
+    /// Add a base weapon damage range (empty ranges are skipped)
+    pub fn with_weapon_damage(mut self, damage_type: DamageType, min: f64, max: f64) -> Self {
+        if min > 0.0 || max > 0.0 {
+            self.weapon_damages.push((damage_type, min, max));
+        }
+        self
+    }
+}
+
+impl StatSource for TemplateSource {
+    fn id(&self) -> &str {
+        &self.template_id
+    }
+
+    fn priority(&self) -> i32 {
+        -100 // Templates are base stats
+    }
+
+    fn apply(&self, stats: &mut StatAccumulator) {
+        for (stat, value) in &self.modifiers {
+            stats.apply_stat_type(*stat, *value);
+        }
+
+        for (damage_type, min, max) in &self.weapon_damages {
+            match damage_type {
+                DamageType::Physical => {
+                    stats.weapon_physical_min += min;
+                    stats.weapon_physical_max += max;
+                }
+                _ => stats
+                    .weapon_elemental_damages
+                    .push((*damage_type, *min, *max)),
+            }
+        }
+        if self.weapon_attack_speed > 0.0 {
+            stats.weapon_attack_speed = self.weapon_attack_speed;
+        }
+        if self.weapon_crit_chance > 0.0 {
+            stats.weapon_crit_chance = self.weapon_crit_chance;
+        }
+    }
+}
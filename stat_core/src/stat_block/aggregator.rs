@@ -118,10 +118,14 @@ pub struct StatAccumulator {
     pub chaos_damage_more: Vec<f64>,
     pub elemental_damage_increased: f64,
     pub attack_speed_increased: f64,
+    pub attack_speed_more: Vec<f64>,
     pub cast_speed_increased: f64,
+    pub cast_speed_more: Vec<f64>,
     pub critical_chance_flat: f64,
     pub critical_chance_increased: f64,
     pub critical_multiplier_flat: f64,
+    /// Crit multiplier for skills dealing each damage type
+    pub crit_multiplier_by_type: HashMap<DamageType, f64>,
 
     // === Penetration ===
     pub fire_penetration: f64,
@@ -132,6 +136,9 @@ pub struct StatAccumulator {
     // === Recovery ===
     pub life_regen_flat: f64,
     pub mana_regen_flat: f64,
+    pub life_recovery_increased: f64,
+    pub mana_recovery_increased: f64,
+    pub es_recharge_increased: f64,
     pub life_leech_percent: f64,
     pub mana_leech_percent: f64,
     pub life_on_hit: f64,
@@ -150,6 +157,11 @@ pub struct StatAccumulator {
     pub weapon_physical_max: f64,
     pub weapon_physical_increased: f64, // Local increased physical damage on weapon
     pub weapon_elemental_damages: Vec<(DamageType, f64, f64)>,
+    pub weapon_fire_increased: f64, // Local increased fire damage on weapon
+    pub weapon_cold_increased: f64,
+    pub weapon_lightning_increased: f64,
+    pub weapon_chaos_increased: f64,
+    pub weapon_elemental_increased: f64, // Local increased elemental (fire/cold/lightning)
     pub weapon_attack_speed: f64,
     pub weapon_crit_chance: f64,
 
@@ -170,10 +182,13 @@ pub struct StatAccumulator {
     pub status_magnitude_on_crit: f64,
     /// Increased status damage on critical strike
     pub status_damage_on_crit_increased: f64,
+    /// More DoT damage for ailments applied by critical strikes
+    pub crit_ailment_dot_more: f64,
 
     // === Block ===
     pub block_chance: f64,
     pub block_amount: f64,
+    pub flat_block_absorb: f64,
 
     // === Dodge ===
     pub spell_dodge_chance: f64,
@@ -193,12 +208,26 @@ pub struct StatAccumulator {
     // === Global Damage Modifiers ===
     pub global_damage_increased: f64,
     pub dot_multiplier: f64,
+    pub stationary_damage_more: Vec<f64>,
+    pub moving_damage_more: Vec<f64>,
+    pub trap_damage_more: Vec<f64>,
+    pub mine_damage_more: Vec<f64>,
+    pub totem_damage_more: Vec<f64>,
 
     // === Defensive ===
     pub reduced_damage_taken: f64,
+    pub dot_damage_taken_increased: f64,
     pub physical_damage_reduction: f64,
     pub physical_penetration: f64,
     pub culling_strike: f64,
+    pub damage_to_mana_percent: f64,
+    pub stun_threshold_increased: f64,
+    pub reflect_physical_percent: f64,
+    pub reflect_elemental_percent: f64,
+    /// Increased effect of buffs on this entity (as decimal)
+    pub buff_effect_received: f64,
+    /// Increased effect of consumables used by this entity (as decimal)
+    pub consumable_effect: f64,
 
     // === On-Kill Recovery ===
     pub life_on_kill: f64,
@@ -234,6 +263,21 @@ impl StatAccumulator {
             StatType::IncreasedAttackSpeed => self.attack_speed_increased += value / 100.0,
             StatType::IncreasedCriticalChance => self.critical_chance_increased += value / 100.0,
             StatType::IncreasedCriticalDamage => self.critical_multiplier_flat += value / 100.0,
+            StatType::IncreasedPhysicalCriticalDamage => {
+                self.add_crit_multiplier(DamageType::Physical, value / 100.0)
+            }
+            StatType::IncreasedFireCriticalDamage => {
+                self.add_crit_multiplier(DamageType::Fire, value / 100.0)
+            }
+            StatType::IncreasedColdCriticalDamage => {
+                self.add_crit_multiplier(DamageType::Cold, value / 100.0)
+            }
+            StatType::IncreasedLightningCriticalDamage => {
+                self.add_crit_multiplier(DamageType::Lightning, value / 100.0)
+            }
+            StatType::IncreasedChaosCriticalDamage => {
+                self.add_crit_multiplier(DamageType::Chaos, value / 100.0)
+            }
 
             // Defenses
             StatType::AddedArmour => self.armour_flat += value,
@@ -267,6 +311,11 @@ impl StatAccumulator {
             StatType::IncreasedMana => self.mana_increased += value / 100.0,
             StatType::LifeRegeneration => self.life_regen_flat += value,
             StatType::ManaRegeneration => self.mana_regen_flat += value,
+            StatType::IncreasedLifeRecoveryRate => self.life_recovery_increased += value / 100.0,
+            StatType::IncreasedManaRecoveryRate => self.mana_recovery_increased += value / 100.0,
+            StatType::IncreasedEnergyShieldRechargeRate => {
+                self.es_recharge_increased += value / 100.0
+            }
             StatType::LifeOnHit => self.life_on_hit += value,
             StatType::LifeLeech => self.life_leech_percent += value / 100.0,
             StatType::ManaLeech => self.mana_leech_percent += value / 100.0,
@@ -535,10 +584,12 @@ impl StatAccumulator {
             StatType::IncreasedStatusDamageOnCrit => {
                 self.status_damage_on_crit_increased += value / 100.0
             }
+            StatType::MoreAilmentDotOnCrit => self.crit_ailment_dot_more += value / 100.0,
 
             // Block
             StatType::BlockChance => self.block_chance += value,
             StatType::BlockAmount => self.block_amount += value,
+            StatType::FlatBlockAbsorb => self.flat_block_absorb += value,
 
             // Dodge
             StatType::SpellDodgeChance => self.spell_dodge_chance += value,
@@ -561,9 +612,17 @@ impl StatAccumulator {
             // Global damage modifiers
             StatType::IncreasedGlobalDamage => self.global_damage_increased += value / 100.0,
             StatType::DamageOverTimeMultiplier => self.dot_multiplier += value / 100.0,
+            StatType::MoreDamageWhileStationary => {
+                self.stationary_damage_more.push(value / 100.0)
+            }
+            StatType::MoreDamageWhileMoving => self.moving_damage_more.push(value / 100.0),
+            StatType::MoreTrapDamage => self.trap_damage_more.push(value / 100.0),
+            StatType::MoreMineDamage => self.mine_damage_more.push(value / 100.0),
+            StatType::MoreTotemDamage => self.totem_damage_more.push(value / 100.0),
 
             // Defensive
             StatType::ReducedDamageTaken => self.reduced_damage_taken += value / 100.0,
+            StatType::IncreasedDotDamageTaken => self.dot_damage_taken_increased += value / 100.0,
             StatType::PhysicalDamageReduction => self.physical_damage_reduction += value,
             StatType::PhysicalPenetration => self.physical_penetration += value,
             StatType::CullingStrike => {
@@ -572,6 +631,12 @@ impl StatAccumulator {
                     self.culling_strike = value;
                 }
             }
+            StatType::DamageTakenFromManaBeforeLife => self.damage_to_mana_percent += value,
+            StatType::IncreasedStunThreshold => self.stun_threshold_increased += value / 100.0,
+            StatType::IncreasedBuffEffectReceived => self.buff_effect_received += value / 100.0,
+            StatType::IncreasedConsumableEffect => self.consumable_effect += value / 100.0,
+            StatType::ReflectPhysicalDamage => self.reflect_physical_percent += value,
+            StatType::ReflectElementalDamage => self.reflect_elemental_percent += value,
 
             // On-kill recovery
             StatType::LifeOnKill => self.life_on_kill += value,
@@ -579,6 +644,39 @@ impl StatAccumulator {
         }
     }
 
+    /// Apply a loot_core StatType as a "more" multiplier (value in percent)
+    ///
+    /// Damage, attack/cast speed, life, and mana have dedicated more buckets;
+    /// other stats fall
+    /// back to their regular (increased/flat) handling.
+    pub fn apply_more_stat_type(&mut self, stat: StatType, value: f64) {
+        match stat {
+            StatType::IncreasedPhysicalDamage => self.physical_damage_more.push(value / 100.0),
+            StatType::IncreasedFireDamage => self.fire_damage_more.push(value / 100.0),
+            StatType::IncreasedColdDamage => self.cold_damage_more.push(value / 100.0),
+            StatType::IncreasedLightningDamage => self.lightning_damage_more.push(value / 100.0),
+            StatType::IncreasedChaosDamage => self.chaos_damage_more.push(value / 100.0),
+            StatType::IncreasedAttackSpeed => self.attack_speed_more.push(value / 100.0),
+            StatType::IncreasedCastSpeed => self.cast_speed_more.push(value / 100.0),
+            StatType::IncreasedLife => self.life_more.push(value / 100.0),
+            StatType::IncreasedMana => self.mana_more.push(value / 100.0),
+            _ => self.apply_stat_type(stat, value),
+        }
+    }
+
+    /// Local increased damage on the weapon for a non-physical damage type
+    fn weapon_local_increased(&self, damage_type: DamageType) -> f64 {
+        match damage_type {
+            DamageType::Physical => 0.0,
+            DamageType::Fire => self.weapon_fire_increased + self.weapon_elemental_increased,
+            DamageType::Cold => self.weapon_cold_increased + self.weapon_elemental_increased,
+            DamageType::Lightning => {
+                self.weapon_lightning_increased + self.weapon_elemental_increased
+            }
+            DamageType::Chaos => self.weapon_chaos_increased,
+        }
+    }
+
     // === Status Effect Helper Methods ===
 
     /// Add to a status effect's DoT increased stat
@@ -594,6 +692,14 @@ impl StatAccumulator {
             .duration_increased += value;
     }
 
+    /// Add crit multiplier for skills dealing a damage type
+    fn add_crit_multiplier(&mut self, damage_type: DamageType, value: f64) {
+        *self
+            .crit_multiplier_by_type
+            .entry(damage_type)
+            .or_insert(0.0) += value;
+    }
+
     /// Add to a status effect's magnitude stat
     fn add_status_magnitude(&mut self, status: StatusEffect, value: f64) {
         self.status_stats.entry(status).or_default().magnitude += value;
@@ -689,6 +795,7 @@ impl StatAccumulator {
         for more in &self.mana_more {
             block.max_mana.add_more(*more);
         }
+        block.max_energy_shield += self.energy_shield_flat * (1.0 + self.energy_shield_increased);
 
         // Attributes (all_attributes applies to all)
         block
@@ -739,6 +846,14 @@ impl StatAccumulator {
             Self::apply_stat_to_block(block, pending.stat, effective);
         }
 
+        // Character-level increased damage per 10 of an attribute
+        for (attribute, damage_type, per_10) in block.damage_per_attribute.clone() {
+            let stacks = block.attribute_value(attribute) / 10.0;
+            block
+                .global_damage_mut(damage_type)
+                .add_increased(per_10 * stacks / 100.0);
+        }
+
         // Defenses
         block.armour.add_flat(self.armour_flat);
         block.armour.add_increased(self.armour_increased);
@@ -806,7 +921,13 @@ impl StatAccumulator {
         block
             .attack_speed
             .add_increased(self.attack_speed_increased);
+        for more in &self.attack_speed_more {
+            block.attack_speed.add_more(*more);
+        }
         block.cast_speed.add_increased(self.cast_speed_increased);
+        for more in &self.cast_speed_more {
+            block.cast_speed.add_more(*more);
+        }
 
         // Crit
         block.critical_chance.add_flat(self.critical_chance_flat);
@@ -816,6 +937,12 @@ impl StatAccumulator {
         block
             .critical_multiplier
             .add_flat(self.critical_multiplier_flat);
+        for (&damage_type, &bonus) in &self.crit_multiplier_by_type {
+            *block
+                .crit_multiplier_by_type
+                .entry(damage_type)
+                .or_insert(0.0) += bonus;
+        }
 
         // Penetration
         block.fire_penetration.add_flat(self.fire_penetration);
@@ -828,8 +955,12 @@ impl StatAccumulator {
         // Recovery
         block.life_regen.add_flat(self.life_regen_flat);
         block.mana_regen.add_flat(self.mana_regen_flat);
+        block.life_regen.add_increased(self.life_recovery_increased);
+        block.mana_regen.add_increased(self.mana_recovery_increased);
+        block.es_recharge_increased += self.es_recharge_increased;
         block.life_leech.add_flat(self.life_leech_percent);
         block.mana_leech.add_flat(self.mana_leech_percent);
+        block.life_on_hit += self.life_on_hit;
 
         // Weapon stats - apply local increased physical damage
         if self.weapon_physical_min > 0.0 || self.weapon_physical_max > 0.0 {
@@ -844,24 +975,25 @@ impl StatAccumulator {
             block.weapon_crit_chance = self.weapon_crit_chance;
         }
 
-        // Apply weapon elemental damages
+        // Apply weapon elemental damages, scaled by local increased damage
         for (dmg_type, min, max) in &self.weapon_elemental_damages {
+            let mult = 1.0 + self.weapon_local_increased(*dmg_type);
             match dmg_type {
                 DamageType::Fire => {
-                    block.weapon_fire_min += min;
-                    block.weapon_fire_max += max;
+                    block.weapon_fire_min += min * mult;
+                    block.weapon_fire_max += max * mult;
                 }
                 DamageType::Cold => {
-                    block.weapon_cold_min += min;
-                    block.weapon_cold_max += max;
+                    block.weapon_cold_min += min * mult;
+                    block.weapon_cold_max += max * mult;
                 }
                 DamageType::Lightning => {
-                    block.weapon_lightning_min += min;
-                    block.weapon_lightning_max += max;
+                    block.weapon_lightning_min += min * mult;
+                    block.weapon_lightning_max += max * mult;
                 }
                 DamageType::Chaos => {
-                    block.weapon_chaos_min += min;
-                    block.weapon_chaos_max += max;
+                    block.weapon_chaos_min += min * mult;
+                    block.weapon_chaos_max += max * mult;
                 }
                 DamageType::Physical => {
                     // Physical is handled separately
@@ -881,6 +1013,7 @@ impl StatAccumulator {
         // Block
         block.block_chance.add_flat(self.block_chance);
         block.block_amount.add_flat(self.block_amount);
+        block.flat_block_absorb += self.flat_block_absorb;
 
         // Dodge
         block.spell_dodge_chance += self.spell_dodge_chance;
@@ -919,11 +1052,37 @@ impl StatAccumulator {
         // DoT multiplier
         block.dot_multiplier += self.dot_multiplier;
 
+        // Movement-conditional damage
+        for more in &self.stationary_damage_more {
+            block.damage_while_stationary.add_more(*more);
+        }
+        for more in &self.moving_damage_more {
+            block.damage_while_moving.add_more(*more);
+        }
+
+        // Delivery-tag damage (traps, mines, totems)
+        for more in &self.trap_damage_more {
+            block.trap_damage.add_more(*more);
+        }
+        for more in &self.mine_damage_more {
+            block.mine_damage.add_more(*more);
+        }
+        for more in &self.totem_damage_more {
+            block.totem_damage.add_more(*more);
+        }
+
         // Defensive
         block.reduced_damage_taken += self.reduced_damage_taken;
+        block.dot_damage_taken += self.dot_damage_taken_increased;
         block.physical_damage_reduction += self.physical_damage_reduction;
         block.physical_penetration.add_flat(self.physical_penetration);
         block.culling_strike = self.culling_strike.max(block.culling_strike);
+        block.damage_to_mana_percent += self.damage_to_mana_percent;
+        block.stun_threshold_increased += self.stun_threshold_increased;
+        block.reflect_physical_percent += self.reflect_physical_percent;
+        block.reflect_elemental_percent += self.reflect_elemental_percent;
+        block.buff_effect_received += self.buff_effect_received;
+        block.consumable_effect += self.consumable_effect;
 
         // On-kill recovery
         block.life_on_kill += self.life_on_kill;
@@ -955,5 +1114,6 @@ impl StatAccumulator {
         block.status_effect_stats.status_magnitude_on_crit = self.status_magnitude_on_crit;
         block.status_effect_stats.status_damage_on_crit_increased =
             self.status_damage_on_crit_increased;
+        block.status_effect_stats.crit_ailment_dot_more = self.crit_ailment_dot_more;
     }
 }
@@ -1,7 +1,25 @@
 //! Computed/derived stat calculations for StatBlock
 
+use crate::config::constants;
+use crate::damage::DamagePacketGenerator;
+use crate::defense::{
+    armour_reduction_percent, calculate_armour_reduction, calculate_effective_resistance,
+    calculate_resistance_mitigation,
+};
 use crate::stat_block::StatBlock;
-use loot_core::types::DamageType;
+use crate::types::{EffectType, SkillTag};
+use loot_core::types::{DamageType, StatusEffect};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Point-in-time copy of a StatBlock's elemental and chaos resistances
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Resistances {
+    pub fire: f64,
+    pub cold: f64,
+    pub lightning: f64,
+    pub chaos: f64,
+}
 
 impl StatBlock {
     /// Get the damage scaling multiplier for a specific damage type
@@ -26,6 +44,42 @@ impl StatBlock {
         }
     }
 
+    /// Get the resistance hits are resolved against: computed resistance plus
+    /// any external `resistance_modifiers`, clamped to the configured min/max
+    pub fn modified_resistance(&self, damage_type: DamageType) -> f64 {
+        let modifier = self
+            .resistance_modifiers
+            .get(&damage_type)
+            .copied()
+            .unwrap_or(0.0);
+        if modifier == 0.0 {
+            return self.resistance(damage_type);
+        }
+        let res_constants = &constants().resistances;
+        (self.resistance(damage_type) + modifier)
+            .clamp(res_constants.min_value, res_constants.max_cap)
+    }
+
+    /// Get the resistance that actually applies against a hit with the given
+    /// penetration, matching what resolution uses (e.g. 75% res vs 25% pen).
+    /// Physical damage has no resistance and always returns 0.
+    pub fn effective_resistance_vs(&self, damage_type: DamageType, penetration: f64) -> f64 {
+        if damage_type == DamageType::Physical {
+            return 0.0;
+        }
+        calculate_effective_resistance(self.modified_resistance(damage_type), penetration)
+    }
+
+    /// Snapshot all resistances at once (uncapped, same as `resistance`)
+    pub fn resistances_snapshot(&self) -> Resistances {
+        Resistances {
+            fire: self.resistance(DamageType::Fire),
+            cold: self.resistance(DamageType::Cold),
+            lightning: self.resistance(DamageType::Lightning),
+            chaos: self.resistance(DamageType::Chaos),
+        }
+    }
+
     /// Get the penetration value for a damage type
     pub fn penetration(&self, damage_type: DamageType) -> f64 {
         match damage_type {
@@ -62,6 +116,83 @@ impl StatBlock {
         self.cast_speed.compute()
     }
 
+    /// Skill uses per second: attack or cast speed times the skill's speed
+    /// modifier
+    ///
+    /// This is the character-sheet rate used by DPS and mana calculations.
+    /// Chills, slows and freezes are not included; multiply by
+    /// `action_speed_multiplier()` for the rate an ailed entity acts at.
+    pub fn skill_use_rate(&self, skill: &DamagePacketGenerator) -> f64 {
+        let speed = if skill.is_attack() {
+            self.computed_attack_speed()
+        } else {
+            self.computed_cast_speed()
+        };
+        speed * skill.attack_speed_modifier
+    }
+
+    /// Whether an active Freeze prevents this entity from acting
+    pub fn is_frozen(&self) -> bool {
+        self.effects
+            .iter()
+            .any(|e| e.is_active() && e.status() == Some(StatusEffect::Freeze))
+    }
+
+    /// Multiplier on action and movement speed from active ailments
+    ///
+    /// Only the strongest Chill or Slow applies; each removes
+    /// `magnitude * reduction_per_magnitude` of action speed, capped at
+    /// `max_reduction`. Frozen entities have no action speed.
+    pub fn action_speed_multiplier(&self) -> f64 {
+        if self.is_frozen() {
+            return 0.0;
+        }
+        let strongest = self
+            .effects
+            .iter()
+            .filter(|e| e.is_active())
+            .filter_map(|e| match &e.effect_type {
+                EffectType::Ailment {
+                    status: StatusEffect::Chill | StatusEffect::Slow,
+                    magnitude,
+                    ..
+                } => Some(*magnitude),
+                _ => None,
+            })
+            .fold(0.0, f64::max);
+        if strongest <= 0.0 {
+            return 1.0;
+        }
+        let slow = &constants().slow;
+        1.0 - (strongest * slow.reduction_per_magnitude).clamp(0.0, slow.max_reduction)
+    }
+
+    /// Multiplier on incoming hit damage from active ailments
+    ///
+    /// Only the strongest Static applies, adding
+    /// `magnitude * damage_taken_per_magnitude` damage taken, capped at
+    /// `max_damage_taken`.
+    pub fn incoming_damage_multiplier(&self) -> f64 {
+        let strongest = self
+            .effects
+            .iter()
+            .filter(|e| e.is_active())
+            .filter_map(|e| match &e.effect_type {
+                EffectType::Ailment {
+                    status: StatusEffect::Static,
+                    magnitude,
+                    ..
+                } => Some(*magnitude),
+                _ => None,
+            })
+            .fold(0.0, f64::max);
+        if strongest <= 0.0 {
+            return 1.0;
+        }
+        let shock = &constants().shock;
+        1.0 + (strongest * shock.damage_taken_per_magnitude).clamp(0.0, shock.max_damage_taken)
+    }
+
     /// Get computed critical strike chance for attacks
     pub fn computed_attack_crit_chance(&self) -> f64 {
         // Base crit from weapon + modifiers
@@ -76,6 +207,53 @@ impl StatBlock {
         self.critical_multiplier.compute()
     }
 
+    /// Critical strike multiplier for a specific skill
+    ///
+    /// Adds the skill's own bonus and the per-damage-type bonuses for every
+    /// type the skill deals to `computed_crit_multiplier`.
+    pub fn crit_multiplier_for(&self, skill: &DamagePacketGenerator) -> f64 {
+        let type_bonus: f64 = self
+            .crit_multiplier_by_type
+            .iter()
+            .filter(|(&damage_type, _)| skill.deals_damage_type(damage_type))
+            .map(|(_, bonus)| bonus)
+            .sum();
+        self.computed_crit_multiplier() + skill.crit_multiplier_bonus + type_bonus
+    }
+
+    /// Get the total percent chance to avoid a status effect (0-100)
+    pub fn ailment_avoid(&self, status: StatusEffect) -> f64 {
+        let specific = self
+            .ailment_avoid_chance
+            .get(&status)
+            .copied()
+            .unwrap_or(0.0);
+        (specific + self.all_ailment_avoid).clamp(0.0, 100.0)
+    }
+
+    /// Get the more-damage multiplier for the current movement state
+    pub fn movement_damage_multiplier(&self) -> f64 {
+        if self.is_moving {
+            self.damage_while_moving.total_more_multiplier()
+        } else {
+            self.damage_while_stationary.total_more_multiplier()
+        }
+    }
+
+    /// Get the more-damage multiplier from a skill's delivery tags
+    /// (Trap, Mine, Totem); 1.0 for skills with none of them
+    pub fn delivery_damage_multiplier(&self, skill: &DamagePacketGenerator) -> f64 {
+        [
+            (SkillTag::Trap, &self.trap_damage),
+            (SkillTag::Mine, &self.mine_damage),
+            (SkillTag::Totem, &self.totem_damage),
+        ]
+        .iter()
+        .filter(|(tag, _)| skill.tags.contains(tag))
+        .map(|(_, damage)| damage.total_more_multiplier())
+        .product()
+    }
+
     /// Get weapon damage range for a damage type
     pub fn weapon_damage(&self, damage_type: DamageType) -> (f64, f64) {
         match damage_type {
@@ -124,11 +302,114 @@ impl StatBlock {
         }
         (self.current_energy_shield / self.max_energy_shield * 100.0).clamp(0.0, 100.0)
     }
+
+    /// Post-mitigation damage needed to kill this entity from its current state
+    ///
+    /// Sums current ward, life and energy shield, plus the mana that would
+    /// absorb damage via `damage_to_mana_percent`. Ward soaks hits in full
+    /// before the other pools. Mana only soaks its share of each hit, so it
+    /// adds at most `(life + ES) * share / (1 - share)`. Assumes all damage
+    /// passes through ES (no chaos bypass) and ignores recovery.
+    pub fn total_defensive_pool(&self) -> f64 {
+        let ward = self.current_ward.max(0.0);
+        let life_and_es = self.current_life.max(0.0) + self.current_energy_shield.max(0.0);
+        let mana = self.current_mana.max(0.0);
+        let share = self.damage_to_mana_percent.clamp(0.0, 100.0) / 100.0;
+        if share <= 0.0 || mana <= 0.0 {
+            return ward + life_and_es;
+        }
+        if share >= 1.0 {
+            return ward + life_and_es + mana;
+        }
+        ward + (life_and_es / (1.0 - share)).min(life_and_es + mana)
+    }
+
+    /// Total physical damage reduction percentage against a hit of
+    /// `reference_hit`, combining armour and flat physical damage reduction
+    ///
+    /// Capped at `armour.max_physical_reduction` (default 90%), the same cap
+    /// resolution applies to armour and physical DR combined.
+    pub fn total_physical_reduction(&self, reference_hit: f64) -> f64 {
+        let after_armour =
+            1.0 - armour_reduction_percent(self.armour.compute(), reference_hit) / 100.0;
+        let after_dr = 1.0 - self.physical_damage_reduction.clamp(0.0, 90.0) / 100.0;
+        let total = (1.0 - after_armour * after_dr) * 100.0;
+        total.clamp(0.0, constants().armour.max_physical_reduction)
+    }
+
+    /// Raw damage of one type this entity can take before dying, in hits of
+    /// `hit_size` (armour mitigates each hit by its size)
+    ///
+    /// Uses current life plus energy shield (chaos skips ES when
+    /// `chaos_bypasses_es`), resistance or armour and physical damage
+    /// reduction, reduced damage taken and active Static. Chance-based layers
+    /// (evasion, block, dodge) are ignored. Damage that is fully mitigated
+    /// gives `f64::INFINITY`.
+    pub fn effective_health_vs(&self, damage_type: DamageType, hit_size: f64) -> f64 {
+        let es = if damage_type == DamageType::Chaos && self.chaos_bypasses_es {
+            0.0
+        } else {
+            self.current_energy_shield.max(0.0)
+        };
+        let pool = self.current_life.max(0.0) + es;
+
+        let taken = self.damage_taken_fraction(damage_type, hit_size, 0.0);
+        if taken <= 0.0 {
+            return f64::INFINITY;
+        }
+        pool / taken
+    }
+
+    /// Fraction of a hit of `hit_size` that this entity actually takes
+    ///
+    /// Same deterministic layers as `effective_health_vs`; `penetration`
+    /// lowers resistance for non-physical damage. Armour and physical damage
+    /// reduction share the `armour.max_physical_reduction` cap, as in
+    /// resolution.
+    pub(crate) fn damage_taken_fraction(
+        &self,
+        damage_type: DamageType,
+        hit_size: f64,
+        penetration: f64,
+    ) -> f64 {
+        let mitigated = if damage_type == DamageType::Physical {
+            let after_armour = if hit_size > 0.0 {
+                calculate_armour_reduction(self.armour.compute(), hit_size) / hit_size
+            } else {
+                1.0
+            };
+            let max_reduction = constants().armour.max_physical_reduction.clamp(0.0, 100.0);
+            let after_dr = 1.0 - self.physical_damage_reduction.clamp(0.0, 90.0) / 100.0;
+            (after_armour * after_dr).max(1.0 - max_reduction / 100.0)
+        } else {
+            calculate_resistance_mitigation(1.0, self.modified_resistance(damage_type), penetration)
+        };
+        mitigated
+            * (1.0 - self.reduced_damage_taken.clamp(0.0, 90.0) / 100.0)
+            * self.incoming_damage_multiplier()
+    }
+
+    /// `effective_health_vs` for every damage type
+    pub fn effective_health_all(&self, hit_size: f64) -> HashMap<DamageType, f64> {
+        [
+            DamageType::Physical,
+            DamageType::Fire,
+            DamageType::Cold,
+            DamageType::Lightning,
+            DamageType::Chaos,
+        ]
+        .into_iter()
+        .map(|dt| (dt, self.effective_health_vs(dt, hit_size)))
+        .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::combat::resolve_damage;
+    use crate::damage::{DamagePacket, FinalDamage};
+    use crate::types::{AilmentStacking, Effect};
 
     #[test]
     fn test_damage_multiplier_default() {
@@ -145,6 +426,104 @@ mod tests {
         assert!((block.life_percent() - 50.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_effective_resistance_vs_penetration() {
+        let mut block = StatBlock::new();
+        block.fire_resistance.add_flat(75.0);
+
+        let effective = block.effective_resistance_vs(DamageType::Fire, 25.0);
+        assert_eq!(effective, calculate_effective_resistance(75.0, 25.0));
+        assert!(effective < 75.0);
+
+        // Matches the damage resolution actually lets through
+        let taken = crate::defense::calculate_resistance_mitigation(100.0, 75.0, 25.0);
+        assert!((taken - 100.0 * (1.0 - effective / 100.0)).abs() < 1e-9);
+        assert_eq!(
+            block.effective_resistance_vs(DamageType::Physical, 25.0),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_resistances_snapshot_matches_individual() {
+        let mut block = StatBlock::new();
+        block.fire_resistance.add_flat(40.0);
+        block.cold_resistance.add_flat(25.0);
+        block.lightning_resistance.add_flat(90.0);
+        block.chaos_resistance.add_flat(-30.0);
+
+        let snapshot = block.resistances_snapshot();
+        assert_eq!(snapshot.fire, block.resistance(DamageType::Fire));
+        assert_eq!(snapshot.cold, block.resistance(DamageType::Cold));
+        assert_eq!(snapshot.lightning, block.resistance(DamageType::Lightning));
+        assert_eq!(snapshot.chaos, block.resistance(DamageType::Chaos));
+    }
+
+    #[test]
+    fn test_strongest_chill_sets_action_speed() {
+        crate::config::ensure_constants_initialized();
+        let ailment = |id: &str, status: StatusEffect, magnitude: f64| {
+            Effect::new_ailment(
+                id,
+                id,
+                status,
+                2.0,
+                magnitude,
+                0.0,
+                0.5,
+                AilmentStacking::Unlimited,
+                true,
+                "enemy",
+            )
+        };
+        let chill = |id: &str, magnitude: f64| ailment(id, StatusEffect::Chill, magnitude);
+        let mut block = StatBlock::new();
+        assert_eq!(block.action_speed_multiplier(), 1.0);
+
+        block.effects.push(chill("chill_1", 1.0));
+        block.effects.push(chill("chill_2", 2.0));
+        block.effects.push(chill("chill_3", 0.5));
+        // Only the 2.0 magnitude chill applies: 60% slower, not 105%
+        assert!((block.action_speed_multiplier() - 0.4).abs() < 1e-9);
+        assert!(!block.is_frozen());
+
+        block.effects.push(chill("chill_4", 10.0));
+        assert!((block.action_speed_multiplier() - 0.3).abs() < 1e-9);
+
+        block
+            .effects
+            .push(ailment("freeze", StatusEffect::Freeze, 1.0));
+        assert!(block.is_frozen());
+        assert_eq!(block.action_speed_multiplier(), 0.0);
+    }
+
+    #[test]
+    fn test_effective_health_vs_damage_types() {
+        crate::config::ensure_constants_initialized();
+        let mut block = StatBlock::new();
+        block.current_life = 800.0;
+        block.current_energy_shield = 200.0;
+        block.fire_resistance.add_flat(50.0);
+        block.armour.add_flat(1000.0);
+        block.reduced_damage_taken = 20.0;
+
+        // 1000 pool / (0.5 resisted * 0.8 reduced)
+        assert!((block.effective_health_vs(DamageType::Fire, 100.0) - 2500.0).abs() < 1e-9);
+        // Chaos skips ES by default
+        assert!((block.effective_health_vs(DamageType::Chaos, 100.0) - 1000.0).abs() < 1e-9);
+
+        // Armour does more against small hits
+        let small = block.effective_health_vs(DamageType::Physical, 50.0);
+        let big = block.effective_health_vs(DamageType::Physical, 5000.0);
+        assert!(small > big && big > 1250.0);
+        let expected = 1000.0 / (calculate_armour_reduction(1000.0, 50.0) / 50.0 * 0.8);
+        assert!((small - expected).abs() < 1e-9);
+
+        let all = block.effective_health_all(100.0);
+        assert_eq!(all.len(), 5);
+        assert!((all[&DamageType::Fire] - 2500.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_weapon_dps() {
         let mut block = StatBlock::new();
@@ -154,4 +533,80 @@ mod tests {
         // Average: 15, DPS: 15 * 1.5 = 22.5
         assert!((block.weapon_dps() - 22.5).abs() < 0.01);
     }
+
+    #[test]
+    fn test_total_defensive_pool() {
+        let mut block = StatBlock::new();
+        block.current_life = 300.0;
+        block.current_energy_shield = 100.0;
+        block.current_mana = 1000.0;
+        assert!((block.total_defensive_pool() - 400.0).abs() < 1e-9);
+
+        // 20% of each hit from mana: 500 damage leaves 400 for life and ES
+        block.damage_to_mana_percent = 20.0;
+        assert!((block.total_defensive_pool() - 500.0).abs() < 1e-9);
+
+        // Mana runs dry first, so it only adds what it holds
+        block.current_mana = 50.0;
+        assert!((block.total_defensive_pool() - 450.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_total_defensive_pool_with_ward() {
+        crate::config::ensure_constants_initialized();
+        crate::config::ensure_dot_registry_initialized();
+        let mut block = StatBlock::new();
+        block.current_life = 300.0;
+        block.current_energy_shield = 100.0;
+        block.current_ward = 50.0;
+        block.current_mana = 0.0;
+        assert!((block.total_defensive_pool() - 450.0).abs() < 1e-9);
+
+        // A hit of exactly the pool empties ward, then ES, then life
+        let mut packet = DamagePacket::new("attacker".to_string(), "test".to_string());
+        packet.damages = vec![FinalDamage::new(DamageType::Fire, 450.0)];
+        let (after, result) = resolve_damage(&block, &packet);
+        assert!((result.damage_absorbed_by_ward - 50.0).abs() < 1e-9);
+        assert!((result.damage_blocked_by_es - 100.0).abs() < 1e-9);
+        assert!(after.current_ward.abs() < 1e-9);
+        assert!(result.is_killing_blow);
+    }
+
+    #[test]
+    fn test_total_physical_reduction_caps() {
+        crate::config::ensure_constants_initialized();
+        let mut block = StatBlock::new();
+        // 500 armour vs a 100 hit: 500 / (500 + 5 * 100) = 50%
+        block.armour.base = 500.0;
+        assert!((block.total_physical_reduction(100.0) - 50.0).abs() < 1e-9);
+
+        // 50% armour then 20% flat DR: 1 - 0.5 * 0.8 = 60%
+        block.physical_damage_reduction = 20.0;
+        assert!((block.total_physical_reduction(100.0) - 60.0).abs() < 1e-9);
+
+        // Huge armour plus 90% flat DR still reports the configured cap
+        block.armour.base = 100_000.0;
+        block.physical_damage_reduction = 90.0;
+        let cap = constants().armour.max_physical_reduction;
+        assert!((block.total_physical_reduction(100.0) - cap).abs() < 1e-9);
+        assert!((cap - 90.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_damage_taken_fraction_matches_capped_hit() {
+        crate::config::ensure_constants_initialized();
+        crate::config::ensure_dot_registry_initialized();
+        let mut block = StatBlock::new();
+        block.current_life = 1000.0;
+        block.armour.base = 100_000.0;
+        block.physical_damage_reduction = 90.0;
+
+        let mut packet = DamagePacket::new("attacker".to_string(), "test".to_string());
+        packet.damages = vec![FinalDamage::new(DamageType::Physical, 100.0)];
+        let (_, result) = resolve_damage(&block, &packet);
+
+        let fraction = block.damage_taken_fraction(DamageType::Physical, 100.0, 0.0);
+        assert!((result.total_damage - 100.0 * fraction).abs() < 1e-9);
+        assert!((result.total_damage - 10.0).abs() < 1e-9);
+    }
 }
@@ -0,0 +1,50 @@
+//! CooldownTracker - Per-skill cooldown timers
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Remaining cooldown per skill ID
+///
+/// Runtime state owned by a StatBlock; skills with no entry (or an expired
+/// one) are ready to use.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CooldownTracker {
+    remaining: HashMap<String, f64>,
+}
+
+impl CooldownTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Put a skill on cooldown for `duration` seconds, replacing any existing timer
+    pub fn start(&mut self, skill_id: impl Into<String>, duration: f64) {
+        if duration > 0.0 {
+            self.remaining.insert(skill_id.into(), duration);
+        }
+    }
+
+    /// Advance all cooldowns by delta time, dropping finished ones
+    pub fn tick(&mut self, delta: f64) {
+        self.remaining.retain(|_, remaining| {
+            *remaining -= delta;
+            *remaining > 0.0
+        });
+    }
+
+    /// Seconds until a skill is ready (0 when ready)
+    pub fn remaining(&self, skill_id: &str) -> f64 {
+        self.remaining.get(skill_id).copied().unwrap_or(0.0)
+    }
+
+    /// Whether a skill is off cooldown
+    pub fn is_ready(&self, skill_id: &str) -> bool {
+        !self.remaining.contains_key(skill_id)
+    }
+
+    /// Clear all cooldowns
+    pub fn reset(&mut self) {
+        self.remaining.clear();
+    }
+}
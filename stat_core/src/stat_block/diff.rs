@@ -0,0 +1,145 @@
+//! StatBlockDiff - Computed stat changes between two StatBlocks
+
+use super::StatBlock;
+use serde::{Deserialize, Serialize};
+
+/// A single computed stat that differs between two blocks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatChange {
+    /// Stat name (e.g. `max_life`, `fire_resistance`)
+    pub stat: String,
+    pub old: f64,
+    pub new: f64,
+}
+
+impl StatChange {
+    /// Signed change from old to new
+    pub fn delta(&self) -> f64 {
+        self.new - self.old
+    }
+}
+
+/// Computed stats that differ between two StatBlocks
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatBlockDiff {
+    /// Changed stats, in a fixed order
+    pub changes: Vec<StatChange>,
+}
+
+impl StatBlockDiff {
+    /// Whether no computed stat changed
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Look up the change for a stat by name
+    pub fn get(&self, stat: &str) -> Option<&StatChange> {
+        self.changes.iter().find(|c| c.stat == stat)
+    }
+}
+
+/// Changes smaller than this are treated as float noise
+const EPSILON: f64 = 1e-9;
+
+impl StatBlock {
+    /// Compare computed stats against another block
+    ///
+    /// `self` is treated as the old state and `other` as the new one.
+    /// Only final (computed) values are compared, so two blocks reaching
+    /// the same value through different modifiers produce no change.
+    pub fn diff(&self, other: &StatBlock) -> StatBlockDiff {
+        let changes = self
+            .computed_stats()
+            .into_iter()
+            .zip(other.computed_stats())
+            .filter(|((_, old), (_, new))| (new - old).abs() > EPSILON)
+            .map(|((stat, old), (_, new))| StatChange {
+                stat: stat.to_string(),
+                old,
+                new,
+            })
+            .collect();
+        StatBlockDiff { changes }
+    }
+
+    /// Named snapshot of the computed stats compared by `diff`
+    fn computed_stats(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("max_life", self.computed_max_life()),
+            ("max_mana", self.computed_max_mana()),
+            ("max_energy_shield", self.max_energy_shield),
+            ("strength", self.strength.compute()),
+            ("dexterity", self.dexterity.compute()),
+            ("intelligence", self.intelligence.compute()),
+            ("constitution", self.constitution.compute()),
+            ("wisdom", self.wisdom.compute()),
+            ("charisma", self.charisma.compute()),
+            ("armour", self.armour.compute()),
+            ("evasion", self.evasion.compute()),
+            ("fire_resistance", self.fire_resistance.compute()),
+            ("cold_resistance", self.cold_resistance.compute()),
+            ("lightning_resistance", self.lightning_resistance.compute()),
+            ("chaos_resistance", self.chaos_resistance.compute()),
+            ("accuracy", self.accuracy.compute()),
+            (
+                "global_physical_damage",
+                self.global_physical_damage.compute(),
+            ),
+            ("global_fire_damage", self.global_fire_damage.compute()),
+            ("global_cold_damage", self.global_cold_damage.compute()),
+            (
+                "global_lightning_damage",
+                self.global_lightning_damage.compute(),
+            ),
+            ("global_chaos_damage", self.global_chaos_damage.compute()),
+            ("attack_speed", self.computed_attack_speed()),
+            ("cast_speed", self.computed_cast_speed()),
+            ("critical_chance", self.computed_attack_crit_chance()),
+            ("critical_multiplier", self.computed_crit_multiplier()),
+            ("fire_penetration", self.fire_penetration.compute()),
+            ("cold_penetration", self.cold_penetration.compute()),
+            (
+                "lightning_penetration",
+                self.lightning_penetration.compute(),
+            ),
+            ("chaos_penetration", self.chaos_penetration.compute()),
+            ("physical_penetration", self.physical_penetration.compute()),
+            ("life_regen", self.life_regen.compute()),
+            ("mana_regen", self.mana_regen.compute()),
+            ("life_leech", self.life_leech.compute()),
+            ("mana_leech", self.mana_leech.compute()),
+            ("block_chance", self.computed_block_chance()),
+            ("block_amount", self.computed_block_amount()),
+            ("spell_dodge_chance", self.computed_spell_dodge_chance()),
+            ("movement_speed_increased", self.movement_speed_increased),
+            ("reduced_damage_taken", self.reduced_damage_taken),
+            ("dot_damage_taken", self.dot_damage_taken),
+            ("physical_damage_reduction", self.physical_damage_reduction),
+            ("weapon_dps", self.weapon_dps()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_reports_changed_stats() {
+        let old = StatBlock::new();
+        let mut new = old.clone();
+        assert!(old.diff(&new).is_empty());
+
+        new.max_life.add_flat(500.0);
+        new.fire_resistance.add_flat(40.0);
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.changes.len(), 2);
+        let life = diff.get("max_life").unwrap();
+        assert!((life.old - 50.0).abs() < 1e-9);
+        assert!((life.delta() - 500.0).abs() < 1e-9);
+        let fire = diff.get("fire_resistance").unwrap();
+        assert!((fire.new - 40.0).abs() < 1e-9);
+        assert!(diff.get("cold_resistance").is_none());
+    }
+}
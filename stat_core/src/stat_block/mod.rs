@@ -2,17 +2,30 @@
 
 mod aggregator;
 mod computed;
+mod cooldowns;
+mod diff;
 mod stat_value;
 
 pub use aggregator::{PendingScaledModifier, StatAccumulator, StatusConversions, StatusEffectStats};
+pub use computed::Resistances;
+pub use cooldowns::CooldownTracker;
+pub use diff::{StatBlockDiff, StatChange};
 pub use stat_value::StatValue;
 
-use crate::combat::{resolve_damage, CombatResult};
-use crate::damage::{calculate_damage, DamagePacket, DamagePacketGenerator};
-use crate::source::{BuffSource, GearSource, StatSource};
-use crate::types::{AilmentStacking, Effect, EffectType, EquipmentSlot, TickResult};
-use loot_core::types::{Attribute, DamageType, StatusEffect};
+use crate::combat::{record, resolve_damage, CombatResult};
+use crate::config::constants;
+use crate::damage::{
+    calculate_damage, calculate_damage_range, calculate_hits_to_kill, calculate_skill_dps,
+    scaled_status_dot_dps, DamagePacket, DamagePacketGenerator, DamageRange,
+};
+use crate::source::{BuffSource, GearSource, StatSource, TemplateSource};
+use crate::types::{
+    AilmentStacking, ConditionState, Effect, EffectType, EquipmentSlot, ReflectBasis, Reservation,
+    TickResult,
+};
+use loot_core::types::{Attribute, DamageType, StatType, StatusEffect};
 use loot_core::Item;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
@@ -34,6 +47,11 @@ pub struct StatBlock {
     #[serde(skip)]
     buff_sources: Vec<BuffSource>,
 
+    // === Template ===
+    /// Base stats from a template (e.g. an enemy), applied on every rebuild
+    #[serde(default)]
+    template: Option<TemplateSource>,
+
     // === Resources ===
     pub max_life: StatValue,
     pub current_life: f64,
@@ -42,6 +60,10 @@ pub struct StatBlock {
     /// Maximum energy shield from warding spells (does NOT passively regenerate)
     pub max_energy_shield: f64,
     pub current_energy_shield: f64,
+    /// Ward absorbs hit damage before mana, energy shield and life. It is a
+    /// pool set by the caller and is not restored by rebuilds or recharge
+    #[serde(default)]
+    pub current_ward: f64,
 
     // === Attributes ===
     pub strength: StatValue,
@@ -72,6 +94,9 @@ pub struct StatBlock {
     pub cast_speed: StatValue,
     pub critical_chance: StatValue,
     pub critical_multiplier: StatValue,
+    /// Extra crit multiplier for skills dealing each damage type
+    #[serde(default)]
+    pub crit_multiplier_by_type: HashMap<DamageType, f64>,
 
     // === Penetration ===
     pub fire_penetration: StatValue,
@@ -80,10 +105,25 @@ pub struct StatBlock {
     pub chaos_penetration: StatValue,
 
     // === Recovery ===
+    /// Life regenerated per second (flat, scaled by increased life recovery rate)
     pub life_regen: StatValue,
+    /// Mana regenerated per second (flat, scaled by increased mana recovery rate)
     pub mana_regen: StatValue,
+    /// Increased energy shield recharge rate (as decimal)
+    #[serde(default)]
+    pub es_recharge_increased: f64,
     pub life_leech: StatValue,
     pub mana_leech: StatValue,
+    /// Flat life gained for each hit that connects
+    #[serde(default)]
+    pub life_on_hit: f64,
+    /// Life leeched within the last second, drained at the max leech rate by
+    /// `tick_recovery`. Runtime state; preserved across rebuilds.
+    #[serde(default)]
+    pub life_leeched_recently: f64,
+    /// Mana leeched within the last second (see `life_leeched_recently`)
+    #[serde(default)]
+    pub mana_leeched_recently: f64,
 
     // === Utility ===
     pub movement_speed_increased: f64,
@@ -93,6 +133,9 @@ pub struct StatBlock {
     // === Block ===
     pub block_chance: StatValue,
     pub block_amount: StatValue,
+    /// Flat damage absorbed from every hit, independent of the block roll
+    #[serde(default)]
+    pub flat_block_absorb: f64,
 
     // === Dodge ===
     pub spell_dodge_chance: f64,
@@ -112,21 +155,117 @@ pub struct StatBlock {
     // === Global DoT ===
     pub dot_multiplier: f64,
 
+    // === Movement ===
+    /// Whether the entity is currently moving. Runtime state set by the game
+    /// layer; preserved across rebuilds.
+    #[serde(default)]
+    pub is_moving: bool,
+    /// More damage multipliers that apply while not moving
+    #[serde(default)]
+    pub damage_while_stationary: StatValue,
+    /// More damage multipliers that apply while moving
+    #[serde(default)]
+    pub damage_while_moving: StatValue,
+
+    // === Delivery Tags ===
+    /// More damage multipliers for Trap-tagged skills
+    #[serde(default)]
+    pub trap_damage: StatValue,
+    /// More damage multipliers for Mine-tagged skills
+    #[serde(default)]
+    pub mine_damage: StatValue,
+    /// More damage multipliers for Totem-tagged skills
+    #[serde(default)]
+    pub totem_damage: StatValue,
+
     // === Defensive ===
     pub reduced_damage_taken: f64,
+    /// Multiplier on damage taken from damaging ailment ticks (1.0 = normal)
+    #[serde(default = "default_dot_damage_taken")]
+    pub dot_damage_taken: f64,
     pub physical_damage_reduction: f64,
     pub physical_penetration: StatValue,
     pub culling_strike: f64,
+    /// Percent of post-mitigation damage taken from mana before ES/life
+    #[serde(default)]
+    pub damage_to_mana_percent: f64,
+    /// Increased stun threshold (as decimal)
+    #[serde(default)]
+    pub stun_threshold_increased: f64,
+    /// Percent of physical hit damage reflected back at the attacker
+    #[serde(default)]
+    pub reflect_physical_percent: f64,
+    /// Percent of fire, cold and lightning hit damage reflected back at the attacker
+    #[serde(default)]
+    pub reflect_elemental_percent: f64,
+    /// Whether reflect uses raw or landed hit damage. Preserved across rebuilds.
+    #[serde(default)]
+    pub reflect_basis: ReflectBasis,
+    /// Increased effect of buff effects applied to this entity (as decimal).
+    /// Scales non-debuff stat modifier effects during rebuild.
+    #[serde(default)]
+    pub buff_effect_received: f64,
+    /// Increased effect of consumables used by this entity (as decimal).
+    /// Scales consumable buff effects during rebuild, on top of buff effect.
+    #[serde(default)]
+    pub consumable_effect: f64,
+    /// Whether hits can never stun this entity. Preserved across rebuilds.
+    #[serde(default)]
+    pub stun_immune: bool,
+    /// Percent chance to avoid each status effect. Preserved across rebuilds.
+    #[serde(default)]
+    pub ailment_avoid_chance: HashMap<StatusEffect, f64>,
+    /// Percent chance to avoid every status effect, added to the per-status
+    /// chance. Preserved across rebuilds.
+    #[serde(default)]
+    pub all_ailment_avoid: f64,
+    /// Debuffs (curses, exposure) can't lower resistances below 0.
+    /// Resistance already negative from other sources is unaffected.
+    /// Preserved across rebuilds.
+    #[serde(default)]
+    pub resistance_reduction_floor: bool,
+    /// Flat resistance adjustments pushed by external systems (e.g. -25 fire
+    /// from a debuff), applied on top of computed resistance when resolving
+    /// hits. Preserved across rebuilds.
+    #[serde(default)]
+    pub resistance_modifiers: HashMap<DamageType, f64>,
+    /// Whether chaos damage skips energy shield and hits life directly.
+    /// Preserved across rebuilds so per-enemy overrides stick.
+    #[serde(default = "default_chaos_bypasses_es")]
+    pub chaos_bypasses_es: bool,
 
     // === On-Kill Recovery ===
     pub life_on_kill: f64,
     pub mana_on_kill: f64,
 
+    // === Attribute Damage Scaling ===
+    /// Character-level "X% increased <damage type> damage per 10 <attribute>"
+    /// entries, resolved during rebuild once attributes are finalized.
+    /// Preserved across rebuilds.
+    #[serde(default)]
+    pub damage_per_attribute: Vec<(Attribute, DamageType, f64)>,
+    /// Whether converted damage also counts as its origin type for increased
+    /// damage scaling. Preserved across rebuilds.
+    #[serde(default)]
+    pub converted_damage_double_scaling: bool,
+
     // === Active Effects ===
     /// All active effects (buffs, debuffs, ailments)
     #[serde(default)]
     pub effects: Vec<Effect>,
 
+    // === On-Kill Triggers ===
+    /// Effects this entity gains when it lands a killing blow
+    /// (e.g. "on kill, gain 20% increased damage for 4s").
+    /// Preserved across rebuilds; see `on_kill_effects_for`.
+    #[serde(default)]
+    pub on_kill_effects: Vec<Effect>,
+
+    // === Skill Cooldowns ===
+    /// Remaining cooldown per skill. Runtime state; preserved across rebuilds.
+    #[serde(default)]
+    pub cooldowns: CooldownTracker,
+
     // === Weapon Stats (from equipped weapon) ===
     pub weapon_physical_min: f64,
     pub weapon_physical_max: f64,
@@ -152,6 +291,36 @@ pub struct StatBlock {
     pub status_buildup: HashMap<StatusEffect, f64>,
 }
 
+fn default_chaos_bypasses_es() -> bool {
+    true
+}
+
+fn default_dot_damage_taken() -> f64 {
+    1.0
+}
+
+/// Fire, cold, lightning and chaos resistance totals in an accumulator
+fn resistance_totals(accumulator: &StatAccumulator) -> [f64; 4] {
+    [
+        accumulator.fire_resistance + accumulator.all_resistances,
+        accumulator.cold_resistance + accumulator.all_resistances,
+        accumulator.lightning_resistance + accumulator.all_resistances,
+        accumulator.chaos_resistance,
+    ]
+}
+
+/// Current over max for a resource, or None if it has no maximum
+fn resource_ratio(current: f64, max: f64) -> Option<f64> {
+    (max > 0.0).then(|| (current / max).clamp(0.0, 1.0))
+}
+
+/// Life and mana actually restored by `StatBlock::apply_leech`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LeechRecovery {
+    pub life: f64,
+    pub mana: f64,
+}
+
 /// Holds all status effect related stats (HashMap-based for extensibility)
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StatusEffectData {
@@ -165,6 +334,9 @@ pub struct StatusEffectData {
     /// Increased status damage on critical strike
     #[serde(default)]
     pub status_damage_on_crit_increased: f64,
+    /// More DoT damage for ailments applied by critical strikes
+    #[serde(default)]
+    pub crit_ailment_dot_more: f64,
 }
 
 impl StatusEffectData {
@@ -225,6 +397,124 @@ impl StatusEffectData {
     }
 }
 
+/// State a rebuild must carry over: identity, stat sources, current
+/// resources and flags set by the caller rather than derived from sources
+///
+/// `take` and `restore` list every field without `..`, so a field added here
+/// can't be forgotten on either side.
+struct RuntimeState {
+    id: String,
+    equipped_items: HashMap<EquipmentSlot, Item>,
+    buff_sources: Vec<BuffSource>,
+    template: Option<TemplateSource>,
+    damage_per_attribute: Vec<(Attribute, DamageType, f64)>,
+    effects: Vec<Effect>,
+    on_kill_effects: Vec<Effect>,
+    cooldowns: CooldownTracker,
+    status_buildup: HashMap<StatusEffect, f64>,
+    current_life: f64,
+    current_mana: f64,
+    current_energy_shield: f64,
+    current_ward: f64,
+    life_leeched_recently: f64,
+    mana_leeched_recently: f64,
+    chaos_bypasses_es: bool,
+    converted_damage_double_scaling: bool,
+    reflect_basis: ReflectBasis,
+    is_moving: bool,
+    stun_immune: bool,
+    ailment_avoid_chance: HashMap<StatusEffect, f64>,
+    all_ailment_avoid: f64,
+    resistance_reduction_floor: bool,
+    resistance_modifiers: HashMap<DamageType, f64>,
+}
+
+impl RuntimeState {
+    /// Move the runtime state out of `block`
+    fn take(block: &mut StatBlock) -> Self {
+        RuntimeState {
+            id: std::mem::take(&mut block.id),
+            equipped_items: std::mem::take(&mut block.equipped_items),
+            buff_sources: std::mem::take(&mut block.buff_sources),
+            template: block.template.take(),
+            damage_per_attribute: std::mem::take(&mut block.damage_per_attribute),
+            effects: std::mem::take(&mut block.effects),
+            on_kill_effects: std::mem::take(&mut block.on_kill_effects),
+            cooldowns: std::mem::take(&mut block.cooldowns),
+            status_buildup: std::mem::take(&mut block.status_buildup),
+            current_life: block.current_life,
+            current_mana: block.current_mana,
+            current_energy_shield: block.current_energy_shield,
+            current_ward: block.current_ward,
+            life_leeched_recently: block.life_leeched_recently,
+            mana_leeched_recently: block.mana_leeched_recently,
+            chaos_bypasses_es: block.chaos_bypasses_es,
+            converted_damage_double_scaling: block.converted_damage_double_scaling,
+            reflect_basis: block.reflect_basis,
+            is_moving: block.is_moving,
+            stun_immune: block.stun_immune,
+            ailment_avoid_chance: std::mem::take(&mut block.ailment_avoid_chance),
+            all_ailment_avoid: block.all_ailment_avoid,
+            resistance_reduction_floor: block.resistance_reduction_floor,
+            resistance_modifiers: std::mem::take(&mut block.resistance_modifiers),
+        }
+    }
+
+    /// Move the runtime state back into `block`
+    fn restore(self, block: &mut StatBlock) {
+        let RuntimeState {
+            id,
+            equipped_items,
+            buff_sources,
+            template,
+            damage_per_attribute,
+            effects,
+            on_kill_effects,
+            cooldowns,
+            status_buildup,
+            current_life,
+            current_mana,
+            current_energy_shield,
+            current_ward,
+            life_leeched_recently,
+            mana_leeched_recently,
+            chaos_bypasses_es,
+            converted_damage_double_scaling,
+            reflect_basis,
+            is_moving,
+            stun_immune,
+            ailment_avoid_chance,
+            all_ailment_avoid,
+            resistance_reduction_floor,
+            resistance_modifiers,
+        } = self;
+        block.id = id;
+        block.equipped_items = equipped_items;
+        block.buff_sources = buff_sources;
+        block.template = template;
+        block.damage_per_attribute = damage_per_attribute;
+        block.effects = effects;
+        block.on_kill_effects = on_kill_effects;
+        block.cooldowns = cooldowns;
+        block.status_buildup = status_buildup;
+        block.current_life = current_life;
+        block.current_mana = current_mana;
+        block.current_energy_shield = current_energy_shield;
+        block.current_ward = current_ward;
+        block.life_leeched_recently = life_leeched_recently;
+        block.mana_leeched_recently = mana_leeched_recently;
+        block.chaos_bypasses_es = chaos_bypasses_es;
+        block.converted_damage_double_scaling = converted_damage_double_scaling;
+        block.reflect_basis = reflect_basis;
+        block.is_moving = is_moving;
+        block.stun_immune = stun_immune;
+        block.ailment_avoid_chance = ailment_avoid_chance;
+        block.all_ailment_avoid = all_ailment_avoid;
+        block.resistance_reduction_floor = resistance_reduction_floor;
+        block.resistance_modifiers = resistance_modifiers;
+    }
+}
+
 impl Default for StatBlock {
     fn default() -> Self {
         Self::new()
@@ -249,6 +539,9 @@ impl StatBlock {
             // Buff sources
             buff_sources: Vec::new(),
 
+            // Template
+            template: None,
+
             // Resources
             max_life: StatValue::with_base(50.0),
             current_life: 50.0,
@@ -256,6 +549,7 @@ impl StatBlock {
             current_mana: 40.0,
             max_energy_shield: 0.0,
             current_energy_shield: 0.0,
+            current_ward: 0.0,
 
             // Attributes
             strength: StatValue::with_base(10.0),
@@ -284,6 +578,7 @@ impl StatBlock {
             cast_speed: StatValue::with_base(1.0),
             critical_chance: StatValue::default(),
             critical_multiplier: StatValue::with_base(1.5), // 150% base crit multiplier
+            crit_multiplier_by_type: HashMap::new(),
 
             // Penetration
             fire_penetration: StatValue::default(),
@@ -294,8 +589,12 @@ impl StatBlock {
             // Recovery
             life_regen: StatValue::default(),
             mana_regen: StatValue::default(),
+            es_recharge_increased: 0.0,
             life_leech: StatValue::default(),
             mana_leech: StatValue::default(),
+            life_on_hit: 0.0,
+            life_leeched_recently: 0.0,
+            mana_leeched_recently: 0.0,
 
             // Utility
             movement_speed_increased: 0.0,
@@ -305,6 +604,7 @@ impl StatBlock {
             // Block
             block_chance: StatValue::default(),
             block_amount: StatValue::default(),
+            flat_block_absorb: 0.0,
 
             // Dodge
             spell_dodge_chance: 0.0,
@@ -323,20 +623,50 @@ impl StatBlock {
 
             // Global DoT
             dot_multiplier: 0.0,
+            is_moving: false,
+            damage_while_stationary: StatValue::default(),
+            damage_while_moving: StatValue::default(),
+            trap_damage: StatValue::default(),
+            mine_damage: StatValue::default(),
+            totem_damage: StatValue::default(),
 
             // Defensive
             reduced_damage_taken: 0.0,
+            dot_damage_taken: 1.0,
             physical_damage_reduction: 0.0,
             physical_penetration: StatValue::default(),
             culling_strike: 0.0,
+            damage_to_mana_percent: 0.0,
+            stun_threshold_increased: 0.0,
+            reflect_physical_percent: 0.0,
+            reflect_elemental_percent: 0.0,
+            reflect_basis: ReflectBasis::default(),
+            buff_effect_received: 0.0,
+            consumable_effect: 0.0,
+            stun_immune: false,
+            ailment_avoid_chance: HashMap::new(),
+            all_ailment_avoid: 0.0,
+            resistance_reduction_floor: false,
+            resistance_modifiers: HashMap::new(),
+            chaos_bypasses_es: true,
 
             // On-kill recovery
             life_on_kill: 0.0,
             mana_on_kill: 0.0,
 
+            // Attribute damage scaling
+            damage_per_attribute: Vec::new(),
+            converted_damage_double_scaling: false,
+
             // Active effects
             effects: Vec::new(),
 
+            // On-kill triggers
+            on_kill_effects: Vec::new(),
+
+            // Skill cooldowns
+            cooldowns: CooldownTracker::new(),
+
             // Weapon stats
             weapon_physical_min: 0.0,
             weapon_physical_max: 0.0,
@@ -359,21 +689,48 @@ impl StatBlock {
         }
     }
 
+    /// Create a StatBlock wearing the given items, at full life, mana and ES
+    ///
+    /// Equips everything and rebuilds once, instead of once per `equip`.
+    /// A later item in the same slot replaces an earlier one.
+    pub fn with_equipment(id: impl Into<String>, items: Vec<(EquipmentSlot, Item)>) -> Self {
+        let mut block = Self::with_id(id);
+        block.equipped_items.extend(items);
+        block.rebuild();
+        block.current_life = block.computed_max_life();
+        block.current_mana = block.computed_max_mana();
+        block.current_energy_shield = block.max_energy_shield;
+        block
+    }
+
+    /// Reset all aggregated stats to base values, preserving identity,
+    /// equipment, buffs, active effects, and current resources
+    fn reset_to_base(&mut self) {
+        let runtime = RuntimeState::take(self);
+        *self = StatBlock::with_id(String::new());
+        runtime.restore(self);
+    }
+
     /// Rebuild stats from all sources (external API for custom sources)
+    ///
+    /// Stat modifier effects in `effects` are applied on top of the sources.
     pub fn rebuild_from_sources(&mut self, sources: &[Box<dyn StatSource>]) {
-        // Preserve identity and equipment
-        let id = std::mem::take(&mut self.id);
-        let equipped_items = std::mem::take(&mut self.equipped_items);
-        let buff_sources = std::mem::take(&mut self.buff_sources);
+        record(|s| s.rebuilds += 1);
+
+        // Capture condition state before maxes are reset
+        let conditions = self.condition_state();
 
-        // Reset to base values
-        *self = StatBlock::with_id(id);
-        self.equipped_items = equipped_items;
-        self.buff_sources = buff_sources;
+        // Reset to base values, preserving identity and equipment
+        self.reset_to_base();
 
         // Create accumulator and apply all sources
         let mut accumulator = StatAccumulator::new();
 
+        // The template is part of this block's base
+        if let Some(template) = &self.template {
+            template.apply(&mut accumulator);
+        }
+
         // Sort sources by priority
         let mut sorted_sources: Vec<_> = sources.iter().collect();
         sorted_sources.sort_by_key(|s| s.priority());
@@ -382,30 +739,62 @@ impl StatBlock {
             source.apply(&mut accumulator);
         }
 
+        // Apply stat modifier effects on top of the sources above
+        self.apply_effect_modifiers(&mut accumulator, &conditions);
+
         // Apply accumulated stats to self
         accumulator.apply_to(self);
 
-        // Update current values to max if they exceed
-        self.current_life = self.current_life.min(self.max_life.compute());
-        self.current_mana = self.current_mana.min(self.max_mana.compute());
-        self.current_energy_shield = self.current_energy_shield.min(self.max_energy_shield);
+        self.clamp_current_resources();
+    }
+
+    /// Recompute all stats from equipment, buffs and effects
+    ///
+    /// The public entry point for a full recomputation, e.g. after adding
+    /// effects directly or editing base stats. Derived stats are cleared and
+    /// rebuilt from equipped items, buffs and stat modifier effects, and
+    /// current life, mana and ES keep their ratio to the new maxes.
+    ///
+    /// This is the only rebuild that keeps ratios, so it can raise current
+    /// values when maxes grow. The rebuilds run by `equip`, `unequip`,
+    /// buffs, effect changes, `rebuild_from_sources` and
+    /// `finalize_after_load` only clamp (see `clamp_current_resources`).
+    pub fn rebuild(&mut self) {
+        let life = resource_ratio(self.current_life, self.computed_max_life());
+        let mana = resource_ratio(self.current_mana, self.computed_max_mana());
+        let energy_shield = resource_ratio(self.current_energy_shield, self.max_energy_shield);
+
+        self.rebuild_stats();
+
+        if let Some(ratio) = life {
+            self.current_life = ratio * self.computed_max_life();
+        }
+        if let Some(ratio) = mana {
+            self.current_mana = ratio * self.computed_max_mana();
+        }
+        if let Some(ratio) = energy_shield {
+            self.current_energy_shield = ratio * self.max_energy_shield;
+        }
     }
 
-    /// Rebuild stats from internal equipment and buffs
-    fn rebuild(&mut self) {
-        // Preserve identity and internal state
-        let id = std::mem::take(&mut self.id);
-        let equipped_items = std::mem::take(&mut self.equipped_items);
-        let buff_sources = std::mem::take(&mut self.buff_sources);
+    /// Rebuild stats from internal equipment, buffs, and stat modifier effects
+    fn rebuild_stats(&mut self) {
+        record(|s| s.rebuilds += 1);
+
+        // Capture condition state before maxes are reset
+        let conditions = self.condition_state();
 
-        // Reset to base values
-        *self = StatBlock::with_id(id);
-        self.equipped_items = equipped_items;
-        self.buff_sources = buff_sources;
+        // Reset to base values, preserving identity and internal state
+        self.reset_to_base();
 
         // Create accumulator
         let mut accumulator = StatAccumulator::new();
 
+        // Apply template base stats
+        if let Some(template) = &self.template {
+            template.apply(&mut accumulator);
+        }
+
         // Apply gear sources
         for (slot, item) in &self.equipped_items {
             let gear_source = GearSource::new(*slot, item.clone());
@@ -417,13 +806,93 @@ impl StatBlock {
             buff.apply(&mut accumulator);
         }
 
+        // Apply stat modifier effects on top of the sources above
+        self.apply_effect_modifiers(&mut accumulator, &conditions);
+
         // Apply accumulated stats to self
         accumulator.apply_to(self);
 
-        // Update current values to max if they exceed
-        self.current_life = self.current_life.min(self.max_life.compute());
-        self.current_mana = self.current_mana.min(self.max_mana.compute());
-        self.current_energy_shield = self.current_energy_shield.min(self.max_energy_shield);
+        self.clamp_current_resources();
+    }
+
+    /// Apply stat modifier effects to an accumulator during rebuild
+    ///
+    /// Buffs are scaled by this block's increased effect of buffs received,
+    /// taken from the sources already in the accumulator, and consumable
+    /// buffs additionally by its increased consumable effect. With
+    /// `resistance_reduction_floor`, debuffs can lower each resistance to 0
+    /// but no further. Conditional modifiers are checked against
+    /// `conditions`.
+    fn apply_effect_modifiers(
+        &self,
+        accumulator: &mut StatAccumulator,
+        conditions: &ConditionState,
+    ) {
+        let buff_effect = 1.0 + accumulator.buff_effect_received;
+        let consumable_effect = buff_effect * (1.0 + accumulator.consumable_effect);
+        let (debuffs, buffs): (Vec<&Effect>, Vec<&Effect>) =
+            self.effects.iter().partition(|effect| effect.is_debuff());
+        for effect in buffs {
+            if effect.is_consumable {
+                effect.apply_modifiers_scaled(accumulator, consumable_effect, conditions);
+            } else {
+                effect.apply_modifiers_scaled(accumulator, buff_effect, conditions);
+            }
+        }
+
+        let before = resistance_totals(accumulator);
+        for effect in debuffs {
+            effect.apply_modifiers(accumulator, conditions);
+        }
+        if self.resistance_reduction_floor {
+            let after = resistance_totals(accumulator);
+            let shortfall = |i: usize| (before[i].min(0.0) - after[i]).max(0.0);
+            accumulator.fire_resistance += shortfall(0);
+            accumulator.cold_resistance += shortfall(1);
+            accumulator.lightning_resistance += shortfall(2);
+            accumulator.chaos_resistance += shortfall(3);
+        }
+    }
+
+    /// Current state for evaluating conditional stat modifiers
+    fn condition_state(&self) -> ConditionState {
+        ConditionState {
+            full_energy_shield: self.max_energy_shield > 0.0
+                && self.current_energy_shield >= self.max_energy_shield,
+            full_life: self.current_life >= self.computed_max_life(),
+            life_percent: self.life_percent(),
+            active_effects: self
+                .effects
+                .iter()
+                .filter(|e| e.is_active())
+                .map(|e| e.id.clone())
+                .collect(),
+        }
+    }
+
+    /// Finalize a StatBlock after deserialization (required post-load step)
+    ///
+    /// Saved blocks carry both aggregated maxes and current resource values.
+    /// The maxes are recomputed from equipped items so balance changes take
+    /// effect, while current life/mana/ES are restored from the save and
+    /// clamped to the new maxes. Active effects and status buildup are kept.
+    pub fn finalize_after_load(&mut self) {
+        self.rebuild_stats();
+    }
+
+    /// Clamp current life, mana and ES to their maxes
+    ///
+    /// Called at the end of the rebuilds run by equipment, buffs, effects,
+    /// `rebuild_from_sources` and `finalize_after_load`. These never raise
+    /// current values, so gaining max life leaves current life as it was,
+    /// while losing it (e.g. a buff expiring) pulls current life down to the
+    /// new max. The public `rebuild()` keeps resource ratios instead.
+    pub fn clamp_current_resources(&mut self) {
+        self.current_life = self.current_life.clamp(0.0, self.computed_max_life());
+        self.current_mana = self.current_mana.clamp(0.0, self.computed_max_mana());
+        self.current_energy_shield = self
+            .current_energy_shield
+            .clamp(0.0, self.max_energy_shield);
     }
 
     /// Check if the entity is alive
@@ -453,6 +922,47 @@ impl StatBlock {
         }
     }
 
+    /// Get the global damage stat for a damage type
+    pub fn global_damage(&self, damage_type: DamageType) -> &StatValue {
+        match damage_type {
+            DamageType::Physical => &self.global_physical_damage,
+            DamageType::Fire => &self.global_fire_damage,
+            DamageType::Cold => &self.global_cold_damage,
+            DamageType::Lightning => &self.global_lightning_damage,
+            DamageType::Chaos => &self.global_chaos_damage,
+        }
+    }
+
+    /// Total flat added damage of a type, before increased/more scaling
+    ///
+    /// This is the flat component `calculate_damage` adds to skills
+    /// (e.g. "Adds 20 Fire Damage" from global gear affixes).
+    pub fn added_flat_damage(&self, damage_type: DamageType) -> f64 {
+        self.global_damage(damage_type).flat
+    }
+
+    /// Total increased and more multipliers for a damage type, as `(increased, more)`
+    ///
+    /// Both are multipliers (e.g. 50% increased is 1.5), for damage breakdown tooltips.
+    pub fn damage_multipliers(&self, damage_type: DamageType) -> (f64, f64) {
+        let stat = self.global_damage(damage_type);
+        (
+            stat.total_increased_multiplier(),
+            stat.total_more_multiplier(),
+        )
+    }
+
+    /// Get the global damage stat for a damage type (mutable)
+    pub fn global_damage_mut(&mut self, damage_type: DamageType) -> &mut StatValue {
+        match damage_type {
+            DamageType::Physical => &mut self.global_physical_damage,
+            DamageType::Fire => &mut self.global_fire_damage,
+            DamageType::Cold => &mut self.global_cold_damage,
+            DamageType::Lightning => &mut self.global_lightning_damage,
+            DamageType::Chaos => &mut self.global_chaos_damage,
+        }
+    }
+
     /// Heal life by amount, capped at max
     pub fn heal(&mut self, amount: f64) {
         let max = self.computed_max_life();
@@ -471,6 +981,86 @@ impl StatBlock {
             (self.current_energy_shield + amount).min(self.max_energy_shield);
     }
 
+    /// Recharge energy shield for one tick
+    ///
+    /// `time_since_hit` is the time since the last hit that reset recharge
+    /// (see `CombatResult::resets_es_recharge`), including this tick. ES
+    /// recharges at `recharge_rate` of its max per second (scaled by
+    /// `es_recharge_increased`) for the part of the tick past
+    /// `recharge_delay`, stopping when full.
+    pub fn tick_energy_shield(&mut self, delta: f64, time_since_hit: f64) {
+        let es = &constants().energy_shield;
+        let recharge_time = delta.min(time_since_hit - es.recharge_delay);
+        if recharge_time <= 0.0 || self.current_energy_shield >= self.max_energy_shield {
+            return;
+        }
+        let rate = es.recharge_rate * (1.0 + self.es_recharge_increased);
+        self.apply_energy_shield(self.max_energy_shield * rate * recharge_time);
+    }
+
+    /// Regenerate life and mana for one tick
+    ///
+    /// Restores `life_regen` and `mana_regen` per second, which include
+    /// increased recovery rate. Dead entities don't regenerate.
+    pub fn tick_recovery(&mut self, delta: f64) {
+        if !self.is_alive() || delta <= 0.0 {
+            return;
+        }
+        self.heal(self.life_regen.compute() * delta);
+        self.restore_mana(self.mana_regen.compute() * delta);
+
+        // Leech budget refills at the max leech rate
+        let leech = &constants().leech;
+        self.life_leeched_recently = (self.life_leeched_recently
+            - leech.max_life_leech_rate * self.computed_max_life() * delta)
+            .max(0.0);
+        self.mana_leeched_recently = (self.mana_leeched_recently
+            - leech.max_mana_leech_rate * self.computed_max_mana() * delta)
+            .max(0.0);
+    }
+
+    /// Recover life and mana from a hit this entity dealt
+    ///
+    /// Leech restores `life_leech`/`mana_leech` of the hit's total damage.
+    /// Leech across all hits is capped at one second's worth of the
+    /// configured max leech rate; `tick_recovery` frees that budget back up
+    /// at the same rate. Life on hit is added for any hit that wasn't dodged, and on-kill
+    /// recovery from the result is added on killing blows. Returns what was
+    /// actually restored after capping at max life/mana.
+    pub fn apply_leech(&mut self, result: &CombatResult) -> LeechRecovery {
+        let leech = &constants().leech;
+        let life_budget =
+            leech.max_life_leech_rate * self.computed_max_life() - self.life_leeched_recently;
+        let mana_budget =
+            leech.max_mana_leech_rate * self.computed_max_mana() - self.mana_leeched_recently;
+        let life_leeched = (self.life_leech.compute() * result.total_damage)
+            .min(life_budget)
+            .max(0.0);
+        let mana_leeched = (self.mana_leech.compute() * result.total_damage)
+            .min(mana_budget)
+            .max(0.0);
+        self.life_leeched_recently += life_leeched;
+        self.mana_leeched_recently += mana_leeched;
+
+        let mut life = life_leeched;
+        let mut mana = mana_leeched;
+        if !result.was_dodged {
+            life += self.life_on_hit;
+        }
+        if result.is_killing_blow {
+            life += result.life_gained_on_kill;
+            mana += result.mana_gained_on_kill;
+        }
+
+        let (life_before, mana_before) = (self.current_life, self.current_mana);
+        self.heal(life.max(0.0));
+        self.restore_mana(mana.max(0.0));
+        LeechRecovery {
+            life: self.current_life - life_before,
+            mana: self.current_mana - mana_before,
+        }
+    }
+
     /// Set maximum energy shield capacity
     pub fn set_max_energy_shield(&mut self, amount: f64) {
         self.max_energy_shield = amount;
@@ -482,18 +1072,35 @@ impl StatBlock {
     /// Equip an item to a slot, automatically rebuilding stats
     pub fn equip(&mut self, slot: EquipmentSlot, item: Item) {
         self.equipped_items.insert(slot, item);
-        self.rebuild();
+        self.rebuild_stats();
     }
 
     /// Unequip an item from a slot, returning it if present
+    ///
+    /// Rebuilds stats from the remaining gear, buffs and effects, so the
+    /// item's weapon stats and modifiers no longer apply.
     pub fn unequip(&mut self, slot: EquipmentSlot) -> Option<Item> {
         let item = self.equipped_items.remove(&slot);
         if item.is_some() {
-            self.rebuild();
+            self.rebuild_stats();
         }
         item
     }
 
+    /// Set the template base stats and rebuild
+    ///
+    /// The template is kept on the block and applied before gear on every
+    /// rebuild, so it survives buffs and effects changing.
+    pub fn set_template(&mut self, template: TemplateSource) {
+        self.template = Some(template);
+        self.rebuild_stats();
+    }
+
+    /// The template base stats, if any
+    pub fn template(&self) -> Option<&TemplateSource> {
+        self.template.as_ref()
+    }
+
     /// Get a reference to the item equipped in a slot
     pub fn equipped(&self, slot: EquipmentSlot) -> Option<&Item> {
         self.equipped_items.get(&slot)
@@ -504,6 +1111,36 @@ impl StatBlock {
         self.equipped_items.iter()
     }
 
+    /// How much of a stat each equipment slot contributes
+    ///
+    /// Each slot lists what its item adds; `None` holds what buffs and stat
+    /// modifier effects add. Values are raw totals in the stat's own units
+    /// (e.g. flat life for `AddedLife`), before any increased, more or buff
+    /// effect scaling; local weapon and attribute-scaled modifiers are not
+    /// included. Only nonzero sources are listed, in slot order with `None`
+    /// last. Read from the sources directly, so rebuilds don't pay for it.
+    pub fn stat_sources(&self, stat: StatType) -> Vec<(Option<EquipmentSlot>, f64)> {
+        let mut sources = Vec::new();
+        for slot in EquipmentSlot::all() {
+            if let Some(item) = self.equipped_items.get(slot) {
+                let total = GearSource::new(*slot, item.clone()).stat_total(stat);
+                sources.push((Some(*slot), total));
+            }
+        }
+
+        let conditions = self.condition_state();
+        let buffs: f64 = self.buff_sources.iter().map(|b| b.stat_total(stat)).sum();
+        let effects: f64 = self
+            .effects
+            .iter()
+            .map(|e| e.stat_total(stat, &conditions))
+            .sum();
+        sources.push((None, buffs + effects));
+
+        sources.retain(|(_, value)| *value != 0.0);
+        sources
+    }
+
     // === Buff Methods ===
 
     /// Apply a buff, automatically rebuilding stats
@@ -519,7 +1156,7 @@ impl StatBlock {
         } else {
             self.buff_sources.push(buff);
         }
-        self.rebuild();
+        self.rebuild_stats();
     }
 
     /// Remove a buff by ID
@@ -527,7 +1164,7 @@ impl StatBlock {
         let had_buff = self.buff_sources.iter().any(|b| b.buff_id == buff_id);
         self.buff_sources.retain(|b| b.buff_id != buff_id);
         if had_buff {
-            self.rebuild();
+            self.rebuild_stats();
         }
     }
 
@@ -536,7 +1173,7 @@ impl StatBlock {
         let count_before = self.buff_sources.len();
         self.buff_sources.retain_mut(|buff| buff.tick(delta));
         if self.buff_sources.len() != count_before {
-            self.rebuild();
+            self.rebuild_stats();
         }
     }
 
@@ -545,12 +1182,154 @@ impl StatBlock {
         &self.buff_sources
     }
 
+    // === Cooldown Methods ===
+
+    /// Put a skill on cooldown, using its base cooldown reduced by cooldown reduction
+    pub fn start_cooldown(&mut self, skill: &DamagePacketGenerator) {
+        let duration = skill.effective_cooldown(self.cooldown_reduction);
+        self.cooldowns.start(skill.id.clone(), duration);
+    }
+
+    /// Tick all skill cooldowns by delta time
+    pub fn tick_cooldowns(&mut self, delta: f64) {
+        self.cooldowns.tick(delta);
+    }
+
+    /// Whether a skill is off cooldown
+    pub fn is_ready(&self, skill_id: &str) -> bool {
+        self.cooldowns.is_ready(skill_id)
+    }
+
     // === Combat Methods ===
 
     /// Generate a damage packet for a skill attack (RNG handled internally)
     pub fn attack(&self, skill: &DamagePacketGenerator) -> DamagePacket {
+        self.attack_with_rng(skill, &mut rand::thread_rng())
+    }
+
+    /// Generate a damage packet using a provided RNG (for replays and
+    /// deterministic testing)
+    ///
+    /// Damage range rolls, the crit roll and any random conversion or ailment
+    /// rolls all consume `rng`, so the same seed gives the same packet.
+    pub fn attack_with_rng(
+        &self,
+        skill: &DamagePacketGenerator,
+        rng: &mut impl Rng,
+    ) -> DamagePacket {
+        calculate_damage(self, skill, self.id.clone(), rng)
+    }
+
+    /// Generate one damage packet per target for a skill hitting several targets
+    ///
+    /// With more than one target the skill's `aoe_damage_effectiveness` applies
+    /// on top of `damage_effectiveness`; a single target is a normal `attack`.
+    pub fn attack_multi(&self, skill: &DamagePacketGenerator, targets: usize) -> Vec<DamagePacket> {
         let mut rng = rand::thread_rng();
-        calculate_damage(self, skill, self.id.clone(), &mut rng)
+        let area_skill;
+        let skill = if targets > 1 {
+            area_skill = skill.for_multiple_targets();
+            &area_skill
+        } else {
+            skill
+        };
+        (0..targets)
+            .map(|_| calculate_damage(self, skill, self.id.clone(), &mut rng))
+            .collect()
+    }
+
+    /// Per-type min, max and average hit damage for a skill, without rolling
+    ///
+    /// Includes weapon rolls, conversions and all damage scaling; crits are
+    /// not included. Empty if the skill deals no damage.
+    pub fn preview_damage(&self, skill: &DamagePacketGenerator) -> DamageRange {
+        calculate_damage_range(self, skill)
+    }
+
+    /// Calculate skill DPS with the given effects applied (immutable API)
+    ///
+    /// Clones this block, adds the effects, and rebuilds stats so tooling can
+    /// toggle buffs without mutating the live block.
+    pub fn dps_with_effects(&self, skill: &DamagePacketGenerator, effects: &[Effect]) -> f64 {
+        let mut block = self.clone();
+        for effect in effects {
+            block.add_effect(effect.clone());
+        }
+        block.rebuild();
+        calculate_skill_dps(&block, skill)
+    }
+
+    /// Skill DPS per point of mana spent per second while using it continuously
+    ///
+    /// Uses the mana cost after `reduced_mana_cost` and the skill's use rate.
+    /// Skills that can't be used (zero use rate) return 0; free skills
+    /// return `f64::INFINITY`.
+    pub fn dps_per_mana(&self, skill: &DamagePacketGenerator) -> f64 {
+        let rate = self.skill_use_rate(skill);
+        if rate <= 0.0 {
+            return 0.0;
+        }
+        let mana_per_second = skill.effective_mana_cost(self.reduced_mana_cost) * rate;
+        if mana_per_second <= 0.0 {
+            return f64::INFINITY;
+        }
+        calculate_skill_dps(self, skill) / mana_per_second
+    }
+
+    /// Average hits of a skill needed to kill `defender` from its current
+    /// life and energy shield
+    ///
+    /// `f64::INFINITY` if the skill can't damage the defender.
+    pub fn hits_to_kill(&self, skill: &DamagePacketGenerator, defender: &StatBlock) -> f64 {
+        calculate_hits_to_kill(self, skill, defender)
+    }
+
+    /// Mean and standard deviation of sampled DPS for a skill
+    ///
+    /// Rolls `samples` attacks (damage and crit rolls included) and scales
+    /// each by its hit count and the skill's use rate, showing how spiky a
+    /// build is beyond its average. Returns `(0.0, 0.0)` for zero samples.
+    pub fn dps_variance(
+        &self,
+        skill: &DamagePacketGenerator,
+        samples: usize,
+        rng: &mut impl Rng,
+    ) -> (f64, f64) {
+        if samples == 0 {
+            return (0.0, 0.0);
+        }
+        let rate = self.skill_use_rate(skill);
+        let values: Vec<f64> = (0..samples)
+            .map(|_| {
+                let packet = calculate_damage(self, skill, self.id.clone(), rng);
+                packet.total_damage() * packet.hit_count as f64 * rate
+            })
+            .collect();
+        let mean = values.iter().sum::<f64>() / samples as f64;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / samples as f64;
+        (mean, variance.sqrt())
+    }
+
+    /// Computed attack speed needed for a skill to reach `target_dps`
+    ///
+    /// DPS is linear in speed, so this scales the current speed while holding
+    /// damage and crit constant. Spells solve for cast speed instead. Returns
+    /// `None` when no speed reaches the target (the skill deals no damage).
+    pub fn attack_speed_for_dps(
+        &self,
+        skill: &DamagePacketGenerator,
+        target_dps: f64,
+    ) -> Option<f64> {
+        let speed = if skill.is_attack() {
+            self.computed_attack_speed()
+        } else {
+            self.computed_cast_speed()
+        };
+        let dps = calculate_skill_dps(self, skill);
+        if speed <= 0.0 || dps <= 0.0 {
+            return None;
+        }
+        Some(target_dps * speed / dps)
     }
 
     /// Receive damage from a damage packet (immutable API)
@@ -596,15 +1375,22 @@ impl StatBlock {
                             // Update dps if higher
                             if let EffectType::Ailment {
                                 dot_dps: existing_dps,
+                                from_crit: existing_from_crit,
+                                origin: existing_origin,
                                 ..
                             } = &mut existing_effect.effect_type
                             {
                                 if let EffectType::Ailment {
-                                    dot_dps: new_dps, ..
+                                    dot_dps: new_dps,
+                                    from_crit: new_from_crit,
+                                    origin: new_origin,
+                                    ..
                                 } = &effect.effect_type
                                 {
                                     if *new_dps > *existing_dps {
                                         *existing_dps = *new_dps;
+                                        *existing_from_crit = *new_from_crit;
+                                        *existing_origin = *new_origin;
                                     }
                                 }
                             }
@@ -625,10 +1411,49 @@ impl StatBlock {
         }
 
         // Check for stat modifier with same ID
-        if let EffectType::StatModifier { .. } = &effect.effect_type {
+        if let EffectType::StatModifier { stacking, .. } = &effect.effect_type {
             let existing = self.effects.iter_mut().find(|e| e.id == effect.id);
             if let Some(existing_effect) = existing {
-                existing_effect.add_stack();
+                match stacking {
+                    AilmentStacking::StrongestOnly => {
+                        // Keep the larger modifier, refresh duration either way
+                        if effect.modifier_magnitude() > existing_effect.modifier_magnitude() {
+                            existing_effect.effect_type = effect.effect_type.clone();
+                            existing_effect.source_id = effect.source_id.clone();
+                        }
+                        existing_effect.refresh(effect.duration_remaining);
+                        return;
+                    }
+                    AilmentStacking::Limited { .. } => {
+                        // Never shorten the buff; an added stack then extends it
+                        if effect.duration_remaining > existing_effect.duration_remaining {
+                            existing_effect.refresh(effect.duration_remaining);
+                        }
+                        existing_effect.add_stack();
+                        return;
+                    }
+                    AilmentStacking::Unlimited => {
+                        // Just add as new effect (fall through)
+                    }
+                }
+            }
+        }
+
+        // Re-applying a penetration effect refreshes it, keeping the stronger one
+        if let EffectType::Penetration { .. } = &effect.effect_type {
+            if let Some(existing_effect) = self.effects.iter_mut().find(|e| e.id == effect.id) {
+                if let (
+                    EffectType::Penetration {
+                        amount: existing, ..
+                    },
+                    EffectType::Penetration { amount: new, .. },
+                ) = (&existing_effect.effect_type, &effect.effect_type)
+                {
+                    if new > existing {
+                        existing_effect.effect_type = effect.effect_type.clone();
+                        existing_effect.source_id = effect.source_id.clone();
+                    }
+                }
                 existing_effect.refresh(effect.duration_remaining);
                 return;
             }
@@ -637,23 +1462,85 @@ impl StatBlock {
         self.effects.push(effect);
     }
 
+    /// Turn an aura on or off, returning whether it is now active
+    ///
+    /// Adds the aura effect (with its reservation) if no effect with its ID
+    /// is active, otherwise removes it. Stats are rebuilt either way.
+    pub fn toggle_aura(&mut self, aura: Effect) -> bool {
+        let was_active = self.effects.iter().any(|e| e.id == aura.id);
+        if was_active {
+            self.effects.retain(|e| e.id != aura.id);
+        } else {
+            self.add_effect(aura);
+        }
+        self.rebuild_from_effects();
+        !was_active
+    }
+
+    /// Percent of maximum mana reserved by active effects (capped at 100)
+    pub fn reserved_mana_percent(&self) -> f64 {
+        self.active_reservations()
+            .filter_map(|r| match r {
+                Reservation::Mana(percent) => Some(percent),
+                Reservation::Life(_) => None,
+            })
+            .sum::<f64>()
+            .clamp(0.0, 100.0)
+    }
+
+    /// Percent of maximum life reserved by active effects (capped at 100)
+    pub fn reserved_life_percent(&self) -> f64 {
+        self.active_reservations()
+            .filter_map(|r| match r {
+                Reservation::Life(percent) => Some(percent),
+                Reservation::Mana(_) => None,
+            })
+            .sum::<f64>()
+            .clamp(0.0, 100.0)
+    }
+
+    fn active_reservations(&self) -> impl Iterator<Item = Reservation> + '_ {
+        self.effects
+            .iter()
+            .filter(|e| e.is_active())
+            .filter_map(|e| e.reservation)
+    }
+
+    /// Total penetration from active effects for a damage type
+    pub fn effect_penetration(&self, damage_type: DamageType) -> f64 {
+        self.effects
+            .iter()
+            .map(|e| e.penetration(damage_type))
+            .sum()
+    }
+
     /// Tick all effects by delta time (immutable pattern)
     /// Returns a new StatBlock and the tick result
     pub fn tick_effects(&self, delta: f64) -> (StatBlock, TickResult) {
         let mut new_block = self.clone();
         let mut result = TickResult::default();
 
-        // Process all effects
+        // Process all effects; execute DoTs scale with life before the tick
+        let life_percent = new_block.life_percent();
+        let mut non_lethal_damage = 0.0;
         for effect in &mut new_block.effects {
-            let damage = effect.tick(delta);
+            let damage = effect.tick(delta) * effect.missing_life_multiplier(life_percent);
             if damage > 0.0 {
                 result.dot_damage += damage;
+                if !effect.is_lethal() {
+                    non_lethal_damage += damage;
+                }
             }
         }
+        let damage_taken = new_block.dot_damage_taken.max(0.0);
+        result.dot_damage *= damage_taken;
+        non_lethal_damage *= damage_taken;
 
-        // Apply DoT damage
+        // Apply DoT damage: non-lethal DoTs stop at 1 life, lethal ones can kill
         if result.dot_damage > 0.0 {
-            new_block.current_life -= result.dot_damage;
+            let floor = new_block.current_life.min(1.0);
+            new_block.current_life = (new_block.current_life - non_lethal_damage).max(floor);
+            new_block.current_life -= result.dot_damage - non_lethal_damage;
             if new_block.current_life <= 0.0 {
                 new_block.current_life = 0.0;
                 result.is_dead = true;
@@ -686,7 +1573,7 @@ impl StatBlock {
     fn rebuild_from_effects(&mut self) {
         // For now, just call the standard rebuild
         // Stat modifier effects would be applied during stat accumulation
-        self.rebuild();
+        self.rebuild_stats();
     }
 
     /// Get all active effects
@@ -707,10 +1594,109 @@ impl StatBlock {
         self.effects.iter().map(|e| e.dps()).sum()
     }
 
+    /// Seconds until active DoTs kill this entity if it takes no more hits
+    ///
+    /// Lethal DoTs (scaled by `dot_damage_taken`) drain life against
+    /// `life_regen` per second, piecewise as each DoT expires. Non-lethal
+    /// DoTs can't kill and are ignored, as is energy shield, which DoTs
+    /// bypass. Returns None if recovery outpaces the DoTs or they expire
+    /// first.
+    pub fn dot_survival_time(&self) -> Option<f64> {
+        if self.current_life <= 0.0 {
+            return Some(0.0);
+        }
+        let damage_taken = self.dot_damage_taken.max(0.0);
+        let mut dots: Vec<(f64, f64)> = self
+            .effects
+            .iter()
+            .filter(|e| e.is_active() && e.is_lethal() && e.dps() > 0.0)
+            .map(|e| (e.duration_remaining, e.dps() * damage_taken))
+            .collect();
+        dots.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let regen = self.life_regen.compute();
+        let max_life = self.computed_max_life();
+        let mut life = self.current_life;
+        let mut elapsed = 0.0;
+        for i in 0..dots.len() {
+            let (expires_at, _) = dots[i];
+            let net_dps: f64 = dots[i..].iter().map(|(_, dps)| dps).sum::<f64>() - regen;
+            let segment = expires_at - elapsed;
+            if net_dps > 0.0 && life <= net_dps * segment {
+                return Some(elapsed + life / net_dps);
+            }
+            if segment.is_infinite() {
+                return None;
+            }
+            life = (life - net_dps * segment).min(max_life);
+            elapsed = expires_at;
+        }
+        None
+    }
+
+    /// Recompute DPS of DoTs applied by `source` from its current stats
+    ///
+    /// Only affects ailments whose config sets `recompute_on_buff`; others
+    /// keep the DPS snapshotted when they were applied.
+    pub fn refresh_dot_dps(&mut self, source: &StatBlock) {
+        for effect in &mut self.effects {
+            if effect.source_id != source.id {
+                continue;
+            }
+            if let EffectType::Ailment {
+                status,
+                dot_dps,
+                from_crit,
+                origin: Some(origin),
+                ..
+            } = &mut effect.effect_type
+            {
+                *dot_dps = scaled_status_dot_dps(source, *status, origin.base_dps, *from_crit);
+            }
+        }
+    }
+
+    /// Remove effects that break on taking a hit, returning them
+    ///
+    /// Rebuilds stats if any removed effect was a stat modifier.
+    pub(crate) fn remove_effects_expiring_on_hit(&mut self) -> Vec<Effect> {
+        let (broken, kept) = std::mem::take(&mut self.effects)
+            .into_iter()
+            .partition(|e: &Effect| e.expires_on_hit);
+        self.effects = kept;
+        if broken.iter().any(|e| e.is_stat_modifier()) {
+            self.rebuild_from_effects();
+        }
+        broken
+    }
+
     /// Clear all effects
     pub fn clear_effects(&mut self) {
         self.effects.clear();
     }
+
+    /// Register an effect to be granted to this entity on killing blows
+    pub fn add_on_kill_effect(&mut self, effect: Effect) {
+        self.on_kill_effects.push(effect);
+    }
+
+    /// Get the effects this entity should gain from a hit it dealt
+    ///
+    /// Returns the registered on-kill effects, sourced from this entity, when
+    /// `result` is a killing blow, and nothing otherwise. The crate does not
+    /// apply them; the game layer passes them to `add_effect`.
+    pub fn on_kill_effects_for(&self, result: &CombatResult) -> Vec<Effect> {
+        if !result.is_killing_blow {
+            return Vec::new();
+        }
+        self.on_kill_effects
+            .iter()
+            .map(|effect| Effect {
+                source_id: self.id.clone(),
+                ..effect.clone()
+            })
+            .collect()
+    }
 }
 
 impl fmt::Display for StatBlock {
@@ -785,3 +1771,1335 @@ impl fmt::Display for StatBlock {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::damage::{BaseDamage, CritMode};
+    use crate::test_fixtures::ring_with_added_fire;
+    use crate::types::{SkillTag, StatMod};
+    use loot_core::types::StatType;
+
+    #[test]
+    fn test_load_save_without_damage_to_mana() {
+        // Saves from before damage_to_mana_percent existed still load
+        let mut json = serde_json::to_value(StatBlock::new()).unwrap();
+        json.as_object_mut()
+            .unwrap()
+            .remove("damage_to_mana_percent");
+        let loaded: StatBlock = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.damage_to_mana_percent, 0.0);
+    }
+
+    #[test]
+    fn test_finalize_after_load_clamps_current_to_new_max() {
+        let belt = generate_item("leather_belt");
+        let belt_life = belt.implicit.as_ref().unwrap().value as f64;
+
+        let mut saved = StatBlock::with_id("player");
+        saved.equip(EquipmentSlot::Belt, belt);
+        saved.current_life = saved.computed_max_life();
+        saved.current_mana = 25.0;
+        assert!((saved.current_life - (50.0 + belt_life)).abs() < 1e-9);
+
+        let json = serde_json::to_string(&saved).unwrap();
+        let mut loaded: StatBlock = serde_json::from_str(&json).unwrap();
+
+        // A balance patch lowered the belt's life roll after the save
+        let implicit = loaded
+            .equipped_items
+            .get_mut(&EquipmentSlot::Belt)
+            .unwrap()
+            .implicit
+            .as_mut()
+            .unwrap();
+        implicit.value -= 20;
+        loaded.finalize_after_load();
+
+        // Max recomputed from the equipped belt, current clamped down
+        let new_max = 50.0 + belt_life - 20.0;
+        assert!((loaded.computed_max_life() - new_max).abs() < 1e-9);
+        assert!((loaded.current_life - new_max).abs() < 1e-9);
+        // Values below the new max are restored as saved
+        assert!((loaded.current_mana - 25.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_damage_per_attribute() {
+        let mut block = StatBlock::with_id("player");
+        // 1% increased physical damage per 10 strength
+        block
+            .damage_per_attribute
+            .push((Attribute::Strength, DamageType::Physical, 1.0));
+
+        // Base 10 + 190 = 200 strength
+        let might = BuffSource::new("might".to_string(), "Might".to_string(), 10.0, false)
+            .with_modifier(StatType::AddedStrength, 190.0, false);
+        block.apply_buff(might);
+
+        assert!((block.strength.compute() - 200.0).abs() < 1e-9);
+        assert!((block.global_physical_damage.increased - 0.20).abs() < 1e-9);
+        assert!(block.global_fire_damage.increased.abs() < 1e-9);
+        // Scaling entry survives the rebuild
+        assert_eq!(block.damage_per_attribute.len(), 1);
+    }
+
+    #[test]
+    fn test_resistance_reduction_keeps_strongest() {
+        let mut block = StatBlock::with_id("enemy");
+        block.add_effect(Effect::new_resistance_reduction(
+            DamageType::Fire,
+            10.0,
+            4.0,
+            "a",
+        ));
+        block.add_effect(Effect::new_resistance_reduction(
+            DamageType::Fire,
+            25.0,
+            4.0,
+            "b",
+        ));
+        // Weaker exposure only refreshes, never overrides
+        block.add_effect(Effect::new_resistance_reduction(
+            DamageType::Fire,
+            15.0,
+            4.0,
+            "c",
+        ));
+
+        assert_eq!(block.effects.len(), 1);
+        assert_eq!(block.effects[0].source_id, "b");
+        assert_eq!(block.effects[0].stacks, 1);
+
+        block.rebuild();
+        assert!((block.fire_resistance.compute() - -25.0).abs() < 1e-9);
+        assert!(block.cold_resistance.compute().abs() < 1e-9);
+    }
+
+    /// Roll a normal item from the real config base types
+    fn generate_item(base_type_id: &str) -> Item {
+        let config = loot_core::Config::load_from_dir(std::path::Path::new("../config")).unwrap();
+        loot_core::Generator::new(config)
+            .generate(base_type_id, 1)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_energy_shield_from_gear() {
+        let robe = generate_item("silk_robe");
+        let es = robe.defenses.energy_shield.unwrap() as f64;
+        assert!(es > 0.0);
+
+        let mut block = StatBlock::new();
+        block.equip(EquipmentSlot::BodyArmour, robe);
+        assert!((block.max_energy_shield - es).abs() < 1e-9);
+
+        // Rebuilding from sources keeps the gear ES
+        block.equip(EquipmentSlot::Ring1, ring_with_added_fire(1));
+        assert!((block.max_energy_shield - es).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_added_flat_damage_from_gear() {
+        let mut block = StatBlock::new();
+        block.equip(EquipmentSlot::Ring1, ring_with_added_fire(12));
+        block.equip(EquipmentSlot::Ring2, ring_with_added_fire(8));
+
+        assert!((block.added_flat_damage(DamageType::Fire) - 20.0).abs() < f64::EPSILON);
+        assert!(block.added_flat_damage(DamageType::Cold).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_block_chance_from_gear() {
+        let mut shield = generate_item("iron_shield");
+        let mut warding = ring_with_added_fire(25).prefixes.remove(0);
+        warding.affix_id = "block_chance".to_string();
+        warding.name = "Warding".to_string();
+        warding.stat = StatType::BlockChance;
+        shield.prefixes.push(warding);
+
+        let mut block = StatBlock::new();
+        assert!(block.computed_block_chance().abs() < 1e-9);
+        block.equip(EquipmentSlot::OffHand, shield);
+        assert!((block.computed_block_chance() - 25.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_every_stat_type_changes_the_block() {
+        let build = |stat: Option<StatType>| {
+            // Increased ES only scales flat ES from the same accumulator
+            let mut accumulator = StatAccumulator::new();
+            accumulator.apply_stat_type(StatType::AddedEnergyShield, 100.0);
+            if let Some(stat) = stat {
+                accumulator.apply_stat_type(stat, 10.0);
+            }
+            let mut block = StatBlock::new();
+            accumulator.apply_to(&mut block);
+            serde_json::to_value(&block).unwrap()
+        };
+        let baseline = build(None);
+        let unchanged: Vec<_> = StatType::all()
+            .iter()
+            .filter(|stat| build(Some(**stat)) == baseline)
+            .collect();
+        assert!(unchanged.is_empty(), "{unchanged:?}");
+    }
+
+    #[test]
+    fn test_stat_sources_per_slot() {
+        let with_life = |value: i32| {
+            let mut item = ring_with_added_fire(value);
+            item.prefixes[0].stat = StatType::AddedLife;
+            item
+        };
+        let mut block = StatBlock::new();
+        block.equip(EquipmentSlot::Amulet, with_life(40));
+        block.equip(EquipmentSlot::BodyArmour, with_life(80));
+        block.equip(EquipmentSlot::Ring1, ring_with_added_fire(5));
+        let vigor = BuffSource::new("vigor".to_string(), "Vigor".to_string(), 60.0, false);
+        block.apply_buff(vigor.with_modifier(StatType::AddedLife, 10.0, false));
+        block.add_effect(Effect::new_stat_modifier(
+            "vitality",
+            "Vitality",
+            60.0,
+            false,
+            vec![StatMod::new(StatType::AddedLife, 5.0, false)],
+            "player",
+        ));
+
+        assert_eq!(
+            block.stat_sources(StatType::AddedLife),
+            vec![
+                (Some(EquipmentSlot::BodyArmour), 80.0),
+                (Some(EquipmentSlot::Amulet), 40.0),
+                (None, 15.0),
+            ]
+        );
+        assert_eq!(
+            block.stat_sources(StatType::AddedFireDamage),
+            vec![(Some(EquipmentSlot::Ring1), 5.0)]
+        );
+        assert!(block.stat_sources(StatType::AddedMana).is_empty());
+    }
+
+    #[test]
+    fn test_unequip_returns_item_and_keeps_other_gear() {
+        let mut block = StatBlock::new();
+        block.equip(EquipmentSlot::Ring1, ring_with_added_fire(12));
+        block.equip(EquipmentSlot::Ring2, ring_with_added_fire(8));
+        block.equip(EquipmentSlot::MainHand, generate_item("crystal_wand"));
+        assert_eq!(block.weapon_damage(DamageType::Cold), (3.0, 8.0));
+        assert_eq!(block.weapon_damage(DamageType::Lightning), (1.0, 12.0));
+
+        let ring = block.unequip(EquipmentSlot::Ring1).unwrap();
+        assert_eq!(ring.prefixes[0].value, 12);
+        assert!((block.added_flat_damage(DamageType::Fire) - 8.0).abs() < f64::EPSILON);
+        assert!(block.unequip(EquipmentSlot::Ring1).is_none());
+
+        block.unequip(EquipmentSlot::MainHand).unwrap();
+        assert_eq!(block.weapon_damage(DamageType::Cold), (0.0, 0.0));
+        assert_eq!(block.weapon_damage(DamageType::Lightning), (0.0, 0.0));
+        assert!((block.added_flat_damage(DamageType::Fire) - 8.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_with_equipment_builds_full_player() {
+        let belt = generate_item("leather_belt");
+        let belt_life = belt.implicit.as_ref().unwrap().value as f64;
+
+        let player = StatBlock::with_equipment(
+            "player",
+            vec![
+                (EquipmentSlot::MainHand, generate_item("iron_sword")),
+                (EquipmentSlot::Ring1, ring_with_added_fire(12)),
+                (EquipmentSlot::Belt, belt),
+            ],
+        );
+
+        assert_eq!(player.id, "player");
+        assert_eq!(player.all_equipped().count(), 3);
+        assert_eq!(player.weapon_damage(DamageType::Physical), (5.0, 12.0));
+        assert!((player.added_flat_damage(DamageType::Fire) - 12.0).abs() < f64::EPSILON);
+        let base_life = StatBlock::new().computed_max_life();
+        assert!((player.computed_max_life() - (base_life + belt_life)).abs() < 1e-9);
+        assert_eq!(player.current_life, player.computed_max_life());
+        assert_eq!(player.current_mana, player.computed_max_mana());
+    }
+
+    #[test]
+    fn test_reserved_mana_percent() {
+        let mut block = StatBlock::new();
+        for id in ["clarity", "discipline"] {
+            block.add_effect(
+                Effect::new_stat_modifier(id, id, 60.0, false, vec![], "player")
+                    .with_reservation(Reservation::Mana(35.0)),
+            );
+        }
+
+        assert!((block.reserved_mana_percent() - 70.0).abs() < 1e-9);
+        assert!(block.reserved_life_percent().abs() < f64::EPSILON);
+
+        block.add_effect(
+            Effect::new_stat_modifier("wrath", "Wrath", 60.0, false, vec![], "player")
+                .with_reservation(Reservation::Mana(50.0)),
+        );
+        assert!((block.reserved_mana_percent() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_toggle_aura_adds_then_removes() {
+        let mut block = StatBlock::with_id("player");
+        let purity = Effect::new_stat_modifier(
+            "purity_of_fire",
+            "Purity of Fire",
+            f64::INFINITY,
+            false,
+            vec![StatMod::new(StatType::FireResistance, 20.0, false)],
+            "player",
+        )
+        .with_reservation(Reservation::Mana(25.0));
+
+        assert!(block.toggle_aura(purity.clone()));
+        assert!((block.reserved_mana_percent() - 25.0).abs() < 1e-9);
+        assert!((block.fire_resistance.compute() - 20.0).abs() < 1e-9);
+
+        assert!(!block.toggle_aura(purity));
+        assert!(block.reserved_mana_percent().abs() < f64::EPSILON);
+        assert!(block.fire_resistance.compute().abs() < f64::EPSILON);
+        assert!(block.active_effects().is_empty());
+    }
+
+    #[test]
+    fn test_energy_shield_recharges_after_delay() {
+        crate::config::ensure_constants_initialized();
+        crate::config::ensure_dot_registry_initialized();
+        let es = &constants().energy_shield;
+        let mut block = StatBlock::with_id("player");
+        block.set_max_energy_shield(100.0);
+        block.current_energy_shield = 100.0;
+
+        let packet = DamagePacket::builder("enemy", "hit")
+            .damage(DamageType::Cold, 60.0)
+            .build();
+        let (mut block, result) = block.receive_damage(&packet);
+        assert!(result.resets_es_recharge);
+        assert!((block.current_energy_shield - 40.0).abs() < 1e-9);
+
+        // Still inside the delay: nothing recharges
+        block.tick_energy_shield(1.0, es.recharge_delay - 0.5);
+        assert!((block.current_energy_shield - 40.0).abs() < 1e-9);
+
+        // Only the half second past the delay counts
+        block.tick_energy_shield(1.0, es.recharge_delay + 0.5);
+        let expected = 40.0 + 100.0 * es.recharge_rate * 0.5;
+        assert!((block.current_energy_shield - expected).abs() < 1e-9);
+
+        // Recharge stops at max
+        block.tick_energy_shield(60.0, 100.0);
+        assert!((block.current_energy_shield - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_increased_recovery_rate_scales_regen() {
+        crate::config::ensure_constants_initialized();
+        let mut block = StatBlock::with_id("player");
+        block.apply_buff(
+            BuffSource::new("regen".to_string(), "Regen".to_string(), 60.0, false)
+                .with_modifier(StatType::LifeRegeneration, 20.0, false)
+                .with_modifier(StatType::IncreasedLifeRecoveryRate, 50.0, false)
+                .with_modifier(StatType::ManaRegeneration, 4.0, false),
+        );
+        assert!((block.life_regen.compute() - 30.0).abs() < 1e-9);
+
+        block.max_life.base = 1000.0;
+        block.current_life = 100.0;
+        block.max_mana.base = 100.0;
+        block.current_mana = 0.0;
+        block.tick_recovery(2.0);
+        assert!((block.current_life - 160.0).abs() < 1e-9);
+        assert!((block.current_mana - 8.0).abs() < 1e-9);
+
+        block.current_life = 0.0;
+        block.tick_recovery(1.0);
+        assert_eq!(block.current_life, 0.0);
+
+        let es = &constants().energy_shield;
+        block.set_max_energy_shield(100.0);
+        block.es_recharge_increased = 0.5;
+        block.tick_energy_shield(1.0, es.recharge_delay + 1.0);
+        let expected = 100.0 * es.recharge_rate * 1.5;
+        assert!((block.current_energy_shield - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_apply_leech_recovers_from_hits() {
+        crate::config::ensure_constants_initialized();
+        let leech = &constants().leech;
+        let mut block = StatBlock::with_id("player");
+        block.max_life.base = 1000.0;
+        block.current_life = 100.0;
+        block.max_mana.base = 100.0;
+        block.current_mana = 0.0;
+        block.life_leech.base = 0.05;
+        block.mana_leech.base = 0.01;
+        block.life_on_hit = 10.0;
+
+        let mut hit = CombatResult::new();
+        hit.total_damage = 1000.0;
+        let recovered = block.apply_leech(&hit);
+        assert!((recovered.life - 60.0).abs() < 1e-9);
+        assert!((recovered.mana - 10.0).abs() < 1e-9);
+
+        // Leech over one second of the max leech rate is capped across hits
+        hit.total_damage = 100_000.0;
+        let recovered = block.apply_leech(&hit);
+        let cap = leech.max_life_leech_rate * 1000.0;
+        assert!((recovered.life - (cap - 50.0 + 10.0)).abs() < 1e-9);
+        assert!((block.apply_leech(&hit).life - 10.0).abs() < 1e-9);
+
+        // A second of recovery frees the whole budget again
+        block.tick_recovery(1.0);
+        let life_before = block.current_life;
+        let recovered = block.apply_leech(&hit);
+        assert!((recovered.life - (cap + 10.0)).abs() < 1e-9);
+        assert!((block.current_life - (life_before + cap + 10.0)).abs() < 1e-9);
+
+        // Killing blows add on-kill recovery; dodged hits grant no life on hit
+        hit.total_damage = 0.0;
+        hit.was_dodged = true;
+        assert_eq!(block.apply_leech(&hit), LeechRecovery::default());
+        hit.was_dodged = false;
+        hit.is_killing_blow = true;
+        hit.life_gained_on_kill = 25.0;
+        hit.mana_gained_on_kill = 5.0;
+        let recovered = block.apply_leech(&hit);
+        assert!((recovered.life - 35.0).abs() < 1e-9);
+
+        // Recovery stops at max life
+        block.current_life = 995.0;
+        assert!((block.apply_leech(&hit).life - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dot_survival_time() {
+        let poison = |id: &str, duration: f64, dps: f64| {
+            Effect::new_ailment(
+                id,
+                "Poison",
+                StatusEffect::Poison,
+                duration,
+                1.0,
+                dps,
+                0.5,
+                AilmentStacking::Unlimited,
+                true,
+                "enemy",
+            )
+        };
+        let mut block = StatBlock::with_id("player");
+        block.max_life = StatValue::with_base(100.0);
+        block.current_life = 50.0;
+        block.life_regen.add_flat(10.0);
+        assert_eq!(block.dot_survival_time(), None);
+
+        // 60 DPS for 1s then 20 DPS: net 50 then 10 per second
+        block.add_effect(poison("strong", 1.0, 40.0));
+        block.add_effect(poison("long", 10.0, 20.0));
+        assert!((block.dot_survival_time().unwrap() - 1.0).abs() < 1e-9);
+        block.current_life = 80.0;
+        // 50 lost in the first second, the remaining 30 at 10 per second
+        assert!((block.dot_survival_time().unwrap() - 4.0).abs() < 1e-9);
+
+        // A weak poison is outpaced by regen
+        block.clear_effects();
+        block.add_effect(poison("weak", 100.0, 5.0));
+        assert_eq!(block.dot_survival_time(), None);
+    }
+
+    #[test]
+    fn test_on_kill_effects_granted_on_killing_blow() {
+        let mut attacker = StatBlock::with_id("player");
+        attacker.add_on_kill_effect(Effect::new_stat_modifier(
+            "rampage",
+            "Rampage",
+            4.0,
+            false,
+            vec![StatMod::new(StatType::IncreasedGlobalDamage, 20.0, false)],
+            "",
+        ));
+        attacker.rebuild();
+        assert_eq!(attacker.on_kill_effects.len(), 1);
+
+        let mut packet = DamagePacket::new("player".to_string(), "test".to_string());
+        packet.add_damage(DamageType::Physical, 10.0);
+        let (_, survived) = StatBlock::with_id("dummy").receive_damage(&packet);
+        assert!(!survived.is_killing_blow);
+        assert!(attacker.on_kill_effects_for(&survived).is_empty());
+
+        let mut weak = StatBlock::with_id("goblin");
+        weak.current_life = 5.0;
+        let (_, killed) = weak.receive_damage(&packet);
+        assert!(killed.is_killing_blow);
+
+        let granted = attacker.on_kill_effects_for(&killed);
+        assert_eq!(granted.len(), 1);
+        assert_eq!(granted[0].id, "rampage");
+        assert_eq!(granted[0].source_id, "player");
+        assert!((granted[0].duration_remaining - 4.0).abs() < f64::EPSILON);
+
+        attacker.add_effect(granted[0].clone());
+        attacker.rebuild();
+        assert!((attacker.global_physical_damage.increased - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_buff_effect_received_scales_auras() {
+        let aura = Effect::new_stat_modifier(
+            "anger",
+            "Anger",
+            60.0,
+            false,
+            vec![StatMod::new(StatType::IncreasedPhysicalDamage, 50.0, false)],
+            "ally",
+        );
+
+        let mut plain = StatBlock::with_id("plain");
+        plain.add_effect(aura.clone());
+        plain.rebuild();
+
+        let mut boosted = StatBlock::with_id("boosted");
+        boosted.apply_buff(
+            BuffSource::new("blessing".to_string(), "Blessing".to_string(), 60.0, false)
+                .with_modifier(StatType::IncreasedBuffEffectReceived, 30.0, false),
+        );
+        boosted.add_effect(aura);
+        boosted.rebuild();
+
+        assert!((boosted.buff_effect_received - 0.3).abs() < 1e-9);
+        assert!((plain.global_physical_damage.increased - 0.5).abs() < 1e-9);
+        assert!((boosted.global_physical_damage.increased - 0.65).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_consumable_effect_scales_consumables() {
+        let flask = Effect::new_stat_modifier(
+            "life_flask",
+            "Life Flask",
+            4.0,
+            false,
+            vec![StatMod::new(StatType::LifeRegeneration, 50.0, false)],
+            "self",
+        )
+        .consumable();
+        let passive = BuffSource::new(
+            "alchemist".to_string(),
+            "Alchemist".to_string(),
+            60.0,
+            false,
+        )
+        .with_modifier(StatType::IncreasedConsumableEffect, 30.0, false);
+
+        let mut plain = StatBlock::with_id("plain");
+        plain.add_effect(flask.clone());
+        plain.rebuild();
+
+        let mut boosted = StatBlock::with_id("boosted");
+        boosted.apply_buff(passive.clone());
+        boosted.add_effect(flask);
+        boosted.rebuild();
+
+        assert!((boosted.consumable_effect - 0.3).abs() < 1e-9);
+        assert!((plain.life_regen.compute() - 50.0).abs() < 1e-9);
+        assert!((boosted.life_regen.compute() - 65.0).abs() < 1e-9);
+
+        // Non-consumable buffs are unaffected
+        let mut aura_only = StatBlock::with_id("aura_only");
+        aura_only.apply_buff(passive);
+        aura_only.add_effect(Effect::new_stat_modifier(
+            "regen_aura",
+            "Regen Aura",
+            60.0,
+            false,
+            vec![StatMod::new(StatType::LifeRegeneration, 50.0, false)],
+            "ally",
+        ));
+        aura_only.rebuild();
+        assert!((aura_only.life_regen.compute() - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dps_with_effects() {
+        crate::config::ensure_dot_registry_initialized();
+        let block = StatBlock::with_id("player");
+        let skill = DamagePacketGenerator {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            ..Default::default()
+        };
+        let buff = Effect::new_stat_modifier(
+            "rage",
+            "Rage",
+            10.0,
+            false,
+            vec![StatMod::new(StatType::IncreasedPhysicalDamage, 50.0, false)],
+            "player",
+        );
+
+        let base_dps = block.dps_with_effects(&skill, &[]);
+        let buffed_dps = block.dps_with_effects(&skill, &[buff]);
+
+        assert!(base_dps > 0.0);
+        assert!((buffed_dps / base_dps - 1.5).abs() < 1e-9);
+        // Live block is untouched
+        assert!(block.effects.is_empty());
+    }
+
+    #[test]
+    fn test_cooldown_reduced_and_ticked_to_ready() {
+        let mut block = StatBlock::with_id("player");
+        block.cooldown_reduction = 0.2;
+        let skill = DamagePacketGenerator {
+            id: "leap_slam".to_string(),
+            cooldown: 5.0,
+            ..Default::default()
+        };
+
+        assert!(block.is_ready("leap_slam"));
+        block.start_cooldown(&skill);
+        assert!(!block.is_ready("leap_slam"));
+        assert!((block.cooldowns.remaining("leap_slam") - 4.0).abs() < 1e-9);
+
+        // Cooldowns survive stat rebuilds
+        block.rebuild();
+        block.tick_cooldowns(3.5);
+        assert!(!block.is_ready("leap_slam"));
+        block.tick_cooldowns(0.5);
+        assert!(block.is_ready("leap_slam"));
+    }
+
+    #[test]
+    fn test_attack_multi_applies_aoe_effectiveness() {
+        crate::config::ensure_dot_registry_initialized();
+        let block = StatBlock::with_id("player");
+        let skill = DamagePacketGenerator {
+            id: "fireball".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            aoe_damage_effectiveness: 0.7,
+            crit_override: Some(CritMode::Never),
+            tags: vec![SkillTag::Spell],
+            ..Default::default()
+        };
+
+        let single = block.attack(&skill).total_damage();
+        assert!((single - 100.0).abs() < 1e-9);
+        assert_eq!(block.attack_multi(&skill, 1)[0].total_damage(), single);
+
+        let packets = block.attack_multi(&skill, 3);
+        assert_eq!(packets.len(), 3);
+        for packet in &packets {
+            assert!((packet.total_damage() - 70.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_damage_multipliers() {
+        let mut block = StatBlock::new();
+        block.global_physical_damage.add_increased(0.5);
+        block.global_physical_damage.add_more(0.1);
+
+        let (increased, more) = block.damage_multipliers(DamageType::Physical);
+        assert!((increased - 1.5).abs() < 1e-9);
+        assert!((more - 1.1).abs() < 1e-9);
+        assert_eq!(block.damage_multipliers(DamageType::Fire), (1.0, 1.0));
+    }
+
+    #[test]
+    fn test_dps_per_mana() {
+        crate::config::ensure_dot_registry_initialized();
+        let mut block = StatBlock::with_id("player");
+        block.reduced_mana_cost = 0.2;
+        let spell = |id: &str, damage: f64, mana_cost: f64| DamagePacketGenerator {
+            id: id.to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, damage, damage)],
+            weapon_effectiveness: 0.0,
+            crit_override: Some(CritMode::Never),
+            tags: vec![SkillTag::Spell],
+            mana_cost,
+            ..Default::default()
+        };
+        let cheap = spell("spark", 60.0, 5.0);
+        let expensive = spell("meteor", 100.0, 20.0);
+
+        assert!(calculate_skill_dps(&block, &cheap) < calculate_skill_dps(&block, &expensive));
+        // 60 damage per 4 mana vs 100 per 16
+        assert!((block.dps_per_mana(&cheap) - 15.0).abs() < 1e-9);
+        assert!((block.dps_per_mana(&expensive) - 6.25).abs() < 1e-9);
+        assert_eq!(block.dps_per_mana(&spell("free", 10.0, 0.0)), f64::INFINITY);
+
+        let mut stopped = cheap.clone();
+        stopped.attack_speed_modifier = 0.0;
+        assert_eq!(block.dps_per_mana(&stopped), 0.0);
+    }
+
+    #[test]
+    fn test_sheet_dps_ignores_chill_and_freeze() {
+        crate::config::ensure_constants_initialized();
+        crate::config::ensure_dot_registry_initialized();
+        let mut block = StatBlock::with_id("player");
+        let spell = DamagePacketGenerator {
+            id: "spark".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Fire, 60.0, 60.0)],
+            weapon_effectiveness: 0.0,
+            crit_override: Some(CritMode::Never),
+            tags: vec![SkillTag::Spell],
+            mana_cost: 5.0,
+            ..Default::default()
+        };
+        let rate = block.skill_use_rate(&spell);
+        let dps = calculate_skill_dps(&block, &spell);
+        let per_mana = block.dps_per_mana(&spell);
+
+        let ailment = |id: &str, status: StatusEffect, magnitude: f64| {
+            Effect::new_ailment(
+                id,
+                id,
+                status,
+                2.0,
+                magnitude,
+                0.0,
+                0.5,
+                AilmentStacking::Unlimited,
+                true,
+                "enemy",
+            )
+        };
+        for status in [StatusEffect::Chill, StatusEffect::Freeze] {
+            let mut ailed = block.clone();
+            ailed.effects.push(ailment("ailment", status, 1.0));
+            assert!(ailed.action_speed_multiplier() < 1.0);
+            assert_eq!(ailed.skill_use_rate(&spell), rate);
+            assert_eq!(calculate_skill_dps(&ailed, &spell), dps);
+            assert_eq!(ailed.dps_per_mana(&spell), per_mana);
+            assert!(ailed.dps_per_mana(&spell).is_finite());
+        }
+
+        block
+            .effects
+            .push(ailment("freeze", StatusEffect::Freeze, 1.0));
+        assert_eq!(
+            block.skill_use_rate(&spell) * block.action_speed_multiplier(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_preview_damage_range() {
+        let mut block = StatBlock::with_id("player");
+        block.weapon_physical_min = 50.0;
+        block.weapon_physical_max = 150.0;
+        block.global_fire_damage.add_increased(0.5);
+        let mut skill = DamagePacketGenerator {
+            id: "flame_strike".to_string(),
+            weapon_effectiveness: 1.0,
+            tags: vec![SkillTag::Attack],
+            ..Default::default()
+        };
+        skill.damage_conversions.physical_to_fire = 0.5;
+
+        let range = block.preview_damage(&skill);
+        let phys = range.of_type(DamageType::Physical).unwrap();
+        assert_eq!((phys.min, phys.max, phys.average), (25.0, 75.0, 50.0));
+        // Converted half scales with 50% increased fire
+        let fire = range.of_type(DamageType::Fire).unwrap();
+        assert_eq!((fire.min, fire.max, fire.average), (37.5, 112.5, 75.0));
+        assert!(range.of_type(DamageType::Cold).is_none());
+        assert_eq!(range.total_average(), 125.0);
+
+        let empty = DamagePacketGenerator {
+            id: "nothing".to_string(),
+            weapon_effectiveness: 0.0,
+            ..Default::default()
+        };
+        assert!(block.preview_damage(&empty).is_empty());
+    }
+
+    #[test]
+    fn test_resistance_reduction_floor() {
+        let mut block = StatBlock::with_id("player");
+        block.apply_buff(
+            BuffSource::new("purity".to_string(), "Purity".to_string(), 10.0, false)
+                .with_modifier(StatType::FireResistance, 10.0, false)
+                .with_modifier(StatType::ColdResistance, -15.0, false),
+        );
+        block.add_effect(Effect::new_resistance_reduction(
+            DamageType::Fire,
+            30.0,
+            4.0,
+            "enemy",
+        ));
+        block.add_effect(Effect::new_resistance_reduction(
+            DamageType::Cold,
+            30.0,
+            4.0,
+            "enemy",
+        ));
+        block.rebuild();
+        assert!((block.fire_resistance.compute() - -20.0).abs() < 1e-9);
+        assert!((block.cold_resistance.compute() - -45.0).abs() < 1e-9);
+
+        block.resistance_reduction_floor = true;
+        block.rebuild();
+        assert!(block.fire_resistance.compute().abs() < 1e-9);
+        // Already negative from other sources: debuffs can't lower it further
+        assert!((block.cold_resistance.compute() - -15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_refresh_dot_dps_snapshot_vs_recompute() {
+        let mut attacker = StatBlock::with_id("attacker");
+
+        // Same poison twice: one snapshotted, one recomputing from its origin
+        let snapshot = Effect::new_ailment(
+            "poison",
+            "Poison",
+            StatusEffect::Poison,
+            2.0,
+            1.0,
+            20.0,
+            0.33,
+            AilmentStacking::Unlimited,
+            true,
+            "attacker",
+        );
+        let recompute = snapshot
+            .clone()
+            .with_dot_origin(crate::types::DotOrigin { base_dps: 20.0 });
+        let mut target = StatBlock::with_id("target");
+        target.add_effect(snapshot);
+        target.add_effect(recompute);
+
+        // +50% DoT multiplier after the poisons were applied
+        attacker.dot_multiplier = 0.5;
+        target.refresh_dot_dps(&attacker);
+        let dps: Vec<f64> = target.active_effects().iter().map(|e| e.dps()).collect();
+        assert_eq!(dps.len(), 2);
+        assert!((dps[0] - 20.0).abs() < 1e-9);
+        assert!((dps[1] - 30.0).abs() < 1e-9);
+
+        // Only the source's own DoTs are refreshed
+        attacker.id = "someone_else".to_string();
+        attacker.dot_multiplier = 1.0;
+        target.refresh_dot_dps(&attacker);
+        assert!((target.active_effects()[1].dps() - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dot_damage_taken_scales_ailment_ticks() {
+        let poisoned = |id: &str| {
+            let mut block = StatBlock::with_id(id);
+            block.max_life.base = 1000.0;
+            block.add_effect(Effect::new_ailment(
+                "poison",
+                "Poison",
+                StatusEffect::Poison,
+                2.0,
+                1.0,
+                20.0,
+                0.5,
+                AilmentStacking::Unlimited,
+                true,
+                "enemy",
+            ));
+            block
+        };
+        let mut baseline = poisoned("baseline");
+        let mut vulnerable = poisoned("vulnerable");
+        vulnerable.add_effect(Effect::new_stat_modifier(
+            "withered",
+            "Withered",
+            4.0,
+            true,
+            vec![StatMod::new(StatType::IncreasedDotDamageTaken, 50.0, false)],
+            "enemy",
+        ));
+        for block in [&mut baseline, &mut vulnerable] {
+            block.rebuild();
+            block.current_life = 1000.0;
+        }
+        assert!((vulnerable.dot_damage_taken - 1.5).abs() < 1e-9);
+
+        let (_, base_tick) = baseline.tick_effects(0.5);
+        let (after, vuln_tick) = vulnerable.tick_effects(0.5);
+        assert!((base_tick.dot_damage - 10.0).abs() < 1e-9);
+        assert!((vuln_tick.dot_damage - 15.0).abs() < 1e-9);
+        assert!((after.current_life - 985.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_attack_speed_for_dps() {
+        crate::config::ensure_dot_registry_initialized();
+        let block = StatBlock::with_id("player");
+        let strike = |damage: f64| DamagePacketGenerator {
+            id: "strike".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, damage, damage)],
+            weapon_effectiveness: 0.0,
+            crit_override: Some(CritMode::Never),
+            ..Default::default()
+        };
+
+        let skill = strike(50.0);
+        let speed = block.computed_attack_speed();
+        let dps = calculate_skill_dps(&block, &skill);
+        let needed = block.attack_speed_for_dps(&skill, 2.0 * dps).unwrap();
+        assert!((needed - 2.0 * speed).abs() < 1e-9);
+        let doubled = block.attack_speed_for_dps(&skill, 4.0 * dps).unwrap();
+        assert!((doubled - 2.0 * needed).abs() < 1e-9);
+
+        assert_eq!(block.attack_speed_for_dps(&strike(0.0), 100.0), None);
+    }
+
+    #[test]
+    fn test_attack_with_rng_is_reproducible() {
+        use rand::SeedableRng;
+        crate::config::ensure_dot_registry_initialized();
+        let block = StatBlock::with_id("player");
+        let mut skill = DamagePacketGenerator {
+            base_crit_chance: 50.0,
+            weapon_effectiveness: 0.0,
+            ..DamagePacketGenerator::new("slash")
+        }
+        .with_base_damage(BaseDamage::new(DamageType::Physical, 10.0, 100.0))
+        .with_base_damage(BaseDamage::new(DamageType::Cold, 5.0, 20.0))
+        .with_base_damage(BaseDamage::new(DamageType::Fire, 5.0, 20.0));
+        skill.damage_conversions.physical_to_lightning = 0.25;
+
+        let roll = |seed: u64| {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            (0..10)
+                .map(|_| block.attack_with_rng(&skill, &mut rng))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(roll(42), roll(42));
+        assert_ne!(roll(42), roll(43));
+
+        // Damage types always come out in the same order
+        for packet in roll(42) {
+            let types: Vec<DamageType> = packet.damages.iter().map(|d| d.damage_type).collect();
+            assert_eq!(
+                types,
+                vec![
+                    DamageType::Physical,
+                    DamageType::Fire,
+                    DamageType::Cold,
+                    DamageType::Lightning
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_dps_variance_higher_for_crit_builds() {
+        use rand::SeedableRng;
+        crate::config::ensure_dot_registry_initialized();
+        let block = StatBlock::with_id("player");
+        let consistent = DamagePacketGenerator {
+            id: "steady".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 100.0, 100.0)],
+            weapon_effectiveness: 0.0,
+            crit_override: Some(CritMode::Never),
+            ..Default::default()
+        };
+        let spiky = DamagePacketGenerator {
+            id: "spiky".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 70.0, 70.0)],
+            weapon_effectiveness: 0.0,
+            base_crit_chance: 10.0,
+            crit_multiplier_bonus: 4.0,
+            ..Default::default()
+        };
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+
+        let (steady_mean, steady_dev) = block.dps_variance(&consistent, 2000, &mut rng);
+        let (spiky_mean, spiky_dev) = block.dps_variance(&spiky, 2000, &mut rng);
+
+        assert!(steady_dev < 1e-9);
+        assert!((spiky_mean / steady_mean - 1.0).abs() < 0.25);
+        assert!(spiky_dev > 0.5 * spiky_mean);
+        assert_eq!(block.dps_variance(&spiky, 0, &mut rng), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_dps_variance_mean_matches_multi_hit_dps() {
+        use rand::SeedableRng;
+        crate::config::ensure_dot_registry_initialized();
+        let block = StatBlock::with_id("player");
+        let flurry = DamagePacketGenerator {
+            id: "flurry".to_string(),
+            base_damages: vec![BaseDamage::new(DamageType::Physical, 80.0, 120.0)],
+            weapon_effectiveness: 0.0,
+            crit_override: Some(CritMode::Never),
+            hits_per_attack: 3,
+            ..Default::default()
+        };
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+
+        let (mean, _) = block.dps_variance(&flurry, 5000, &mut rng);
+        let expected = calculate_skill_dps(&block, &flurry);
+        assert!((mean / expected - 1.0).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_duration_per_stack_extends_buff() {
+        let frenzy = || {
+            let mut effect = Effect::new_stat_modifier(
+                "frenzy",
+                "Frenzy",
+                10.0,
+                false,
+                vec![StatMod::new(StatType::IncreasedAttackSpeed, 5.0, false)],
+                "player",
+            )
+            .with_duration_per_stack(2.0);
+            effect.max_stacks = 4;
+            effect
+        };
+        let mut block = StatBlock::with_id("player");
+        block.add_effect(frenzy());
+
+        for expected in [12.0, 14.0, 16.0] {
+            block.add_effect(frenzy());
+            assert!((block.effects[0].duration_remaining - expected).abs() < 1e-9);
+        }
+        assert_eq!(block.effects[0].stacks, 4);
+
+        // At max stacks re-applying only tops the duration back up to the base
+        block.effects[0].tick(5.0);
+        block.add_effect(frenzy());
+        assert!((block.effects[0].duration_remaining - 11.0).abs() < 1e-9);
+        block.effects[0].tick(5.0);
+        block.add_effect(frenzy());
+        assert!((block.effects[0].duration_remaining - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_public_rebuild_applies_effects_and_keeps_ratios() {
+        let mod_of =
+            |stat: StatType, value_per_stack: f64| StatMod::new(stat, value_per_stack, false);
+        let mut block = StatBlock::with_id("player");
+        let max_life = block.computed_max_life();
+        block.current_life = max_life / 2.0;
+
+        // Pushed directly, so nothing applies until the rebuild
+        block.effects.push(Effect::new_stat_modifier(
+            "war_cry",
+            "War Cry",
+            10.0,
+            false,
+            vec![
+                mod_of(StatType::IncreasedPhysicalDamage, 50.0),
+                mod_of(StatType::IncreasedLife, 100.0),
+            ],
+            "player",
+        ));
+        assert!(block.global_physical_damage.increased.abs() < 1e-9);
+
+        block.rebuild();
+        assert!((block.global_physical_damage.increased - 0.50).abs() < 1e-9);
+        assert!((block.computed_max_life() - 2.0 * max_life).abs() < 1e-9);
+        assert!((block.life_percent() - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_full_energy_shield_condition() {
+        use crate::types::StatCondition;
+        let mut block = StatBlock::with_id("player");
+        let ward = BuffSource::new("ward".to_string(), "Ward".to_string(), 60.0, false);
+        block.apply_buff(ward.with_modifier(StatType::AddedEnergyShield, 100.0, false));
+        block.current_energy_shield = block.max_energy_shield;
+        block.add_effect(Effect::new_stat_modifier(
+            "shield_focus",
+            "Shield Focus",
+            60.0,
+            false,
+            vec![StatMod::new(StatType::IncreasedFireDamage, 40.0, false)
+                .with_condition(StatCondition::FullEnergyShield)],
+            "player",
+        ));
+        block.rebuild();
+        assert!((block.global_fire_damage.increased - 0.40).abs() < 1e-9);
+
+        // Chipped ES drops the bonus on the next rebuild
+        block.current_energy_shield -= 10.0;
+        block.rebuild();
+        assert!(block.global_fire_damage.increased.abs() < 1e-9);
+        assert!((block.max_energy_shield - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_low_life_condition_toggles_damage() {
+        use crate::types::StatCondition;
+        let mut block = StatBlock::with_id("player");
+        let max_life = block.computed_max_life();
+        block.add_effect(Effect::new_stat_modifier(
+            "desperation",
+            "Desperation",
+            60.0,
+            false,
+            vec![StatMod::new(StatType::IncreasedPhysicalDamage, 50.0, false)
+                .with_condition(StatCondition::LowLife(35.0))],
+            "player",
+        ));
+        block.rebuild();
+        assert!(block.global_physical_damage.increased.abs() < 1e-9);
+
+        block.current_life = max_life * 0.30;
+        block.rebuild();
+        assert!((block.global_physical_damage.increased - 0.50).abs() < 1e-9);
+
+        block.current_life = max_life * 0.35;
+        block.rebuild();
+        assert!(block.global_physical_damage.increased.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_effect_and_full_life_conditions() {
+        use crate::types::StatCondition;
+        let conditional = |id: &str, condition: StatCondition| {
+            Effect::new_stat_modifier(
+                id,
+                id,
+                60.0,
+                false,
+                vec![StatMod::new(StatType::FireResistance, 10.0, false).with_condition(condition)],
+                "player",
+            )
+        };
+        let mut block = StatBlock::with_id("player");
+        block.add_effect(conditional(
+            "fortify_bonus",
+            StatCondition::WhileEffectActive("fortify".to_string()),
+        ));
+        block.add_effect(conditional("pristine", StatCondition::FullLife));
+        block.rebuild();
+        assert!((block.fire_resistance.compute() - 10.0).abs() < 1e-9);
+
+        block.add_effect(conditional("fortify", StatCondition::LowLife(0.0)));
+        block.rebuild();
+        assert!((block.fire_resistance.compute() - 20.0).abs() < 1e-9);
+
+        block.current_life -= 1.0;
+        block.rebuild();
+        assert!((block.fire_resistance.compute() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_expires_on_hit_effect_breaks_only_when_damaged() {
+        use crate::combat::resolve_damage_with_rng;
+        use rand::SeedableRng;
+        crate::config::ensure_constants_initialized();
+        crate::config::ensure_dot_registry_initialized();
+
+        let mut defender = StatBlock::with_id("defender");
+        defender.max_life.base = 1000.0;
+        defender.add_effect(
+            Effect::new_stat_modifier(
+                "stealth",
+                "Stealth",
+                10.0,
+                false,
+                vec![StatMod::new(StatType::AddedEvasion, 500.0, false)],
+                "defender",
+            )
+            .expiring_on_hit(),
+        );
+        defender.rebuild();
+        defender.current_life = 1000.0;
+        defender.spell_dodge_chance = 75.0;
+        let evasion = defender.evasion.compute();
+
+        // A dodged spell deals nothing, so the buff holds
+        let spell = DamagePacket::builder("enemy", "bolt")
+            .damage(DamageType::Fire, 50.0)
+            .spell(true)
+            .build();
+        let (dodged, result) = (0..100)
+            .map(|seed| {
+                let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+                resolve_damage_with_rng(&defender, &spell, &mut rng)
+            })
+            .find(|(_, result)| result.was_dodged)
+            .unwrap();
+        assert!(result.effects_broken.is_empty());
+        assert_eq!(dodged.active_effects().len(), 1);
+
+        // An attack that lands breaks it and drops its stats
+        let attack = DamagePacket::builder("enemy", "slash")
+            .damage(DamageType::Physical, 50.0)
+            .build();
+        let (hit, result) = defender.receive_damage(&attack);
+        assert!(result.total_damage > 0.0);
+        assert_eq!(result.effects_broken.len(), 1);
+        assert_eq!(result.effects_broken[0].id, "stealth");
+        assert!(hit.active_effects().is_empty());
+        assert!((evasion - hit.evasion.compute() - 500.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_non_lethal_dot_stops_at_one_life() {
+        let bleed = |lethal: bool| {
+            Effect::new_ailment(
+                "bleed",
+                "Bleed",
+                StatusEffect::Bleed,
+                4.0,
+                1.0,
+                100.0,
+                1.0,
+                AilmentStacking::Unlimited,
+                lethal,
+                "enemy",
+            )
+        };
+        let mut block = StatBlock::with_id("duelist");
+        block.current_life = 30.0;
+        block.add_effect(bleed(false));
+
+        let (after, result) = block.tick_effects(1.0);
+        assert!((after.current_life - 1.0).abs() < 1e-9);
+        assert!(!result.is_dead);
+        let (after, result) = after.tick_effects(1.0);
+        assert!((after.current_life - 1.0).abs() < 1e-9);
+        assert!(!result.is_dead);
+
+        // A lethal DoT still finishes the job
+        block.add_effect(bleed(true));
+        let (after, result) = block.tick_effects(1.0);
+        assert_eq!(after.current_life, 0.0);
+        assert!(result.is_dead);
+    }
+
+    #[test]
+    fn test_execute_dot_scales_with_missing_life() {
+        let registry =
+            crate::config::load_dot_configs(std::path::Path::new("../config/dots.toml")).unwrap();
+        let mut config = registry.get("poison").unwrap().clone();
+        config.scales_with_missing_life = 0.5;
+        let poison = Effect::from_config(&config, StatusEffect::Poison, 4.0, 1.0, 5.0, "enemy");
+
+        let mut block = StatBlock::with_id("player");
+        block.add_effect(poison);
+        // Three 0.33s ticks in one second at full life
+        let (_, full) = block.tick_effects(1.0);
+        assert!((full.dot_damage - 4.95).abs() < 1e-9);
+
+        // At 20% life: 1 + 0.5 * 0.8 = 1.4x
+        block.current_life = 10.0;
+        let (after, low) = block.tick_effects(1.0);
+        assert!((low.dot_damage - 4.95 * 1.4).abs() < 1e-9);
+        assert!((after.current_life - (10.0 - 4.95 * 1.4)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_clamp_current_resources_on_max_life_change() {
+        let mut block = StatBlock::with_id("player");
+        block.current_life = 30.0;
+        let buff = || {
+            BuffSource::new("vitality".to_string(), "Vitality".to_string(), 10.0, false)
+                .with_modifier(StatType::AddedLife, 200.0, false)
+        };
+
+        // Gaining max life doesn't heal
+        block.apply_buff(buff());
+        assert!((block.computed_max_life() - 250.0).abs() < 1e-9);
+        assert!((block.current_life - 30.0).abs() < 1e-9);
+
+        // Losing it clamps current life to the new max
+        block.current_life = 240.0;
+        block.remove_buff("vitality");
+        assert!((block.computed_max_life() - 50.0).abs() < 1e-9);
+        assert!((block.current_life - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rebuild_keeps_all_runtime_state() {
+        let mut block = StatBlock::with_id("hero");
+        block.equip(EquipmentSlot::Ring1, ring_with_added_fire(5));
+        let vigor = BuffSource::new("vigor".to_string(), "Vigor".to_string(), 60.0, false);
+        block.apply_buff(vigor.with_modifier(StatType::AddedLife, 100.0, false));
+        block.set_template(
+            TemplateSource::new("brute").with_modifier(StatType::AddedEnergyShield, 80.0),
+        );
+        let haste = Effect::new_stat_modifier(
+            "haste",
+            "Haste",
+            60.0,
+            false,
+            vec![StatMod::new(StatType::IncreasedAttackSpeed, 10.0, false)],
+            "player",
+        );
+        block.add_effect(haste.clone());
+        block.add_on_kill_effect(haste);
+        block
+            .damage_per_attribute
+            .push((Attribute::Strength, DamageType::Physical, 0.5));
+        block.cooldowns.start("slam", 3.0);
+        block.status_buildup.insert(StatusEffect::Freeze, 12.0);
+        block.current_life = 60.0;
+        block.current_mana = 20.0;
+        block.current_energy_shield = 40.0;
+        block.current_ward = 30.0;
+        block.life_leeched_recently = 5.0;
+        block.mana_leeched_recently = 3.0;
+        block.chaos_bypasses_es = !block.chaos_bypasses_es;
+        block.converted_damage_double_scaling = !block.converted_damage_double_scaling;
+        block.reflect_basis = match block.reflect_basis {
+            ReflectBasis::Raw => ReflectBasis::Landed,
+            ReflectBasis::Landed => ReflectBasis::Raw,
+        };
+        block.is_moving = !block.is_moving;
+        block.stun_immune = !block.stun_immune;
+        block.ailment_avoid_chance.insert(StatusEffect::Bleed, 40.0);
+        block.all_ailment_avoid = 25.0;
+        block.resistance_reduction_floor = !block.resistance_reduction_floor;
+        block.resistance_modifiers.insert(DamageType::Fire, -10.0);
+
+        let before = block.clone();
+        block.rebuild();
+
+        assert_eq!(block.id, before.id);
+        assert_eq!(block.equipped_items.len(), 1);
+        assert!(block.equipped(EquipmentSlot::Ring1).is_some());
+        assert_eq!(block.buff_sources.len(), 1);
+        assert_eq!(block.template, before.template);
+        assert_eq!(block.damage_per_attribute, before.damage_per_attribute);
+        let ids = |effects: &[Effect]| effects.iter().map(|e| e.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&block.effects), ids(&before.effects));
+        assert_eq!(ids(&block.on_kill_effects), ids(&before.on_kill_effects));
+        assert!((block.cooldowns.remaining("slam") - 3.0).abs() < 1e-9);
+        assert_eq!(block.status_buildup, before.status_buildup);
+        assert!((block.current_life - 60.0).abs() < 1e-9);
+        assert!((block.current_mana - 20.0).abs() < 1e-9);
+        assert!((block.current_energy_shield - 40.0).abs() < 1e-9);
+        assert!((block.current_ward - 30.0).abs() < 1e-9);
+        assert!((block.life_leeched_recently - 5.0).abs() < 1e-9);
+        assert!((block.mana_leeched_recently - 3.0).abs() < 1e-9);
+        assert_eq!(block.chaos_bypasses_es, before.chaos_bypasses_es);
+        assert_eq!(
+            block.converted_damage_double_scaling,
+            before.converted_damage_double_scaling
+        );
+        assert_eq!(block.reflect_basis, before.reflect_basis);
+        assert_eq!(block.is_moving, before.is_moving);
+        assert_eq!(block.stun_immune, before.stun_immune);
+        assert_eq!(block.ailment_avoid_chance, before.ailment_avoid_chance);
+        assert!((block.all_ailment_avoid - 25.0).abs() < 1e-9);
+        assert_eq!(
+            block.resistance_reduction_floor,
+            before.resistance_reduction_floor
+        );
+        assert_eq!(block.resistance_modifiers, before.resistance_modifiers);
+    }
+}
@@ -0,0 +1,64 @@
+//! Shared item fixtures for unit tests
+
+use loot_core::item::{Defenses, Modifier};
+use loot_core::types::{AffixScope, ItemClass, Requirements, StatType};
+use loot_core::Item;
+
+/// A normal item with no affixes, defenses or weapon damage
+pub(crate) fn item(base_type_id: &str, class: ItemClass) -> Item {
+    Item {
+        seed: 1,
+        operations: vec![],
+        base_type_id: base_type_id.to_string(),
+        name: base_type_id.to_string(),
+        base_name: base_type_id.to_string(),
+        class,
+        rarity: "normal".to_string(),
+        tags: vec![],
+        requirements: Requirements::default(),
+        item_level: 1,
+        implicit: None,
+        prefixes: vec![],
+        suffixes: vec![],
+        defenses: Defenses::default(),
+        damage: None,
+        granted_skills: vec![],
+        influence: None,
+    }
+}
+
+/// A tier 1 modifier rolled at `value` (and `value_max` for ranges)
+pub(crate) fn modifier(
+    stat: StatType,
+    scope: AffixScope,
+    value: i32,
+    value_max: Option<i32>,
+) -> Modifier {
+    Modifier {
+        affix_id: format!("{:?}", stat),
+        name: format!("{:?}", stat),
+        stat,
+        scope,
+        tier: 1,
+        value,
+        value_max,
+        tier_min: value,
+        tier_max: value,
+        tier_max_value: None,
+        granted_skills: vec![],
+        scaling: None,
+    }
+}
+
+/// A magic ring with a single global added fire damage prefix
+pub(crate) fn ring_with_added_fire(value: i32) -> Item {
+    let mut ring = item("gold_ring", ItemClass::Ring);
+    ring.rarity = "magic".to_string();
+    ring.prefixes.push(modifier(
+        StatType::AddedFireDamage,
+        AffixScope::Global,
+        value,
+        None,
+    ));
+    ring
+}
@@ -1,7 +1,8 @@
 //! Core types specific to stat_manager
 
 use crate::dot::{DotConfig, DotStacking};
-use loot_core::types::StatusEffect;
+use crate::stat_block::StatAccumulator;
+use loot_core::types::{DamageType, StatType, StatusEffect};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -28,6 +29,44 @@ pub struct Effect {
     pub max_stacks: u32,
     /// Source entity ID that applied this effect
     pub source_id: String,
+    /// Resource reserved while this effect is active (e.g. auras)
+    #[serde(default)]
+    pub reservation: Option<Reservation>,
+    /// Removed as soon as a hit deals damage to the owner (e.g. stealth)
+    #[serde(default)]
+    pub expires_on_hit: bool,
+    /// Comes from a consumable (e.g. a flask); buffs scale with the owner's
+    /// increased consumable effect
+    #[serde(default)]
+    pub is_consumable: bool,
+}
+
+/// A resource reservation held by an effect, as a percent of the maximum
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reservation {
+    /// Percent of maximum mana reserved
+    Mana(f64),
+    /// Percent of maximum life reserved
+    Life(f64),
+}
+
+/// Which damage a defender's reflect is computed from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReflectBasis {
+    /// The hit's damage before any mitigation
+    Raw,
+    /// Damage that landed after mitigation, evasion and block
+    #[default]
+    Landed,
+}
+
+/// What an applied DoT needs to recompute its DPS from its source's stats
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DotOrigin {
+    /// DPS before the source's DoT scaling (base damage percent * status damage)
+    pub base_dps: f64,
 }
 
 /// The type of effect - either stat modifiers or ailments
@@ -39,6 +78,12 @@ pub enum EffectType {
         modifiers: Vec<StatMod>,
         /// Whether this is a debuff (negative effect)
         is_debuff: bool,
+        /// How re-applications with the same ID stack
+        #[serde(default = "default_stat_modifier_stacking")]
+        stacking: AilmentStacking,
+        /// Seconds each added stack extends the effect's duration by
+        #[serde(default)]
+        duration_per_stack: f64,
     },
     /// Ailment effect (status effect like poison, bleed, etc.)
     Ailment {
@@ -56,9 +101,40 @@ pub enum EffectType {
         stacking: AilmentStacking,
         /// Effectiveness multiplier (for stacking)
         effectiveness: f64,
+        /// Whether this ailment was applied by a critical strike
+        #[serde(default)]
+        from_crit: bool,
+        /// Set when the DoT recomputes its DPS from the source's stats
+        #[serde(default)]
+        origin: Option<DotOrigin>,
+        /// Whether ticks can kill; non-lethal DoTs stop at 1 life
+        #[serde(default = "default_lethal")]
+        lethal: bool,
+        /// Extra tick damage per missing life fraction of the bearer
+        #[serde(default)]
+        scales_with_missing_life: f64,
+    },
+    /// Penetration added to the owner's outgoing hits while active
+    /// (e.g. a banner: "enemies you hit have -15% fire resistance")
+    Penetration {
+        /// Damage type the penetration applies to
+        damage_type: DamageType,
+        /// Penetration amount per stack (percent)
+        amount: f64,
     },
 }
 
+fn default_lethal() -> bool {
+    true
+}
+
+/// Default stacking for stat modifier effects: re-applying adds a stack
+fn default_stat_modifier_stacking() -> AilmentStacking {
+    AilmentStacking::Limited {
+        stack_effectiveness: 1.0,
+    }
+}
+
 /// A stat modifier from an effect
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatMod {
@@ -68,12 +144,79 @@ pub struct StatMod {
     pub value_per_stack: f64,
     /// Whether this is a "more" multiplier
     pub is_more: bool,
+    /// Only applies while this condition holds (checked at rebuild)
+    #[serde(default)]
+    pub condition: Option<StatCondition>,
+}
+
+impl StatMod {
+    /// Create an unconditional stat modifier
+    pub fn new(stat: loot_core::types::StatType, value_per_stack: f64, is_more: bool) -> Self {
+        StatMod {
+            stat,
+            value_per_stack,
+            is_more,
+            condition: None,
+        }
+    }
+
+    /// Only apply this modifier while `condition` holds
+    pub fn with_condition(mut self, condition: StatCondition) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+}
+
+/// A condition gating a StatMod
+///
+/// Conditions are evaluated when stats are rebuilt, not on every change to
+/// current life or energy shield. After `take_damage`, `heal`, ES recharge
+/// or resolving a hit, call `rebuild()` for conditional modifiers to pick up
+/// the new state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatCondition {
+    /// Current energy shield is at its maximum (and the maximum is above 0)
+    FullEnergyShield,
+    /// An effect with this id is active on the block
+    WhileEffectActive(String),
+    /// Life is below this percentage of maximum (0-100)
+    LowLife(f64),
+    /// Life is at its maximum
+    FullLife,
+}
+
+impl StatCondition {
+    /// Whether the condition holds for a block in the given state
+    pub fn is_met(&self, state: &ConditionState) -> bool {
+        match self {
+            StatCondition::FullEnergyShield => state.full_energy_shield,
+            StatCondition::WhileEffectActive(id) => state.active_effects.contains(id),
+            StatCondition::LowLife(threshold) => state.life_percent < *threshold,
+            StatCondition::FullLife => state.full_life,
+        }
+    }
+}
+
+/// Snapshot of a StatBlock's state used to evaluate StatConditions
+///
+/// Captured at the start of a rebuild, before maxes are reset, so it only
+/// changes when the block is rebuilt.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConditionState {
+    pub full_energy_shield: bool,
+    pub full_life: bool,
+    /// Current life as a percentage of maximum (0-100)
+    pub life_percent: f64,
+    /// Ids of active effects
+    pub active_effects: Vec<String>,
 }
 
 /// How ailments stack
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub enum AilmentStacking {
     /// Only the strongest instance applies
+    #[default]
     StrongestOnly,
     /// Unlimited stacking
     Unlimited,
@@ -84,12 +227,6 @@ pub enum AilmentStacking {
     },
 }
 
-impl Default for AilmentStacking {
-    fn default() -> Self {
-        AilmentStacking::StrongestOnly
-    }
-}
-
 /// Result of processing effect ticks
 #[derive(Debug, Clone, Default)]
 pub struct TickResult {
@@ -121,16 +258,88 @@ impl Effect {
             effect_type: EffectType::StatModifier {
                 modifiers,
                 is_debuff,
+                stacking: default_stat_modifier_stacking(),
+                duration_per_stack: 0.0,
             },
             duration_remaining: duration,
             total_duration: duration,
             stacks: 1,
             max_stacks: 1,
             source_id: source_id.into(),
+            reservation: None,
+            expires_on_hit: false,
+            is_consumable: false,
+        }
+    }
+
+    /// Create a resistance reduction ("exposure") debuff
+    ///
+    /// Lowers the target's resistance to `element` by `amount` while active.
+    /// Unlike per-hit penetration this is a debuff on the target. Exposures to
+    /// the same element share an ID and use StrongestOnly stacking, so the
+    /// largest reduction wins and re-application refreshes the duration.
+    /// Physical exposure reduces physical damage reduction instead.
+    pub fn new_resistance_reduction(
+        element: DamageType,
+        amount: f64,
+        duration: f64,
+        source_id: impl Into<String>,
+    ) -> Self {
+        let stat = match element {
+            DamageType::Physical => StatType::PhysicalDamageReduction,
+            DamageType::Fire => StatType::FireResistance,
+            DamageType::Cold => StatType::ColdResistance,
+            DamageType::Lightning => StatType::LightningResistance,
+            DamageType::Chaos => StatType::ChaosResistance,
+        };
+
+        let mut effect = Effect::new_stat_modifier(
+            format!("exposure_{}", element.to_string().to_lowercase()),
+            format!("{} Exposure", element),
+            duration,
+            true,
+            vec![StatMod::new(stat, -amount, false)],
+            source_id,
+        );
+        if let EffectType::StatModifier { stacking, .. } = &mut effect.effect_type {
+            *stacking = AilmentStacking::StrongestOnly;
+        }
+        effect
+    }
+
+    /// Create an outgoing penetration effect (buff on the attacker)
+    ///
+    /// While active, hits from the owner penetrate `amount` of the target's
+    /// `damage_type` resistance. Physical has no resistance to penetrate, so
+    /// physical penetration effects have no effect on packets.
+    pub fn new_penetration(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        damage_type: DamageType,
+        amount: f64,
+        duration: f64,
+        source_id: impl Into<String>,
+    ) -> Self {
+        Effect {
+            id: id.into(),
+            name: name.into(),
+            effect_type: EffectType::Penetration {
+                damage_type,
+                amount,
+            },
+            duration_remaining: duration,
+            total_duration: duration,
+            stacks: 1,
+            max_stacks: 1,
+            source_id: source_id.into(),
+            reservation: None,
+            expires_on_hit: false,
+            is_consumable: false,
         }
     }
 
     /// Create a new ailment effect
+    #[allow(clippy::too_many_arguments)]
     pub fn new_ailment(
         id: impl Into<String>,
         name: impl Into<String>,
@@ -140,6 +349,7 @@ impl Effect {
         dot_dps: f64,
         tick_rate: f64,
         stacking: AilmentStacking,
+        lethal: bool,
         source_id: impl Into<String>,
     ) -> Self {
         Effect {
@@ -153,12 +363,19 @@ impl Effect {
                 time_until_tick: tick_rate,
                 stacking,
                 effectiveness: 1.0,
+                from_crit: false,
+                origin: None,
+                lethal,
+                scales_with_missing_life: 0.0,
             },
             duration_remaining: duration,
             total_duration: duration,
             stacks: 1,
             max_stacks: 999,
             source_id: source_id.into(),
+            reservation: None,
+            expires_on_hit: false,
+            is_consumable: false,
         }
     }
 
@@ -194,10 +411,103 @@ impl Effect {
             dot_dps,
             config.tick_rate,
             stacking,
+            config.lethal,
             source_id,
         );
         effect.max_stacks = config.max_stacks;
-        effect
+        effect.with_missing_life_scaling(config.scales_with_missing_life)
+    }
+
+    /// Reserve a resource while this effect is active
+    pub fn with_reservation(mut self, reservation: Reservation) -> Self {
+        self.reservation = Some(reservation);
+        self
+    }
+
+    /// Break this effect when its owner takes damage from a hit
+    pub fn expiring_on_hit(mut self) -> Self {
+        self.expires_on_hit = true;
+        self
+    }
+
+    /// Mark this effect as coming from a consumable
+    pub fn consumable(mut self) -> Self {
+        self.is_consumable = true;
+        self
+    }
+
+    /// Extend a stat modifier's duration with each added stack (no-op for
+    /// other effects)
+    pub fn with_duration_per_stack(mut self, seconds: f64) -> Self {
+        if let EffectType::StatModifier {
+            duration_per_stack, ..
+        } = &mut self.effect_type
+        {
+            *duration_per_stack = seconds;
+        }
+        self
+    }
+
+    /// Mark an ailment as applied by a critical strike (no-op for other effects)
+    pub fn with_crit_origin(mut self, crit: bool) -> Self {
+        if let EffectType::Ailment { from_crit, .. } = &mut self.effect_type {
+            *from_crit = crit;
+        }
+        self
+    }
+
+    /// Let an ailment recompute its DPS from its source (no-op for other effects)
+    pub fn with_dot_origin(mut self, dot_origin: DotOrigin) -> Self {
+        if let EffectType::Ailment { origin, .. } = &mut self.effect_type {
+            *origin = Some(dot_origin);
+        }
+        self
+    }
+
+    /// Scale an ailment's ticks with its bearer's missing life (no-op for
+    /// other effects)
+    pub fn with_missing_life_scaling(mut self, factor: f64) -> Self {
+        if let EffectType::Ailment {
+            scales_with_missing_life,
+            ..
+        } = &mut self.effect_type
+        {
+            *scales_with_missing_life = factor;
+        }
+        self
+    }
+
+    /// Tick damage multiplier for a bearer at `life_percent` (0-100) life
+    ///
+    /// Matches `DotConfig::missing_life_multiplier`; 1.0 for effects that
+    /// don't scale with missing life.
+    pub fn missing_life_multiplier(&self, life_percent: f64) -> f64 {
+        match &self.effect_type {
+            EffectType::Ailment {
+                scales_with_missing_life,
+                ..
+            } => {
+                let missing = 1.0 - (life_percent / 100.0).clamp(0.0, 1.0);
+                1.0 + scales_with_missing_life * missing
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Whether this ailment was applied by a critical strike
+    pub fn is_from_crit(&self) -> bool {
+        matches!(
+            self.effect_type,
+            EffectType::Ailment {
+                from_crit: true,
+                ..
+            }
+        )
+    }
+
+    /// Whether this effect's damage can kill (false only for non-lethal DoTs)
+    pub fn is_lethal(&self) -> bool {
+        !matches!(self.effect_type, EffectType::Ailment { lethal: false, .. })
     }
 
     /// Check if the effect is still active
@@ -205,11 +515,112 @@ impl Effect {
         self.duration_remaining > 0.0 && self.stacks > 0
     }
 
+    /// Apply this effect's stat modifiers to an accumulator (no-op for ailments)
+    pub fn apply_modifiers(&self, stats: &mut StatAccumulator, conditions: &ConditionState) {
+        self.apply_modifiers_scaled(stats, 1.0, conditions);
+    }
+
+    /// Apply this effect's stat modifiers, scaling buffs (not debuffs) by
+    /// `buff_effect` — the receiver's increased effect of buffs on them
+    ///
+    /// Modifiers whose condition isn't met by `conditions` are skipped.
+    pub fn apply_modifiers_scaled(
+        &self,
+        stats: &mut StatAccumulator,
+        buff_effect: f64,
+        conditions: &ConditionState,
+    ) {
+        if !self.is_active() {
+            return;
+        }
+
+        if let EffectType::StatModifier {
+            modifiers,
+            is_debuff,
+            ..
+        } = &self.effect_type
+        {
+            let effect_mult = if *is_debuff { 1.0 } else { buff_effect };
+            let stack_mult = self.stacks as f64 * effect_mult;
+            for modifier in modifiers {
+                if let Some(condition) = &modifier.condition {
+                    if !condition.is_met(conditions) {
+                        continue;
+                    }
+                }
+                let total_value = modifier.value_per_stack * stack_mult;
+                if modifier.is_more {
+                    stats.apply_more_stat_type(modifier.stat, total_value);
+                } else {
+                    stats.apply_stat_type(modifier.stat, total_value);
+                }
+            }
+        }
+    }
+
+    /// Raw total this effect adds to `stat` under `conditions`, before buff
+    /// effect scaling and not counting "more" modifiers (0 for ailments)
+    pub(crate) fn stat_total(&self, stat: StatType, conditions: &ConditionState) -> f64 {
+        if !self.is_active() {
+            return 0.0;
+        }
+        match &self.effect_type {
+            EffectType::StatModifier { modifiers, .. } => modifiers
+                .iter()
+                .filter(|m| m.stat == stat && !m.is_more)
+                .filter(|m| m.condition.as_ref().is_none_or(|c| c.is_met(conditions)))
+                .map(|m| m.value_per_stack * self.stacks as f64)
+                .sum(),
+            _ => 0.0,
+        }
+    }
+
+    /// Total absolute stat change of a stat modifier effect (0 for ailments)
+    ///
+    /// Used to compare strength for StrongestOnly stacking.
+    pub fn modifier_magnitude(&self) -> f64 {
+        match &self.effect_type {
+            EffectType::StatModifier { modifiers, .. } => {
+                modifiers
+                    .iter()
+                    .map(|m| m.value_per_stack.abs())
+                    .sum::<f64>()
+                    * self.stacks as f64
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Penetration this effect adds to outgoing hits of a damage type
+    pub fn penetration(&self, damage_type: DamageType) -> f64 {
+        if !self.is_active() {
+            return 0.0;
+        }
+        match &self.effect_type {
+            EffectType::Penetration {
+                damage_type: effect_type,
+                amount,
+            } if *effect_type == damage_type => amount * self.stacks as f64,
+            _ => 0.0,
+        }
+    }
+
     /// Check if this is a stat modifier effect
     pub fn is_stat_modifier(&self) -> bool {
         matches!(self.effect_type, EffectType::StatModifier { .. })
     }
 
+    /// Check if this is a stat modifier debuff
+    pub fn is_debuff(&self) -> bool {
+        matches!(
+            self.effect_type,
+            EffectType::StatModifier {
+                is_debuff: true,
+                ..
+            }
+        )
+    }
+
     /// Check if this is an ailment effect
     pub fn is_ailment(&self) -> bool {
         matches!(self.effect_type, EffectType::Ailment { .. })
@@ -263,10 +674,24 @@ impl Effect {
         (self.duration_remaining / self.total_duration * 100.0).clamp(0.0, 100.0)
     }
 
-    /// Add a stack (capped at max_stacks)
+    /// Seconds each stack beyond the first adds to this effect's duration
+    pub fn duration_per_stack(&self) -> f64 {
+        match &self.effect_type {
+            EffectType::StatModifier {
+                duration_per_stack, ..
+            } => *duration_per_stack,
+            _ => 0.0,
+        }
+    }
+
+    /// Add a stack (capped at max_stacks), extending the duration by
+    /// `duration_per_stack` when one is added
     pub fn add_stack(&mut self) {
         if self.stacks < self.max_stacks {
             self.stacks += 1;
+            let extra = self.duration_per_stack();
+            self.duration_remaining += extra;
+            self.total_duration += extra;
         }
     }
 
@@ -281,23 +706,21 @@ impl Effect {
     pub fn tick(&mut self, delta: f64) -> f64 {
         let mut damage_dealt = 0.0;
 
-        match &mut self.effect_type {
-            EffectType::Ailment {
-                time_until_tick,
-                tick_rate,
-                dot_dps,
-                effectiveness,
-                ..
-            } => {
-                if *dot_dps > 0.0 {
-                    *time_until_tick -= delta;
-                    while *time_until_tick <= 0.0 && self.duration_remaining > 0.0 {
-                        damage_dealt += *dot_dps * *tick_rate * self.stacks as f64 * *effectiveness;
-                        *time_until_tick += *tick_rate;
-                    }
+        if let EffectType::Ailment {
+            time_until_tick,
+            tick_rate,
+            dot_dps,
+            effectiveness,
+            ..
+        } = &mut self.effect_type
+        {
+            if *dot_dps > 0.0 {
+                *time_until_tick -= delta;
+                while *time_until_tick <= 0.0 && self.duration_remaining > 0.0 {
+                    damage_dealt += *dot_dps * *tick_rate * self.stacks as f64 * *effectiveness;
+                    *time_until_tick += *tick_rate;
                 }
             }
-            _ => {}
         }
 
         self.duration_remaining -= delta;
@@ -359,6 +782,10 @@ pub enum SkillTag {
     Projectile,
     // Area
     Aoe,
+    // Alternate delivery
+    Trap,
+    Mine,
+    Totem,
 }
 
 impl fmt::Display for SkillTag {
@@ -376,6 +803,9 @@ impl fmt::Display for SkillTag {
             SkillTag::Ranged => write!(f, "Ranged"),
             SkillTag::Projectile => write!(f, "Projectile"),
             SkillTag::Aoe => write!(f, "AoE"),
+            SkillTag::Trap => write!(f, "Trap"),
+            SkillTag::Mine => write!(f, "Mine"),
+            SkillTag::Totem => write!(f, "Totem"),
         }
     }
 }
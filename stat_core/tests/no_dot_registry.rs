@@ -0,0 +1,42 @@
+//! Damage calculation before any DoT registry is loaded
+//!
+//! Runs in its own test binary so nothing else initializes the global
+//! registry first.
+
+use rand::SeedableRng;
+use stat_core::config::dot_registry_initialized;
+use stat_core::damage::{calculate_damage, calculate_skill_dps, SkillStatusConversions};
+use stat_core::{BaseDamage, DamagePacketGenerator, DamageType, StatBlock, StatusEffect};
+
+#[test]
+fn test_status_conversion_without_dot_registry() {
+    assert!(!dot_registry_initialized());
+
+    let attacker = StatBlock::new();
+    let skill = DamagePacketGenerator {
+        id: "fire_skill".to_string(),
+        name: "Fire Skill".to_string(),
+        base_damages: vec![BaseDamage::new(DamageType::Fire, 100.0, 100.0)],
+        weapon_effectiveness: 0.0,
+        status_conversions: SkillStatusConversions {
+            fire_to_burn: 0.5,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+    let packet = calculate_damage(&attacker, &skill, "player".to_string(), &mut rng);
+
+    let burn = packet
+        .status_effects_to_apply
+        .iter()
+        .find(|s| s.effect_type == StatusEffect::Burn)
+        .expect("fire damage should convert to burn");
+    assert!((burn.status_damage - 50.0).abs() < 1e-9);
+    assert!(burn.duration > 0.0);
+
+    // No configs means no DoT damage, leaving only the hit
+    assert!(calculate_skill_dps(&attacker, &skill) > 0.0);
+    assert!(!dot_registry_initialized());
+}
@@ -43,21 +43,30 @@ pub struct EntryConfig {
     #[serde(default)]
     pub currencies: Vec<String>,
 
-    // Currency-specific fields
+    // Currency-specific fields (`id` is also used by unique and table entries)
     #[serde(default)]
     pub id: Option<String>,
     #[serde(default)]
     pub count: Option<CountConfig>,
     // For single count value (not a range)
     // This allows `count = 5` instead of `count = [5, 5]`
+
+    // Table-specific fields
+    /// Independent rolls of the referenced table per selection (default 1)
+    #[serde(default)]
+    pub rolls: Option<u32>,
 }
 
-/// Count can be a single value or a range [min, max]
+/// Count can be a single value, a range [min, max], or weighted values
 #[derive(Debug, Clone, Deserialize)]
 #[serde(untagged)]
 pub enum CountConfig {
     Single(u32),
     Range([u32; 2]),
+    /// `[value, weight]` pairs, e.g. `{ weights = [[10, 50], [100, 5]] }`
+    Weighted {
+        weights: Vec<[u32; 2]>,
+    },
 }
 
 impl CountConfig {
@@ -65,6 +74,7 @@ impl CountConfig {
         match self {
             CountConfig::Single(v) => *v,
             CountConfig::Range([min, _]) => *min,
+            CountConfig::Weighted { weights } => weights.iter().map(|[v, _]| *v).min().unwrap_or(0),
         }
     }
 
@@ -72,6 +82,7 @@ impl CountConfig {
         match self {
             CountConfig::Single(v) => *v,
             CountConfig::Range([_, max]) => *max,
+            CountConfig::Weighted { weights } => weights.iter().map(|[v, _]| *v).max().unwrap_or(0),
         }
     }
 }
@@ -85,7 +85,7 @@ mod tests {
 
     #[test]
     fn test_get_items() {
-        let drops = vec![
+        let drops = [
             Drop::Item {
                 base_type: "sword".into(),
                 currencies: vec!["transmute".into()],
@@ -108,7 +108,7 @@ mod tests {
 
     #[test]
     fn test_get_currencies() {
-        let drops = vec![
+        let drops = [
             Drop::Item {
                 base_type: "sword".into(),
                 currencies: vec![],
@@ -132,7 +132,7 @@ mod tests {
 
     #[test]
     fn test_get_uniques() {
-        let drops = vec![
+        let drops = [
             Drop::Unique {
                 id: "starforge".into(),
             },
@@ -0,0 +1,110 @@
+use crate::drop::Drop;
+use crate::registry::DropTableRegistry;
+use crate::table::RollOptions;
+use crate::GenerateError;
+use loot_core::{GenerateOptions, Generator, Item};
+use rand::Rng;
+
+/// A rolled drop with its item already generated
+#[derive(Debug, Clone)]
+pub enum GeneratedDrop {
+    /// A generated item with the table's currencies applied
+    Item(Box<Item>),
+    /// A currency drop with a count
+    Currency { id: String, count: u32 },
+    /// A unique item, left for the caller to generate
+    Unique { id: String },
+}
+
+/// Roll a table and generate every item drop
+///
+/// Items are generated at `opts.level` with a seed drawn from `rng`, then
+/// have their listed currencies applied in order, so the same RNG state
+/// always produces the same items. Currency and unique drops are passed
+/// through unchanged.
+pub fn roll_and_generate<R: Rng>(
+    registry: &DropTableRegistry,
+    generator: &Generator,
+    table_id: &str,
+    opts: &RollOptions,
+    rng: &mut R,
+) -> Result<Vec<GeneratedDrop>, GenerateError> {
+    let drops = registry.roll_with_options(table_id, opts, rng)?;
+
+    drops
+        .into_iter()
+        .map(|drop| match drop {
+            Drop::Item {
+                base_type,
+                currencies,
+            } => generate_item(generator, &base_type, &currencies, opts.level, rng.gen())
+                .map(|item| GeneratedDrop::Item(Box::new(item)))
+                .map_err(|error| GenerateError::Item { base_type, error }),
+            Drop::Currency { id, count } => Ok(GeneratedDrop::Currency { id, count }),
+            Drop::Unique { id } => Ok(GeneratedDrop::Unique { id }),
+        })
+        .collect()
+}
+
+fn generate_item(
+    generator: &Generator,
+    base_type: &str,
+    currencies: &[String],
+    level: u32,
+    seed: u64,
+) -> Result<Item, loot_core::GeneratorError> {
+    let options = GenerateOptions {
+        item_level: Some(level),
+        ..Default::default()
+    };
+    let mut item = generator.generate_with_options(base_type, seed, &options)?;
+    for currency in currencies {
+        item = generator.apply_currency(&item, currency)?;
+    }
+    Ok(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use loot_core::Config;
+    use rand::SeedableRng;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_roll_and_generate_item() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("chest.toml"),
+            r#"
+[table]
+id = "chest"
+
+[[entries]]
+type = "item"
+base_type = "iron_sword"
+currencies = ["transmute"]
+weight = 1
+"#,
+        )
+        .unwrap();
+        let registry = DropTableRegistry::load(dir.path()).unwrap();
+        let generator = Generator::new(Config::load_from_dir(Path::new("../config")).unwrap());
+
+        let opts = RollOptions {
+            level: 20,
+            ..Default::default()
+        };
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let drops = roll_and_generate(&registry, &generator, "chest", &opts, &mut rng).unwrap();
+
+        assert_eq!(drops.len(), 1);
+        let GeneratedDrop::Item(item) = &drops[0] else {
+            panic!("expected an item drop");
+        };
+        assert_eq!(item.base_type_id, "iron_sword");
+        assert_eq!(item.item_level, 20);
+        assert_eq!(item.rarity, "magic");
+    }
+}
@@ -1,10 +1,13 @@
 mod config;
 mod drop;
+mod generate;
 mod registry;
 mod table;
 
 pub use drop::{CurrencyDrop, Drop, DropsExt, ItemDrop, UniqueDrop};
+pub use generate::{roll_and_generate, GeneratedDrop};
 pub use registry::DropTableRegistry;
+pub use table::RollOptions;
 
 use std::path::PathBuf;
 use thiserror::Error;
@@ -36,3 +39,15 @@ pub enum RollError {
     #[error("Invalid entry type: {0}")]
     InvalidEntryType(String),
 }
+
+/// Error rolling a drop table and generating its items
+#[derive(Debug, Error)]
+pub enum GenerateError {
+    #[error(transparent)]
+    Roll(#[from] RollError),
+    #[error("Failed to generate '{base_type}': {error}")]
+    Item {
+        base_type: String,
+        error: loot_core::GeneratorError,
+    },
+}
@@ -1,6 +1,6 @@
 use crate::config::TableFileConfig;
 use crate::drop::Drop;
-use crate::table::DropTable;
+use crate::table::{DropTable, RollOptions};
 use crate::{ConfigError, RollError};
 use rand::Rng;
 use std::collections::HashMap;
@@ -97,12 +97,67 @@ impl DropTableRegistry {
         quantity_mult: f64,
         level: u32,
         rng: &mut R,
+    ) -> Result<Vec<Drop>, RollError> {
+        let opts = RollOptions {
+            rarity_mult,
+            quantity_mult,
+            level,
+            ..Default::default()
+        };
+        self.roll_with_options(table_id, &opts, rng)
+    }
+
+    /// Roll a table by ID with full roll options
+    pub fn roll_with_options<R: Rng>(
+        &self,
+        table_id: &str,
+        opts: &RollOptions,
+        rng: &mut R,
+    ) -> Result<Vec<Drop>, RollError> {
+        let table = self
+            .get(table_id)
+            .ok_or_else(|| RollError::UnknownTable(table_id.to_string()))?;
+
+        table.roll(opts, rng, self, 0)
+    }
+
+    /// Roll a table `count` times and merge the results
+    ///
+    /// Currency drops with the same ID are summed into the first one rolled;
+    /// items and uniques are kept separate, in roll order.
+    pub fn roll_many<R: Rng>(
+        &self,
+        table_id: &str,
+        opts: &RollOptions,
+        count: u32,
+        rng: &mut R,
     ) -> Result<Vec<Drop>, RollError> {
         let table = self
             .get(table_id)
             .ok_or_else(|| RollError::UnknownTable(table_id.to_string()))?;
 
-        table.roll(rarity_mult, quantity_mult, level, rng, self, 0)
+        let mut drops: Vec<Drop> = Vec::new();
+        let mut currency_index: HashMap<String, usize> = HashMap::new();
+        for _ in 0..count {
+            for drop in table.roll(opts, rng, self, 0)? {
+                let Drop::Currency { id, count } = drop else {
+                    drops.push(drop);
+                    continue;
+                };
+                match currency_index.get(&id) {
+                    Some(&index) => {
+                        if let Drop::Currency { count: total, .. } = &mut drops[index] {
+                            *total = total.saturating_add(count);
+                        }
+                    }
+                    None => {
+                        currency_index.insert(id.clone(), drops.len());
+                        drops.push(Drop::Currency { id, count });
+                    }
+                }
+            }
+        }
+        Ok(drops)
     }
 }
 
@@ -346,6 +401,56 @@ min_level = 50
         assert!(drops.is_empty());
     }
 
+    #[test]
+    fn test_roll_many_merges_currencies() {
+        use crate::drop::DropsExt;
+        use rand::SeedableRng;
+
+        let dir = TempDir::new().unwrap();
+        create_test_table(
+            dir.path(),
+            "mixed",
+            r#"
+[table]
+id = "mixed"
+
+[[table.rolls]]
+count = 2
+weight = 100
+
+[[entries]]
+type = "currency"
+id = "gold"
+count = [10, 10]
+weight = 50
+
+[[entries]]
+type = "item"
+base_type = "iron_sword"
+weight = 50
+"#,
+        );
+
+        let registry = DropTableRegistry::load(dir.path()).unwrap();
+        let opts = RollOptions {
+            level: 10,
+            ..Default::default()
+        };
+        let roll = |seed: u64| {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            registry.roll_many("mixed", &opts, 50, &mut rng).unwrap()
+        };
+        let drops = roll(42);
+        assert_eq!(drops, roll(42));
+
+        // 100 entry rolls: every one is either an item or 10 gold
+        let currencies = drops.get_currencies();
+        assert_eq!(currencies.len(), 1);
+        let items = drops.get_items().len() as u32;
+        assert!(items > 0);
+        assert_eq!(currencies[0].count + items * 10, 1000);
+    }
+
     #[test]
     fn test_unknown_table_error() {
         let registry = DropTableRegistry::new();
@@ -477,6 +582,52 @@ weight = 100
         assert!(avg > 18.0 && avg < 22.0, "Average was {}", avg);
     }
 
+    #[test]
+    fn test_currency_specific_quantity_mult() {
+        let dir = TempDir::new().unwrap();
+        create_test_table(
+            dir.path(),
+            "currency",
+            r#"
+[table]
+id = "currency"
+
+[[entries]]
+type = "currency"
+id = "gold"
+count = 10
+weight = 50
+
+[[entries]]
+type = "currency"
+id = "chaos"
+count = 10
+weight = 50
+"#,
+        );
+
+        let registry = DropTableRegistry::load(dir.path()).unwrap();
+        let mut rng = rand::thread_rng();
+        let opts = RollOptions {
+            quantity_mult: 1.5,
+            currency_quantity_mult: HashMap::from([("gold".to_string(), 2.0)]),
+            ..Default::default()
+        };
+
+        // 10 per drop: gold gets 1.5 * 2.0, chaos only the global 1.5
+        let mut seen = HashMap::new();
+        for _ in 0..100 {
+            let drops = registry.roll_with_options("currency", &opts, &mut rng);
+            for drop in drops.unwrap() {
+                if let Drop::Currency { id, count } = drop {
+                    seen.insert(id, count);
+                }
+            }
+        }
+        assert_eq!(seen["gold"], 30);
+        assert_eq!(seen["chaos"], 15);
+    }
+
     #[test]
     fn test_cycle_detection() {
         let dir = TempDir::new().unwrap();
@@ -513,4 +664,171 @@ weight = 100
         let result = registry.roll("table_a", 1.0, 1.0, 10, &mut rng);
         assert!(matches!(result, Err(RollError::CycleDetected(_))));
     }
+
+    #[test]
+    fn test_expected_currency_nested() {
+        let dir = TempDir::new().unwrap();
+        create_test_table(
+            dir.path(),
+            "outer",
+            r#"
+[table]
+id = "outer"
+
+[[table.rolls]]
+count = 1
+weight = 1
+
+[[table.rolls]]
+count = 2
+weight = 1
+
+[[entries]]
+type = "no_drop"
+weight = 50
+
+[[entries]]
+type = "table"
+id = "inner"
+weight = 50
+"#,
+        );
+        create_test_table(
+            dir.path(),
+            "inner",
+            r#"
+[table]
+id = "inner"
+
+[[entries]]
+type = "currency"
+id = "chaos"
+count = 10
+weight = 100
+"#,
+        );
+
+        let registry = DropTableRegistry::load(dir.path()).unwrap();
+        let table = registry.get("outer").unwrap();
+        let expected = table
+            .expected_currency(&crate::RollOptions::default(), &registry)
+            .unwrap();
+
+        // 1.5 average rolls * 50% selection * 10 per drop
+        assert!((expected["chaos"] - 7.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_table_entry_rolls() {
+        let dir = TempDir::new().unwrap();
+        create_test_table(
+            dir.path(),
+            "boss",
+            r#"
+[table]
+id = "boss"
+
+[[entries]]
+type = "table"
+id = "rare_pool"
+rolls = 3
+weight = 100
+"#,
+        );
+        create_test_table(
+            dir.path(),
+            "rare_pool",
+            r#"
+[table]
+id = "rare_pool"
+
+[[entries]]
+type = "item"
+base_type = "iron_sword"
+weight = 50
+
+[[entries]]
+type = "no_drop"
+weight = 50
+"#,
+        );
+
+        let registry = DropTableRegistry::load(dir.path()).unwrap();
+        let mut rng = rand::thread_rng();
+        let iterations = 1000;
+        let mut total = 0;
+        for _ in 0..iterations {
+            let drops = registry.roll("boss", 1.0, 1.0, 10, &mut rng).unwrap();
+            assert!(drops.len() <= 3);
+            total += drops.len();
+        }
+
+        // 3 rolls * 50% item chance
+        let avg = total as f64 / iterations as f64;
+        assert!(avg > 1.35 && avg < 1.65, "Average was {}", avg);
+        let expected = registry
+            .get("boss")
+            .unwrap()
+            .expected_item_count(&crate::RollOptions::default(), &registry)
+            .unwrap();
+        assert!((expected - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_expected_item_count() {
+        let dir = TempDir::new().unwrap();
+        create_test_table(
+            dir.path(),
+            "mobs",
+            r#"
+[table]
+id = "mobs"
+
+[[table.rolls]]
+count = 1
+weight = 1
+
+[[table.rolls]]
+count = 2
+weight = 1
+
+[[entries]]
+type = "no_drop"
+weight = 50
+
+[[entries]]
+type = "table"
+id = "gear"
+weight = 50
+
+[[entries]]
+type = "item"
+base_type = "crown"
+weight = 100
+min_level = 50
+"#,
+        );
+        create_test_table(
+            dir.path(),
+            "gear",
+            r#"
+[table]
+id = "gear"
+
+[[entries]]
+type = "item"
+base_type = "iron_sword"
+weight = 100
+"#,
+        );
+
+        let registry = DropTableRegistry::load(dir.path()).unwrap();
+        let table = registry.get("mobs").unwrap();
+        let expected = table
+            .expected_item_count(&crate::RollOptions::default(), &registry)
+            .unwrap();
+
+        // 1.5 average rolls * 50% selection; the level 50 entry is filtered
+        assert!((expected - 0.75).abs() < 1e-9);
+    }
 }
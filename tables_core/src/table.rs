@@ -2,6 +2,41 @@ use crate::config::{CountConfig, EntryConfig, TableFileConfig};
 use crate::drop::Drop;
 use crate::RollError;
 use rand::Rng;
+use std::collections::HashMap;
+
+/// Roll parameters for rolling or analyzing a table
+#[derive(Debug, Clone)]
+pub struct RollOptions {
+    pub rarity_mult: f64,
+    pub quantity_mult: f64,
+    pub level: u32,
+    /// Extra quantity for specific currency ids (e.g. "increased gold found"),
+    /// multiplied with `quantity_mult`
+    pub currency_quantity_mult: HashMap<String, f64>,
+}
+
+impl Default for RollOptions {
+    fn default() -> Self {
+        RollOptions {
+            rarity_mult: 1.0,
+            quantity_mult: 1.0,
+            level: 1,
+            currency_quantity_mult: HashMap::new(),
+        }
+    }
+}
+
+impl RollOptions {
+    /// Combined quantity multiplier for a currency id
+    pub fn currency_quantity(&self, currency_id: &str) -> f64 {
+        self.quantity_mult
+            * self
+                .currency_quantity_mult
+                .get(currency_id)
+                .copied()
+                .unwrap_or(1.0)
+    }
+}
 
 /// A drop table with weighted roll counts and entries
 #[derive(Debug, Clone)]
@@ -35,20 +70,79 @@ enum EntryType {
     },
     Currency {
         id: String,
-        count: CountRange,
+        count: CurrencyCount,
     },
     Unique {
         id: String,
     },
     Table {
         id: String,
+        /// Independent rolls of the referenced table per selection
+        rolls: u32,
     },
 }
 
-#[derive(Debug, Clone, Copy)]
-struct CountRange {
-    min: u32,
-    max: u32,
+/// How many of a currency an entry drops, before quantity scaling
+#[derive(Debug, Clone)]
+enum CurrencyCount {
+    /// Uniform between min and max (inclusive)
+    Range { min: u32, max: u32 },
+    /// `(value, weight)` pairs
+    Weighted(Vec<(u32, u32)>),
+}
+
+impl CurrencyCount {
+    fn from_config(config: CountConfig) -> Self {
+        match config {
+            CountConfig::Weighted { weights } => {
+                CurrencyCount::Weighted(weights.into_iter().map(|[v, w]| (v, w)).collect())
+            }
+            other => CurrencyCount::Range {
+                min: other.min(),
+                max: other.max(),
+            },
+        }
+    }
+
+    fn roll<R: Rng>(&self, rng: &mut R) -> u32 {
+        match self {
+            CurrencyCount::Range { min, max } => rng.gen_range(*min..=*max),
+            CurrencyCount::Weighted(weights) => {
+                // Summed as u64 so large weights can't overflow
+                let total_weight: u64 = weights.iter().map(|&(_, w)| w as u64).sum();
+                if total_weight == 0 {
+                    return 0;
+                }
+
+                let mut roll = rng.gen_range(0..total_weight);
+                for &(value, weight) in weights {
+                    if roll < weight as u64 {
+                        return value;
+                    }
+                    roll -= weight as u64;
+                }
+
+                weights.last().map(|(v, _)| *v).unwrap_or(0)
+            }
+        }
+    }
+
+    fn average(&self) -> f64 {
+        match self {
+            CurrencyCount::Range { min, max } => (min + max) as f64 / 2.0,
+            CurrencyCount::Weighted(weights) => {
+                let total_weight: u64 = weights.iter().map(|&(_, w)| w as u64).sum();
+                if total_weight == 0 {
+                    return 0.0;
+                }
+                weights
+                    .iter()
+                    .map(|&(v, w)| v as f64 * w as f64)
+                    .sum::<f64>()
+                    / total_weight as f64
+            }
+        }
+    }
 }
 
 impl DropTable {
@@ -88,9 +182,7 @@ impl DropTable {
     /// Roll this table and return the drops
     pub fn roll<R: Rng>(
         &self,
-        rarity_mult: f64,
-        quantity_mult: f64,
-        level: u32,
+        opts: &RollOptions,
         rng: &mut R,
         registry: &crate::DropTableRegistry,
         depth: u32,
@@ -104,7 +196,7 @@ impl DropTable {
         let base_rolls = self.select_roll_count(rng);
 
         // Apply quantity multiplier with fractional chance
-        let roll_count = apply_quantity_mult(base_rolls, quantity_mult, rng);
+        let roll_count = apply_quantity_mult(base_rolls, opts.quantity_mult, rng);
 
         let mut drops = Vec::new();
 
@@ -113,7 +205,7 @@ impl DropTable {
             let valid_entries: Vec<&Entry> = self
                 .entries
                 .iter()
-                .filter(|e| e.level_valid(level))
+                .filter(|e| e.level_valid(opts.level))
                 .collect();
 
             if valid_entries.is_empty() {
@@ -123,7 +215,7 @@ impl DropTable {
             // Calculate effective weights with rarity bonus
             let weights: Vec<f64> = valid_entries
                 .iter()
-                .map(|e| e.weight as f64 + e.rarity_bonus as f64 * rarity_mult)
+                .map(|e| e.weight as f64 + e.rarity_bonus as f64 * opts.rarity_mult)
                 .collect();
 
             let total_weight: f64 = weights.iter().sum();
@@ -159,8 +251,9 @@ impl DropTable {
                     drops.push(Drop::Unique { id: id.clone() });
                 }
                 EntryType::Currency { id, count } => {
-                    let base_count = rng.gen_range(count.min..=count.max);
-                    let final_count = apply_quantity_mult(base_count, quantity_mult, rng);
+                    let base_count = count.roll(rng);
+                    let final_count =
+                        apply_quantity_mult(base_count, opts.currency_quantity(id), rng);
                     if final_count > 0 {
                         drops.push(Drop::Currency {
                             id: id.clone(),
@@ -168,38 +261,173 @@ impl DropTable {
                         });
                     }
                 }
-                EntryType::Table { id } => {
+                EntryType::Table { id, rolls } => {
+                    let nested_table = registry
+                        .get(id)
+                        .ok_or_else(|| RollError::UnknownTable(id.clone()))?;
+                    for _ in 0..*rolls {
+                        let nested_drops = nested_table.roll(opts, rng, registry, depth + 1)?;
+                        drops.extend(nested_drops);
+                    }
+                }
+            }
+        }
+
+        Ok(drops)
+    }
+
+    /// Expected currency yield of one roll of this table, by currency id
+    ///
+    /// Computed analytically from roll count weights, entry weights (with
+    /// rarity bonus), currency count ranges and the quantity multiplier.
+    /// Nested tables contribute their own expectation scaled by their
+    /// selection probability.
+    pub fn expected_currency(
+        &self,
+        opts: &RollOptions,
+        registry: &crate::DropTableRegistry,
+    ) -> Result<HashMap<String, f64>, RollError> {
+        let mut expected = HashMap::new();
+        self.accumulate_expected_currency(opts, registry, 1.0, 0, &mut expected)?;
+        Ok(expected)
+    }
+
+    fn accumulate_expected_currency(
+        &self,
+        opts: &RollOptions,
+        registry: &crate::DropTableRegistry,
+        scale: f64,
+        depth: u32,
+        expected: &mut HashMap<String, f64>,
+    ) -> Result<(), RollError> {
+        const MAX_DEPTH: u32 = 10;
+        if depth > MAX_DEPTH {
+            return Err(RollError::CycleDetected(self.id.clone()));
+        }
+
+        let expected_rolls = self.expected_roll_count() * opts.quantity_mult;
+
+        for (entry, chance) in self.selection_chances(opts) {
+            let selections = scale * expected_rolls * chance;
+            match &entry.entry_type {
+                EntryType::Currency { id, count } => {
+                    let average = count.average();
+                    *expected.entry(id.clone()).or_insert(0.0) +=
+                        selections * average * opts.currency_quantity(id);
+                }
+                EntryType::Table { id, rolls } => {
                     let nested_table = registry
                         .get(id)
                         .ok_or_else(|| RollError::UnknownTable(id.clone()))?;
-                    let nested_drops = nested_table.roll(
-                        rarity_mult,
-                        quantity_mult,
-                        level,
-                        rng,
+                    nested_table.accumulate_expected_currency(
+                        opts,
                         registry,
+                        selections * *rolls as f64,
                         depth + 1,
+                        expected,
                     )?;
-                    drops.extend(nested_drops);
                 }
+                EntryType::NoDrop | EntryType::Item { .. } | EntryType::Unique { .. } => {}
             }
         }
 
-        Ok(drops)
+        Ok(())
+    }
+
+    /// Expected number of item drops (items and uniques, not currency) from
+    /// one roll of this table
+    ///
+    /// Accounts for roll count weights, level filtering, rarity bonus and
+    /// the quantity multiplier, recursing into nested tables.
+    pub fn expected_item_count(
+        &self,
+        opts: &RollOptions,
+        registry: &crate::DropTableRegistry,
+    ) -> Result<f64, RollError> {
+        self.accumulate_expected_items(opts, registry, 0)
+    }
+
+    fn accumulate_expected_items(
+        &self,
+        opts: &RollOptions,
+        registry: &crate::DropTableRegistry,
+        depth: u32,
+    ) -> Result<f64, RollError> {
+        const MAX_DEPTH: u32 = 10;
+        if depth > MAX_DEPTH {
+            return Err(RollError::CycleDetected(self.id.clone()));
+        }
+
+        let expected_rolls = self.expected_roll_count() * opts.quantity_mult;
+
+        let mut items_per_roll = 0.0;
+        for (entry, chance) in self.selection_chances(opts) {
+            match &entry.entry_type {
+                EntryType::Item { .. } | EntryType::Unique { .. } => items_per_roll += chance,
+                EntryType::Table { id, rolls } => {
+                    let nested_table = registry
+                        .get(id)
+                        .ok_or_else(|| RollError::UnknownTable(id.clone()))?;
+                    let nested =
+                        nested_table.accumulate_expected_items(opts, registry, depth + 1)?;
+                    items_per_roll += chance * *rolls as f64 * nested;
+                }
+                EntryType::NoDrop | EntryType::Currency { .. } => {}
+            }
+        }
+
+        Ok(expected_rolls * items_per_roll)
+    }
+
+    /// Level-valid entries with their chance of being selected by one roll
+    fn selection_chances(&self, opts: &RollOptions) -> Vec<(&Entry, f64)> {
+        let valid_entries: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|e| e.level_valid(opts.level))
+            .collect();
+        let weights: Vec<f64> = valid_entries
+            .iter()
+            .map(|e| e.weight as f64 + e.rarity_bonus as f64 * opts.rarity_mult)
+            .collect();
+        let total_weight: f64 = weights.iter().sum();
+        if total_weight <= 0.0 {
+            return Vec::new();
+        }
+
+        valid_entries
+            .into_iter()
+            .zip(weights)
+            .map(|(entry, weight)| (entry, weight / total_weight))
+            .collect()
+    }
+
+    /// Average base roll count, weighted by roll option weights
+    fn expected_roll_count(&self) -> f64 {
+        let total_weight: u64 = self.rolls.iter().map(|r| r.weight as u64).sum();
+        if total_weight == 0 {
+            return 1.0;
+        }
+        self.rolls
+            .iter()
+            .map(|r| r.count as f64 * r.weight as f64)
+            .sum::<f64>()
+            / total_weight as f64
     }
 
     fn select_roll_count<R: Rng>(&self, rng: &mut R) -> u32 {
-        let total_weight: u32 = self.rolls.iter().map(|r| r.weight).sum();
+        // Summed as u64 so large weights can't overflow
+        let total_weight: u64 = self.rolls.iter().map(|r| r.weight as u64).sum();
         if total_weight == 0 {
             return 1;
         }
 
         let mut roll = rng.gen_range(0..total_weight);
         for option in &self.rolls {
-            if roll < option.weight {
+            if roll < option.weight as u64 {
                 return option.count;
             }
-            roll -= option.weight;
+            roll -= option.weight as u64;
         }
 
         self.rolls.last().map(|r| r.count).unwrap_or(1)
@@ -221,14 +449,12 @@ impl Entry {
                 let count = config.count.unwrap_or(CountConfig::Single(1));
                 EntryType::Currency {
                     id: config.id.unwrap_or_default(),
-                    count: CountRange {
-                        min: count.min(),
-                        max: count.max(),
-                    },
+                    count: CurrencyCount::from_config(count),
                 }
             }
             "table" => EntryType::Table {
                 id: config.id.unwrap_or_default(),
+                rolls: config.rolls.unwrap_or(1),
             },
             _ => {
                 return Err(RollError::InvalidEntryType(config.entry_type));
@@ -275,6 +501,8 @@ fn apply_quantity_mult<R: Rng>(base: u32, mult: f64, rng: &mut R) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
 
     #[test]
     fn test_apply_quantity_mult_no_fraction() {
@@ -330,4 +558,28 @@ mod tests {
         assert!(entry.level_valid(0));
         assert!(entry.level_valid(100));
     }
+
+    #[test]
+    fn test_weighted_currency_count() {
+        #[derive(serde::Deserialize)]
+        struct Wrapper {
+            count: CountConfig,
+        }
+        let config: Wrapper = toml::from_str("count = { weights = [[10, 3], [100, 1]] }").unwrap();
+        assert_eq!((config.count.min(), config.count.max()), (10, 100));
+
+        let count = CurrencyCount::from_config(config.count);
+        assert!((count.average() - 32.5).abs() < 1e-9);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let rolls: Vec<u32> = (0..1000).map(|_| count.roll(&mut rng)).collect();
+        assert!(rolls.iter().all(|&c| c == 10 || c == 100));
+        let big = rolls.iter().filter(|&&c| c == 100).count();
+        assert!(big > 150 && big < 350, "100 rolled {} times", big);
+
+        // Weights summing past u32::MAX still roll and average correctly
+        let huge = CurrencyCount::Weighted(vec![(1, u32::MAX), (3, u32::MAX)]);
+        assert!((huge.average() - 2.0).abs() < 1e-9);
+        assert!((0..100).all(|_| matches!(huge.roll(&mut rng), 1 | 3)));
+    }
 }